@@ -0,0 +1,153 @@
+use std::{collections::BTreeMap, fs, path::Path};
+
+use anyhow::{Context, Result};
+use aoc2023_core::{Answer, DayResult};
+use owo_colors::OwoColorize;
+
+use crate::style::Styles;
+
+// Answers of a day as written in the answers file, keyed by "partN"
+type Table = BTreeMap<String, Answer>;
+
+// Answers of a day, keyed by part
+pub type Answers = BTreeMap<u8, Answer>;
+
+// Expected answers, read from a file like
+//
+//   [2023.day01]
+//   part1 = 54927
+//   part2 = 54581
+//
+// Days and parts without an answer (yet) are left out.
+#[derive(Debug, Default)]
+pub struct Expected(BTreeMap<(u16, u8), Answers>);
+
+impl Expected {
+    pub fn load(path: &Path) -> Result<Self> {
+        let s = fs::read_to_string(path)
+            .with_context(|| format!("failed to read answers {}", path.display()))?;
+        let table = toml::from_str::<BTreeMap<String, BTreeMap<String, Table>>>(&s)
+            .with_context(|| format!("failed to parse answers {}", path.display()))?;
+
+        let mut expected = BTreeMap::new();
+        for (year, days) in table {
+            let year = year
+                .parse::<u16>()
+                .with_context(|| format!("invalid year in {}: {}", path.display(), year))?;
+            for (day, parts) in days {
+                let day = day
+                    .strip_prefix("day")
+                    .and_then(|day| day.parse::<u8>().ok())
+                    .with_context(|| format!("invalid day of {}: {}", year, day))?;
+                let mut answers = Answers::new();
+                for (part, answer) in parts {
+                    let part = part
+                        .strip_prefix("part")
+                        .and_then(|part| part.parse::<u8>().ok())
+                        .with_context(|| format!("invalid part of day {}: {}", day, part))?;
+                    answers.insert(part, answer);
+                }
+                expected.insert((year, day), answers);
+            }
+        }
+        Ok(Expected(expected))
+    }
+
+    // Whether the day of `outcome` succeeded with all the expected answers,
+    // if it has any
+    pub fn check(&self, outcome: &DayResult) -> Option<bool> {
+        let expected = self.0.get(&(outcome.year, outcome.day))?;
+        Some(
+            outcome.result.is_ok()
+                && expected
+                    .iter()
+                    .all(|(part, answer)| outcome.answers.get(part) == Some(answer)),
+        )
+    }
+
+    // Parts of the day of `outcome` without the expected answer, e.g.
+    // "part 2 is 46, expected 47"
+    pub fn mismatches(&self, outcome: &DayResult) -> Vec<String> {
+        let Some(expected) = self.0.get(&(outcome.year, outcome.day)) else {
+            return vec![];
+        };
+        expected
+            .iter()
+            .filter_map(|(part, answer)| match outcome.answers.get(part) {
+                Some(actual) if actual == answer => None,
+                Some(actual) => Some(format!("part {} is {}, expected {}", part, actual, answer)),
+                None => Some(format!("part {} has no answer, expected {}", part, answer)),
+            })
+            .collect()
+    }
+
+    // Check the answers of `outcome` against the expected ones, printing a
+    // line per part. Returns whether all answers match, and at least one
+    // part was checked.
+    pub fn verify(&self, outcome: &DayResult, styles: &Styles) -> bool {
+        let day = format!("day {:02}", outcome.day);
+        let day = day.style(styles.header);
+        if let Err(e) = &outcome.result {
+            println!(
+                "{} {}: {} {:#}",
+                styles.marker(false),
+                day,
+                "failed:".style(styles.fail),
+                e
+            );
+            return false;
+        }
+        let Some(expected) = self.0.get(&(outcome.year, outcome.day)) else {
+            println!("{} {}: no expected answers", styles.marker(false), day);
+            return false;
+        };
+
+        let mut ok = true;
+        for (part, answer) in expected {
+            match outcome.answers.get(part) {
+                Some(actual) if actual == answer => {
+                    println!(
+                        "{} {} part {}: {} ok",
+                        styles.marker(true),
+                        day,
+                        part,
+                        styles.answer(actual)
+                    )
+                }
+                Some(actual) => {
+                    println!(
+                        "{} {} part {}: {}, expected {}",
+                        styles.marker(false),
+                        day,
+                        part,
+                        actual.style(styles.fail),
+                        styles.answer(answer)
+                    );
+                    ok = false;
+                }
+                None => {
+                    println!(
+                        "{} {} part {}: no answer, expected {}",
+                        styles.marker(false),
+                        day,
+                        part,
+                        styles.answer(answer)
+                    );
+                    ok = false;
+                }
+            }
+        }
+        for (part, actual) in &outcome.answers {
+            if !expected.contains_key(part) {
+                println!(
+                    "{} {} part {}: {} not checked",
+                    "-".style(styles.muted),
+                    day,
+                    part,
+                    styles.answer(actual)
+                );
+            }
+        }
+        ok
+    }
+}
@@ -0,0 +1,72 @@
+use std::{collections::BTreeMap, fs, io, path::Path};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+// What a day is expected to cost
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Baseline {
+    // allocations of solving the day, parsing and both parts
+    pub allocations: u64,
+}
+
+// Baselines of days, read from a file like
+//
+//   [2023.day16]
+//   allocations = 23
+//
+// written by `bench --save-baselines`. Days without a baseline aren't
+// checked.
+#[derive(Debug, Default)]
+pub struct Baselines(BTreeMap<(u16, u8), Baseline>);
+
+// Baselines as written in the file, keyed by year and then "dayNN"
+type Table = BTreeMap<String, BTreeMap<String, Baseline>>;
+
+impl Baselines {
+    // Baselines in the file at `path`, or none if there's no file
+    pub fn load(path: &Path) -> Result<Self> {
+        let s = match fs::read_to_string(path) {
+            Ok(s) => s,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Baselines::default()),
+            Err(e) => return Err(e).with_context(|| format!("failed to read {}", path.display())),
+        };
+        let table = toml::from_str::<Table>(&s)
+            .with_context(|| format!("failed to parse {}", path.display()))?;
+
+        let mut baselines = BTreeMap::new();
+        for (year, days) in table {
+            let year = year
+                .parse::<u16>()
+                .with_context(|| format!("invalid year in {}: {}", path.display(), year))?;
+            for (day, baseline) in days {
+                let day = day
+                    .strip_prefix("day")
+                    .and_then(|day| day.parse::<u8>().ok())
+                    .with_context(|| format!("invalid day of {}: {}", year, day))?;
+                baselines.insert((year, day), baseline);
+            }
+        }
+        Ok(Baselines(baselines))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let mut table = Table::new();
+        for (&(year, day), &baseline) in &self.0 {
+            table
+                .entry(year.to_string())
+                .or_default()
+                .insert(format!("day{:02}", day), baseline);
+        }
+        let s = toml::to_string(&table)?;
+        fs::write(path, s).with_context(|| format!("failed to write {}", path.display()))
+    }
+
+    pub fn get(&self, year: u16, day: u8) -> Option<Baseline> {
+        self.0.get(&(year, day)).copied()
+    }
+
+    pub fn insert(&mut self, year: u16, day: u8, baseline: Baseline) {
+        self.0.insert((year, day), baseline);
+    }
+}
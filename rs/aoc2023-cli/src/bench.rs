@@ -0,0 +1,218 @@
+use std::{path::Path, time::Duration};
+
+use anyhow::Result;
+use aoc2023_core::memory::AllocStats;
+
+use crate::{
+    baselines::{Baseline, Baselines},
+    runner::Day,
+};
+
+// How `bench` checks the allocations of days against their baselines
+#[derive(Debug)]
+pub struct Guard<'a> {
+    // baselines file, e.g. baselines.toml
+    pub path: &'a Path,
+    // most growth of a day's allocations over its baseline, in percent
+    pub threshold: f64,
+    // write the allocations of the days as their baselines instead
+    pub save: bool,
+}
+
+// Mean timings of a day over all iterations, and the allocations of the last
+// one, if they're counted
+#[derive(Debug, Default)]
+struct Timing {
+    year: u16,
+    day: u8,
+    parse: Option<Duration>,
+    part1: Option<Duration>,
+    part2: Option<Duration>,
+    total: Duration,
+    allocs: [Option<AllocStats>; 4],
+}
+
+// Solve each day `iterations` times and print a table of the mean time of
+// parsing, each part and the whole day, with a grand total at the bottom.
+// Built with the `alloc-stats` feature, a second table has the number of
+// allocations and the peak of the bytes allocated at once of each.
+//
+// Their allocations are then checked against the baselines file, failing
+// days that allocate more than `guard.threshold` percent over their
+// baseline, or saved to it.
+//
+// Days are solved one at a time so they don't compete for CPUs.
+pub fn run(days: &[Day], iterations: u32, guard: &Guard) -> Result<bool> {
+    anyhow::ensure!(iterations > 0, "iterations must be positive");
+    anyhow::ensure!(
+        !guard.save || cfg!(feature = "alloc-stats"),
+        "allocations aren't counted without the alloc-stats feature"
+    );
+
+    let timings = days
+        .iter()
+        .map(|day| bench(day, iterations))
+        .collect::<Vec<_>>();
+
+    let mut ok = true;
+    let mut totals = Timing::default();
+    let mut allocs = vec![];
+    println!(
+        "{:>4} {:>12} {:>12} {:>12} {:>12}",
+        "day", "parse", "part 1", "part 2", "total"
+    );
+    for timing in timings {
+        let timing = match timing {
+            Ok(timing) => timing,
+            Err(e) => {
+                println!("{:#}", e);
+                ok = false;
+                continue;
+            }
+        };
+        println!(
+            "{:>4} {:>12} {:>12} {:>12} {:>12}",
+            format!("{:02}", timing.day),
+            format_time(timing.parse),
+            format_time(timing.part1),
+            format_time(timing.part2),
+            format_time(Some(timing.total))
+        );
+        totals.parse = Some(totals.parse.unwrap_or_default() + timing.parse.unwrap_or_default());
+        totals.part1 = Some(totals.part1.unwrap_or_default() + timing.part1.unwrap_or_default());
+        totals.part2 = Some(totals.part2.unwrap_or_default() + timing.part2.unwrap_or_default());
+        totals.total += timing.total;
+        if timing.allocs.iter().any(Option::is_some) {
+            allocs.push((timing.year, timing.day, timing.allocs));
+        }
+    }
+    println!(
+        "{:>4} {:>12} {:>12} {:>12} {:>12}",
+        "all",
+        format_time(totals.parse),
+        format_time(totals.part1),
+        format_time(totals.part2),
+        format_time(Some(totals.total))
+    );
+
+    if !allocs.is_empty() {
+        println!("\nallocations / peak bytes allocated at once");
+        println!(
+            "{:>4} {:>20} {:>20} {:>20} {:>20}",
+            "day", "parse", "part 1", "part 2", "total"
+        );
+        for &(_, day, allocs) in &allocs {
+            let [parse, part1, part2, total] = allocs.map(format_alloc);
+            println!(
+                "{:>4} {:>20} {:>20} {:>20} {:>20}",
+                format!("{:02}", day),
+                parse,
+                part1,
+                part2,
+                total
+            );
+        }
+    }
+
+    let mut baselines = Baselines::load(guard.path)?;
+    let mut regressions = vec![];
+    for &(year, day, [.., total]) in &allocs {
+        let Some(total) = total else { continue };
+        if guard.save {
+            let baseline = Baseline {
+                allocations: total.allocations,
+            };
+            baselines.insert(year, day, baseline);
+        } else if let Some(baseline) = baselines.get(year, day) {
+            let limit = baseline.allocations as f64 * (1.0 + guard.threshold / 100.0);
+            if total.allocations as f64 > limit {
+                regressions.push((day, baseline.allocations, total.allocations));
+            }
+        }
+    }
+    if guard.save {
+        baselines.save(guard.path)?;
+        println!(
+            "\nsaved the allocations as baselines to {}",
+            guard.path.display()
+        );
+    }
+    if !regressions.is_empty() {
+        println!(
+            "\nallocations over their baseline by more than {}%",
+            guard.threshold
+        );
+        for (day, baseline, allocations) in regressions {
+            let growth = (allocations - baseline) as f64 / baseline.max(1) as f64 * 100.0;
+            println!(
+                "{:>4} {} allocations, up {:.1}% from {}",
+                format!("{:02}", day),
+                allocations,
+                growth,
+                baseline
+            );
+        }
+        ok = false;
+    }
+    Ok(ok)
+}
+
+fn bench(day: &Day, iterations: u32) -> Result<Timing> {
+    let mut timing = Timing {
+        year: day.year,
+        day: day.day,
+        ..Timing::default()
+    };
+    for _ in 0..iterations {
+        let outcome = day.run();
+        if let Err(e) = outcome.result {
+            anyhow::bail!("day {:02} failed: {:#}", day.day, e);
+        }
+        timing.parse = outcome
+            .parse_time
+            .map(|t| timing.parse.unwrap_or_default() + t);
+        timing.part1 = outcome
+            .part1_time
+            .map(|t| timing.part1.unwrap_or_default() + t);
+        timing.part2 = outcome
+            .part2_time
+            .map(|t| timing.part2.unwrap_or_default() + t);
+        timing.total += outcome.elapsed;
+        timing.allocs = [
+            outcome.parse_alloc,
+            outcome.part1_alloc,
+            outcome.part2_alloc,
+            outcome.alloc,
+        ];
+    }
+    timing.parse = timing.parse.map(|t| t / iterations);
+    timing.part1 = timing.part1.map(|t| t / iterations);
+    timing.part2 = timing.part2.map(|t| t / iterations);
+    timing.total /= iterations;
+    Ok(timing)
+}
+
+fn format_time(time: Option<Duration>) -> String {
+    time.map_or("-".to_string(), |time| format!("{:.2?}", time))
+}
+
+// Allocations and peak bytes, e.g. `1520 / 3.42 MiB`
+fn format_alloc(alloc: Option<AllocStats>) -> String {
+    let Some(alloc) = alloc else {
+        return "-".to_string();
+    };
+    let mut peak = alloc.peak_bytes as f64;
+    let mut unit = "B";
+    for next in ["KiB", "MiB", "GiB"] {
+        if peak < 1024.0 {
+            break;
+        }
+        peak /= 1024.0;
+        unit = next;
+    }
+    if unit == "B" {
+        format!("{} / {} B", alloc.allocations, alloc.peak_bytes)
+    } else {
+        format!("{} / {:.2} {}", alloc.allocations, peak, unit)
+    }
+}
@@ -0,0 +1,192 @@
+use std::{
+    collections::BTreeMap,
+    fmt, fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::config::Config;
+
+// Name of the file next to the inputs of a year recording what was fetched
+const FILE_NAME: &str = "cache.toml";
+
+// Record of a fetched input, to tell later whether the file is still intact
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Entry {
+    pub sha256: String,
+    pub size: u64,
+    // ETag of the download, if adventofcode.com sent one
+    pub etag: Option<String>,
+    // when the input was fetched, RFC 3339
+    pub fetched: String,
+}
+
+impl Entry {
+    pub fn new(input: &[u8], etag: Option<String>) -> Self {
+        Entry {
+            sha256: sha256(input),
+            size: input.len() as u64,
+            etag,
+            fetched: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+        }
+    }
+}
+
+// State of the input of a day, compared to when it was fetched
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Ok,
+    // fetched, but the file is gone or empty
+    Missing,
+    // not fetched, e.g. copied by hand
+    Untracked,
+    // shorter than fetched
+    Truncated,
+    // same size or longer than fetched, but a different checksum
+    Modified,
+}
+
+impl Status {
+    pub fn is_intact(self) -> bool {
+        matches!(self, Status::Ok | Status::Untracked)
+    }
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Status::Ok => "ok",
+            Status::Missing => "missing",
+            Status::Untracked => "untracked",
+            Status::Truncated => "truncated",
+            Status::Modified => "modified",
+        };
+        f.pad(s)
+    }
+}
+
+// Entries of the fetched inputs of a year, read from a file like
+//
+//   [day08]
+//   sha256 = "9f86d0..."
+//   size = 10714
+//   etag = "\"6c1e...\""
+//   fetched = "2023-12-08T05:00:02Z"
+#[derive(Debug)]
+pub struct Cache {
+    path: PathBuf,
+    entries: BTreeMap<String, Entry>,
+}
+
+impl Cache {
+    pub fn load(year: u16, config: &Config) -> Result<Self> {
+        let path = config.input_dir.join(year.to_string()).join(FILE_NAME);
+        let entries = match fs::read_to_string(&path) {
+            Ok(s) => {
+                toml::from_str(&s).with_context(|| format!("failed to parse {}", path.display()))?
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(e) => return Err(e).with_context(|| format!("failed to read {}", path.display())),
+        };
+        Ok(Cache { path, entries })
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let s = toml::to_string(&self.entries)?;
+        fs::write(&self.path, s).with_context(|| format!("failed to write {}", self.path.display()))
+    }
+
+    pub fn get(&self, day: u8) -> Option<&Entry> {
+        self.entries.get(&key(day))
+    }
+
+    pub fn insert(&mut self, day: u8, entry: Entry) {
+        self.entries.insert(key(day), entry);
+    }
+
+    // Status of the input of `day` at `path`, or None if it was neither
+    // fetched nor written
+    pub fn status(&self, day: u8, path: &Path) -> Result<Option<Status>> {
+        let input = match fs::read(path) {
+            Ok(input) => input,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => vec![],
+            Err(e) => return Err(e).with_context(|| format!("failed to read {}", path.display())),
+        };
+        let status = match self.get(day) {
+            // `new-day` creates empty inputs, to be fetched
+            None if input.is_empty() => return Ok(None),
+            None => Status::Untracked,
+            Some(_) if input.is_empty() => Status::Missing,
+            Some(entry) if entry.sha256 == sha256(&input) => Status::Ok,
+            Some(entry) if (input.len() as u64) < entry.size => Status::Truncated,
+            Some(_) => Status::Modified,
+        };
+        Ok(Some(status))
+    }
+}
+
+// Print the status of the inputs of `year`, and return whether they're all
+// intact
+pub fn report(year: u16, config: &Config) -> Result<bool> {
+    let cache = Cache::load(year, config)?;
+    let mut intact = true;
+    for day in 1..=25 {
+        let path = config.input_path(year, day);
+        let Some(status) = cache.status(day, &path)? else {
+            continue;
+        };
+        intact &= status.is_intact();
+        match cache.get(day) {
+            Some(entry) => println!(
+                "day {:02}: {:<9} {:>6} bytes, fetched {}",
+                day, status, entry.size, entry.fetched
+            ),
+            None => println!("day {:02}: {}", day, status),
+        }
+    }
+    Ok(intact)
+}
+
+fn key(day: u8) -> String {
+    format!("day{:02}", day)
+}
+
+fn sha256(input: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(input))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status() -> Result<()> {
+        let dir = std::env::temp_dir().join(format!("aoc2023-cache-{}", std::process::id()));
+        fs::create_dir_all(&dir)?;
+        let mut cache = Cache {
+            path: dir.join(FILE_NAME),
+            entries: BTreeMap::new(),
+        };
+        // inputs as committed, without a newline at the end
+        let input = b"467..114..\n...*......";
+        let path = dir.join("day03.txt");
+        fs::write(&path, input)?;
+        assert_eq!(cache.status(3, &path)?, Some(Status::Untracked));
+        assert_eq!(cache.status(4, &dir.join("day04.txt"))?, None);
+
+        cache.insert(3, Entry::new(input, None));
+        assert_eq!(cache.status(3, &path)?, Some(Status::Ok));
+        fs::write(&path, &input[..12])?;
+        assert_eq!(cache.status(3, &path)?, Some(Status::Truncated));
+        fs::write(&path, b"467..114..\n...#......")?;
+        assert_eq!(cache.status(3, &path)?, Some(Status::Modified));
+        fs::write(&path, b"")?;
+        assert_eq!(cache.status(3, &path)?, Some(Status::Missing));
+
+        fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+}
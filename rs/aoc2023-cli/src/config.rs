@@ -0,0 +1,162 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    sync::LazyLock,
+};
+
+use anyhow::{Context, Result};
+use aoc2023_core::{input, InputStore};
+use serde::{Deserialize, Deserializer};
+use tracing::Level;
+
+use crate::style::Theme;
+
+// Name of the config file looked up in the current directory, and then in
+// the repository root
+const FILE_NAME: &str = "aoc.toml";
+
+// Year of the puzzles, unless given otherwise
+pub const DEFAULT_YEAR: u16 = 2023;
+
+// Settings read from `aoc.toml`, all of them optional:
+//
+//   year = 2023
+//   input_dir = "input"                     # puzzle inputs, per year
+//   sample_dir = "sample"                   # sample inputs, per year
+//   answers_file = "answers.toml"           # answers checked by `verify`
+//   submissions_file = "submissions.toml"   # answers sent by `submit`
+//   baselines_file = "baselines.toml"       # allocations checked by `bench`
+//   session_file = "~/.config/aoc/session"  # adventofcode.com session cookie,
+//                                           # unless $AOC_SESSION is set or
+//                                           # `aoc2023 login` stored one
+//   days = [1, 2, 3]                        # days to run when none are given
+//   log_level = "warn"
+//   jobs = 4
+//   theme = "light"                         # colors of the output
+//
+// Relative paths are relative to the directory of the config file, and
+// default ones to $AOC_ROOT, or the repository root. Options given on the
+// command line take precedence over the config file.
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    // config file the settings were read from, if any
+    #[serde(skip)]
+    pub path: Option<PathBuf>,
+    pub year: Option<u16>,
+    pub input_dir: PathBuf,
+    pub sample_dir: PathBuf,
+    pub answers_file: PathBuf,
+    pub submissions_file: PathBuf,
+    pub baselines_file: PathBuf,
+    pub session_file: Option<PathBuf>,
+    pub days: Vec<u8>,
+    #[serde(deserialize_with = "deserialize_level")]
+    pub log_level: Option<Level>,
+    pub jobs: Option<usize>,
+    pub theme: Option<Theme>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            path: None,
+            year: None,
+            input_dir: root().join("input"),
+            sample_dir: root().join("sample"),
+            answers_file: root().join("answers.toml"),
+            submissions_file: root().join("submissions.toml"),
+            baselines_file: root().join("baselines.toml"),
+            session_file: None,
+            days: vec![],
+            log_level: None,
+            jobs: None,
+            theme: None,
+        }
+    }
+}
+
+impl Config {
+    // Load the config file at `path`, or look for `aoc.toml` in the current
+    // directory and the repository root. Without a config file, settings
+    // default to the layout of this repository.
+    pub fn load(path: Option<&Path>) -> Result<Self> {
+        let path = match path {
+            Some(path) => path.to_path_buf(),
+            None => {
+                let candidates = [PathBuf::from(FILE_NAME), root().join(FILE_NAME)];
+                match candidates.into_iter().find(|path| path.is_file()) {
+                    Some(path) => path,
+                    None => return Ok(Config::default()),
+                }
+            }
+        };
+
+        let s = fs::read_to_string(&path)
+            .with_context(|| format!("failed to read config {}", path.display()))?;
+        let mut config = toml::from_str::<Config>(&s)
+            .with_context(|| format!("failed to parse config {}", path.display()))?;
+
+        let dir = path.parent().unwrap_or(Path::new("."));
+        config.input_dir = resolve(dir, &config.input_dir);
+        config.sample_dir = resolve(dir, &config.sample_dir);
+        config.answers_file = resolve(dir, &config.answers_file);
+        config.submissions_file = resolve(dir, &config.submissions_file);
+        config.baselines_file = resolve(dir, &config.baselines_file);
+        config.session_file = config.session_file.map(|file| resolve(dir, &file));
+        config.path = Some(path);
+        Ok(config)
+    }
+
+    // Inputs and samples in the input and sample directories
+    pub fn inputs(&self) -> InputStore {
+        InputStore::default()
+            .input_dir(&self.input_dir)
+            .sample_dir(&self.sample_dir)
+    }
+
+    // Input of a day, e.g. input/2023/day01.txt
+    pub fn input_path(&self, year: u16, day: u8) -> PathBuf {
+        self.inputs().input_path(year, day)
+    }
+
+    // Sample input of a day, e.g. sample/2023/day01.txt
+    pub fn sample_path(&self, year: u16, day: u8) -> PathBuf {
+        self.inputs().sample_path(year, day)
+    }
+}
+
+// Root of the inputs, samples and answers: $AOC_ROOT, or else the repository
+// root, i.e. the parent of the workspace directory
+pub fn root() -> &'static Path {
+    static ROOT: LazyLock<PathBuf> = LazyLock::new(input::root);
+    &ROOT
+}
+
+// Directory of the workspace, i.e. the parent of the crate directory
+pub fn workspace_dir() -> &'static Path {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    manifest_dir.parent().unwrap_or(manifest_dir)
+}
+
+// Directory of the crate with the solvers
+pub fn core_dir() -> PathBuf {
+    workspace_dir().join("aoc2023-core")
+}
+
+// Resolve `path` relative to `dir`, expanding a leading `~` to $HOME
+fn resolve(dir: &Path, path: &Path) -> PathBuf {
+    match (path.strip_prefix("~"), env::var_os("HOME")) {
+        (Ok(rest), Some(home)) => Path::new(&home).join(rest),
+        _ => dir.join(path),
+    }
+}
+
+fn deserialize_level<'de, D>(deserializer: D) -> Result<Option<Level>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Option::<String>::deserialize(deserializer)?
+        .map(|level| level.parse::<Level>().map_err(serde::de::Error::custom))
+        .transpose()
+}
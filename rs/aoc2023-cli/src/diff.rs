@@ -0,0 +1,157 @@
+use std::{path::Path, time::Duration};
+
+use anyhow::Result;
+use owo_colors::{OwoColorize, Style};
+
+use crate::{
+    report::DayRecord,
+    runner::{self, Day},
+    style::Styles,
+};
+
+// Changes of timings up to this fraction are noise, and aren't highlighted
+const NOISE: f64 = 0.1;
+
+// Solve `days` with their inputs and with the inputs of the same layout in
+// `input_dir`, e.g. a friend's, and return the records of both runs
+pub fn against(days: &[Day], input_dir: &Path, jobs: usize) -> Result<[Vec<DayRecord>; 2]> {
+    let theirs = days
+        .iter()
+        .map(|day| Day {
+            inputs: day.inputs.clone().input_dir(input_dir),
+            ..day.clone()
+        })
+        .collect::<Vec<_>>();
+    let record = |days: &[Day]| -> Result<Vec<DayRecord>> {
+        Ok(runner::run(days, jobs)?
+            .iter()
+            .map(DayRecord::from)
+            .collect())
+    };
+    Ok([record(days)?, record(&theirs)?])
+}
+
+// Print the answers and timings of the days of two runs side by side, e.g.
+//
+//   day  part  answer                          before       after   change
+//    05     1  388071289                       1.20ms      0.90ms   -25.0%
+//    05     2  84206669 → 84206670             3.10ms      3.00ms    -3.2%
+//    05   all                                  4.50ms      4.00ms   -11.1%
+//
+// highlighting answers that changed, and timings that changed by more than
+// 10%. Days of only one of the runs are listed at the end. Returns true if
+// no answer changed.
+pub fn print(runs: [&[DayRecord]; 2], labels: [&str; 2], styles: &Styles) -> bool {
+    let [a, b] = runs;
+    println!(
+        "{:>3}  {:>4}  {:<30}{:>12}{:>12}{:>9}",
+        "day", "part", "answer", labels[0], labels[1], "change"
+    );
+
+    let mut changed = 0;
+    for old in a {
+        let Some(new) = b
+            .iter()
+            .find(|new| (new.year, new.day) == (old.year, old.day))
+        else {
+            continue;
+        };
+        for (part, old_time, new_time) in [
+            (1, old.part1_time, new.part1_time),
+            (2, old.part2_time, new.part2_time),
+        ] {
+            let (old_answer, new_answer) = (answer(old, part), answer(new, part));
+            let (answer, style) = if old_answer == new_answer {
+                (old_answer, styles.answer)
+            } else {
+                changed += 1;
+                (format!("{} → {}", old_answer, new_answer), styles.fail)
+            };
+            println!(
+                "{:>3}  {:>4}  {:<30}{:>12}{:>12}{}",
+                format!("{:02}", old.day).style(styles.header),
+                part,
+                answer.style(style),
+                format_time(old_time),
+                format_time(new_time),
+                change(old_time, new_time, styles)
+            );
+        }
+        println!(
+            "{:>3}  {:>4}  {:<30}{:>12}{:>12}{}",
+            format!("{:02}", old.day).style(styles.header),
+            "all",
+            "",
+            format_time(Some(old.elapsed)),
+            format_time(Some(new.elapsed)),
+            change(Some(old.elapsed), Some(new.elapsed), styles)
+        );
+    }
+
+    let only = |runs: [&[DayRecord]; 2]| {
+        runs[0]
+            .iter()
+            .filter(|day| {
+                runs[1]
+                    .iter()
+                    .all(|other| (other.year, other.day) != (day.year, day.day))
+            })
+            .map(|day| format!("{:02}", day.day))
+            .collect::<Vec<_>>()
+    };
+    for (label, days) in [(labels[0], only([a, b])), (labels[1], only([b, a]))] {
+        if !days.is_empty() {
+            println!(
+                "{} {}",
+                format!("only in {}:", label).style(styles.muted),
+                days.join(", ")
+            );
+        }
+    }
+
+    let total = |run: &[DayRecord]| run.iter().map(|day| day.elapsed).sum::<Duration>();
+    println!(
+        "total {} → {}",
+        format!("{:.2?}", total(a)).style(styles.time),
+        format!("{:.2?}", total(b)).style(styles.time)
+    );
+    if changed == 0 {
+        println!("{} answers match", styles.marker(true));
+    } else {
+        println!("{} {} answers changed", styles.marker(false), changed);
+    }
+    changed == 0
+}
+
+// Answer of a part of a day as printed, `failed` if the day failed before
+// answering it
+fn answer(day: &DayRecord, part: u8) -> String {
+    match (day.answers.get(&part), &day.error) {
+        (Some(answer), _) => answer.to_string(),
+        (None, Some(_)) => "failed".to_string(),
+        (None, None) => "-".to_string(),
+    }
+}
+
+// Relative change of a timing, e.g. ` -25.0%`, highlighted beyond the noise
+fn change(old: Option<Duration>, new: Option<Duration>, styles: &Styles) -> String {
+    let (Some(old), Some(new)) = (old, new) else {
+        return format!("{:>9}", "-");
+    };
+    if old.is_zero() {
+        return format!("{:>9}", "-");
+    }
+    let change = new.as_secs_f64() / old.as_secs_f64() - 1.0;
+    let style = if change > NOISE {
+        styles.fail
+    } else if change < -NOISE {
+        styles.pass
+    } else {
+        Style::new()
+    };
+    format!(" {:>+7.1}%", (change * 100.0).style(style))
+}
+
+fn format_time(time: Option<Duration>) -> String {
+    time.map_or("-".to_string(), |time| format!("{:.2?}", time))
+}
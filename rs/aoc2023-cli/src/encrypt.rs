@@ -0,0 +1,35 @@
+use std::fs;
+
+use anyhow::{Context, Result};
+
+use aoc2023_core::{crypt, input};
+
+use crate::config::Config;
+
+// Encrypt the inputs of `days` of `year`, or of every day with an input if
+// none are given, next to them as dayNN.txt.enc. The plain inputs are kept;
+// leave them out of the repository, e.g. with `input/**/*.txt` in
+// .gitignore.
+pub fn run(year: u16, days: &[u8], config: &Config) -> Result<()> {
+    let key = crypt::key()?;
+    let all = days.is_empty();
+    let days = if all {
+        (1..=25).collect()
+    } else {
+        days.to_vec()
+    };
+    for day in days {
+        let path = config.input_path(year, day);
+        if !path.is_file() {
+            anyhow::ensure!(all, "{} not found", path.display());
+            continue;
+        }
+        let input =
+            fs::read(&path).with_context(|| format!("failed to read {}", path.display()))?;
+        let encrypted = input::with_extension(&path, "enc");
+        fs::write(&encrypted, crypt::encrypt(&key, &input)?)
+            .with_context(|| format!("failed to write {}", encrypted.display()))?;
+        tracing::info!("encrypted {}", encrypted.display());
+    }
+    Ok(())
+}
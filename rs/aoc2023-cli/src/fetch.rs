@@ -0,0 +1,166 @@
+use std::fs;
+
+use anyhow::{Context, Result};
+
+use aoc2023_core::input;
+
+use crate::{
+    cache::{Cache, Entry, Status},
+    config::Config,
+    samples, session,
+};
+
+// Identifies the tool to adventofcode.com, as its maintainer asks of
+// automated requests
+const USER_AGENT: &str = concat!(
+    "github.com/manjuraj/aoc2023 aoc2023-cli/",
+    env!("CARGO_PKG_VERSION")
+);
+
+// Download the input of `day` into the input directory, unless it's there
+// already; inputs never change, so they're fetched only once, unless forced.
+// The checksum and ETag of the download are recorded in the cache, to tell
+// later whether the file was truncated or edited.
+pub fn run(year: u16, day: u8, force: bool, config: &Config) -> Result<()> {
+    anyhow::ensure!((1..=25).contains(&day), "day {} is not in 1..=25", day);
+
+    let path = config.input_path(year, day);
+    if let Some(file) = input::find(&path).filter(|file| *file != path && !force) {
+        tracing::info!("{} is already there, compressed", file.display());
+        return Ok(());
+    }
+    let mut cache = Cache::load(year, config)?;
+    let status = cache.status(day, &path)?;
+    match status {
+        Some(Status::Ok | Status::Untracked) if !force => {
+            tracing::info!("{} is already downloaded", path.display());
+            return Ok(());
+        }
+        Some(status @ (Status::Truncated | Status::Modified)) if !force => {
+            anyhow::bail!(
+                "{} is {}; download it again with `aoc2023 fetch --force {}`",
+                path.display(),
+                status,
+                day
+            );
+        }
+        _ => {}
+    }
+
+    let url = format!("https://adventofcode.com/{}/day/{}/input", year, day);
+    tracing::info!("downloading {}", url);
+    let mut request = request("GET", &url, Some(&session::get(config)?));
+    // only an intact input can be kept if it's unchanged
+    let etag = cache.get(day).and_then(|entry| entry.etag.as_deref());
+    if let (Some(Status::Ok), Some(etag)) = (status, etag) {
+        request = request.set("If-None-Match", etag);
+    }
+    let response = request
+        .call()
+        .with_context(|| format!("failed to download {}", url))?;
+    if response.status() == 304 {
+        tracing::info!("{} is up to date", path.display());
+        return Ok(());
+    }
+    let etag = response.header("ETag").map(str::to_string);
+    let input = response
+        .into_string()
+        .with_context(|| format!("failed to read {}", url))?;
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).with_context(|| format!("failed to create {}", dir.display()))?;
+    }
+    fs::write(&path, &input).with_context(|| format!("failed to write {}", path.display()))?;
+    cache.insert(day, Entry::new(input.as_bytes(), etag));
+    cache.save()?;
+    tracing::info!("fetched {}", path.display());
+    Ok(())
+}
+
+// Extract the samples of `day` from its puzzle page into the sample
+// directory, as dayNN.txt, or dayNNa.txt, dayNNb.txt, ... if the parts have
+// different samples. The samples are the code blocks numbered `blocks` on the
+// page, or else a guess of them. Samples already there are kept, unless
+// forced.
+pub fn samples(year: u16, day: u8, blocks: &[usize], force: bool, config: &Config) -> Result<()> {
+    anyhow::ensure!((1..=25).contains(&day), "day {} is not in 1..=25", day);
+
+    let url = format!("https://adventofcode.com/{}/day/{}", year, day);
+    // anyone can read the first part, only the second needs logging in
+    let session = session::get(config).ok();
+    if session.is_none() {
+        tracing::warn!("no session cookie, so only the first part is visible");
+    }
+    tracing::info!("downloading {}", url);
+    let html = request("GET", &url, session.as_deref())
+        .call()
+        .with_context(|| format!("failed to download {}", url))?
+        .into_string()
+        .with_context(|| format!("failed to read {}", url))?;
+
+    let found = samples::blocks(&html);
+    for (i, block) in found.iter().enumerate() {
+        tracing::debug!(
+            "block {} of part {}{}:\n{}",
+            i + 1,
+            block.part,
+            if block.example { ", an example" } else { "" },
+            block.text
+        );
+    }
+    let picked = if blocks.is_empty() {
+        let guess = samples::guess(&found);
+        let numbers = guess
+            .iter()
+            .map(|i| (i + 1).to_string())
+            .collect::<Vec<_>>();
+        tracing::info!(
+            "guessed block {} of {} as samples; pick others with --block",
+            numbers.join(", "),
+            found.len()
+        );
+        guess
+    } else {
+        for &i in blocks {
+            anyhow::ensure!(
+                (1..=found.len()).contains(&i),
+                "no block {}; the page has {} of them",
+                i,
+                found.len()
+            );
+        }
+        blocks.iter().map(|i| i - 1).collect()
+    };
+    anyhow::ensure!(!picked.is_empty(), "no code blocks on {}", url);
+
+    let path = config.sample_path(year, day);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).with_context(|| format!("failed to create {}", dir.display()))?;
+    }
+    for (i, block) in picked.iter().map(|&i| &found[i]).enumerate() {
+        let path = if picked.len() == 1 {
+            path.clone()
+        } else {
+            let suffix = char::from(b'a' + i as u8);
+            path.with_file_name(format!("day{:02}{}.txt", day, suffix))
+        };
+        // `new-day` creates empty samples, to be filled in
+        if !force && fs::metadata(&path).is_ok_and(|metadata| metadata.len() > 0) {
+            tracing::warn!("kept {}; overwrite it with --force", path.display());
+            continue;
+        }
+        fs::write(&path, &block.text)
+            .with_context(|| format!("failed to write {}", path.display()))?;
+        tracing::info!("extracted {}", path.display());
+    }
+    Ok(())
+}
+
+// Request of `url` to adventofcode.com, logged in with `session` if given
+pub fn request(method: &str, url: &str, session: Option<&str>) -> ureq::Request {
+    let request = ureq::request(method, url).set("User-Agent", USER_AGENT);
+    match session {
+        Some(session) => request.set("Cookie", &format!("session={}", session)),
+        None => request,
+    }
+}
@@ -0,0 +1,35 @@
+use std::{
+    fs,
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{Context, Result};
+
+use aoc2023_core::generate;
+
+use crate::runner::Day;
+
+// Print a fake input of `day` generated from `seed`, `size` times as large
+// as the real ones, or write it to `output`. Without a seed, one is taken from the clock and told on standard
+// error, to generate the same input again.
+pub fn run(day: &Day, seed: Option<u64>, size: usize, output: Option<&Path>) -> Result<()> {
+    let seed = seed.unwrap_or_else(|| {
+        let seed = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_nanos() as u64);
+        eprintln!("day {:02}: --seed {}", day.day, seed);
+        seed
+    });
+    let input = generate::generate(day.solver, seed, size)
+        .with_context(|| format!("day {} has no generator", day.day))?;
+    match output {
+        Some(path) => {
+            fs::write(path, input)
+                .with_context(|| format!("failed to write {}", path.display()))?;
+            tracing::info!("wrote {}", path.display());
+        }
+        None => println!("{}", input),
+    }
+    Ok(())
+}
@@ -0,0 +1,188 @@
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use tracing::Level;
+use tracing_chrome::{ChromeLayer, ChromeLayerBuilder, FlushGuard};
+use tracing_subscriber::{
+    filter::LevelFilter,
+    fmt::{self, MakeWriter},
+    layer::SubscriberExt,
+    util::SubscriberInitExt,
+    EnvFilter, Layer, Registry,
+};
+
+use crate::runner::CaptureWriter;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum LogFormat {
+    /// One line per event
+    #[default]
+    Compact,
+    /// Multi-line events with source file and line numbers
+    Pretty,
+    /// Newline delimited JSON
+    Json,
+}
+
+// Layer writing a chrome trace, set by `trace_to` for `init` to install
+static CHROME: Mutex<Option<ChromeLayer<Registry>>> = Mutex::new(None);
+
+// Write the spans of days and their stages, and info events, to `path` as a
+// chrome trace once `init` installs the subscriber, whatever the log level.
+// The trace is complete once the returned guard is dropped. Traces open in
+// chrome://tracing or https://ui.perfetto.dev, with a track per thread.
+pub fn trace_to(path: &Path) -> Result<FlushGuard> {
+    let file =
+        File::create(path).with_context(|| format!("failed to create {}", path.display()))?;
+    let (layer, guard) = ChromeLayerBuilder::new()
+        .writer(file)
+        .include_args(true)
+        .include_locations(false)
+        .build();
+    *CHROME.lock().unwrap() = Some(layer);
+    Ok(guard)
+}
+
+// Size past which the log file starts over, and how many of the previous
+// ones are kept, as path.1 (the latest) to path.4
+const MAX_LOG_SIZE: u64 = 64 * 1024 * 1024;
+const KEEP_LOGS: usize = 4;
+
+// Log file, and the format of its events, set by `log_to` for `init` to
+// install
+static LOG_FILE: Mutex<Option<(RotatingFile, LogFormat)>> = Mutex::new(None);
+
+// Log file that starts over when it grows past `MAX_LOG_SIZE`, keeping the
+// previous ones
+#[derive(Debug, Clone)]
+struct RotatingFile(Arc<Mutex<Rotating>>);
+
+#[derive(Debug)]
+struct Rotating {
+    path: PathBuf,
+    file: File,
+    size: u64,
+}
+
+impl RotatingFile {
+    fn open(path: &Path) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let size = file.metadata()?.len();
+        Ok(RotatingFile(Arc::new(Mutex::new(Rotating {
+            path: path.to_path_buf(),
+            file,
+            size,
+        }))))
+    }
+}
+
+impl Rotating {
+    fn rotate(&mut self) -> io::Result<()> {
+        let numbered = |n: usize| {
+            let mut path = self.path.clone().into_os_string();
+            path.push(format!(".{}", n));
+            PathBuf::from(path)
+        };
+        for n in (1..KEEP_LOGS).rev() {
+            match fs::rename(numbered(n), numbered(n + 1)) {
+                Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e),
+                _ => {}
+            }
+        }
+        fs::rename(&self.path, numbered(1))?;
+        self.file = File::create(&self.path)?;
+        self.size = 0;
+        Ok(())
+    }
+}
+
+impl Write for RotatingFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut rotating = self.0.lock().unwrap();
+        if rotating.size > 0 && rotating.size + buf.len() as u64 > MAX_LOG_SIZE {
+            rotating.rotate()?;
+        }
+        let len = rotating.file.write(buf)?;
+        rotating.size += len as u64;
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.lock().unwrap().file.flush()
+    }
+}
+
+impl<'a> MakeWriter<'a> for RotatingFile {
+    type Writer = RotatingFile;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        self.clone()
+    }
+}
+
+// Write log output to `path`, in `format`, once `init` installs the
+// subscriber, leaving only warnings and errors to the terminal. The file is
+// appended to, and rotated when it grows past 64 MiB.
+pub fn log_to(path: &Path, format: LogFormat) -> Result<()> {
+    let file =
+        RotatingFile::open(path).with_context(|| format!("failed to open {}", path.display()))?;
+    *LOG_FILE.lock().unwrap() = Some((file, format));
+    Ok(())
+}
+
+// Install the global tracing subscriber.
+//
+// `level` is the default level for every target; directives in `RUST_LOG`
+// take precedence over it, so `RUST_LOG=aoc2023_core::y2023::day05=debug` turns
+// on debug output for day 5 only. `ansi` controls colored output, which has
+// to be off when the output is rendered by something other than a terminal.
+pub fn init(level: Level, format: LogFormat, ansi: bool) {
+    let filter = || {
+        EnvFilter::builder()
+            .with_default_directive(level.into())
+            .from_env_lossy()
+    };
+
+    let layer = fmt::layer().with_writer(CaptureWriter).with_ansi(ansi);
+    let layer = match format {
+        LogFormat::Compact => layer.compact().boxed(),
+        LogFormat::Pretty => layer.pretty().boxed(),
+        LogFormat::Json => layer.json().boxed(),
+    };
+
+    // with a log file, the terminal only gets warnings and errors
+    let file = LOG_FILE.lock().unwrap().take();
+    let (layer, file) = match file {
+        Some((file, file_format)) => {
+            let quiet = LevelFilter::from_level(level.min(Level::WARN));
+            let file_layer = fmt::layer().with_writer(file).with_ansi(false);
+            let file_layer = match file_format {
+                LogFormat::Compact => file_layer.compact().boxed(),
+                LogFormat::Pretty => file_layer.pretty().boxed(),
+                LogFormat::Json => file_layer.json().boxed(),
+            };
+            (
+                layer.with_filter(quiet).boxed(),
+                Some(file_layer.with_filter(filter())),
+            )
+        }
+        None => (layer.with_filter(filter()).boxed(), None),
+    };
+
+    let chrome = CHROME
+        .lock()
+        .unwrap()
+        .take()
+        .map(|chrome| chrome.with_filter(LevelFilter::INFO));
+    tracing_subscriber::registry()
+        .with(chrome)
+        .with(layer)
+        .with(file)
+        .init();
+}
@@ -0,0 +1,710 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::ExitCode,
+    time::Instant,
+};
+
+use anyhow::{Context, Result};
+use aoc2023_core::Params;
+use clap::{Parser, Subcommand, ValueEnum};
+use tracing::Level;
+
+mod answers;
+mod baselines;
+mod bench;
+mod cache;
+mod config;
+mod diff;
+mod encrypt;
+mod fetch;
+mod generate;
+mod logging;
+mod profile;
+mod report;
+mod runner;
+mod samples;
+mod scaffold;
+mod session;
+mod style;
+mod submit;
+mod today;
+mod tui;
+mod validate;
+mod watch;
+
+use answers::Expected;
+use config::Config;
+use logging::LogFormat;
+use runner::Day;
+use style::{Styles, Theme};
+
+// count the allocations of days and parts, for `bench`
+#[cfg(feature = "alloc-stats")]
+#[global_allocator]
+static ALLOC: aoc2023_core::memory::Counting = aoc2023_core::memory::Counting;
+
+/// Advent of Code 2023 - my answers in rust
+#[derive(Debug, Parser)]
+#[command(version, about)]
+struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    #[command(flatten)]
+    run: RunArgs,
+
+    /// Maximum level of log output [default: warn, or info for commands
+    /// that change files, debug in the tui]; RUST_LOG directives take
+    /// precedence
+    #[arg(long, global = true)]
+    log_level: Option<Level>,
+
+    /// Format of log output
+    #[arg(long, value_enum, global = true, default_value_t)]
+    log_format: LogFormat,
+
+    /// File to write log output to, e.g. of debug runs too long for the
+    /// terminal, which then only gets warnings and errors; the file is
+    /// appended to, and rotated past 64 MiB as FILE.1 to FILE.4
+    #[arg(long, global = true, value_name = "FILE")]
+    log_file: Option<PathBuf>,
+
+    /// Format of log output to the log file
+    #[arg(
+        long,
+        value_enum,
+        global = true,
+        default_value_t,
+        requires = "log_file"
+    )]
+    log_file_format: LogFormat,
+
+    /// Write the spans of solving days and their parts to a chrome trace
+    /// file, to view in chrome://tracing or https://ui.perfetto.dev
+    #[arg(long, global = true, value_name = "FILE")]
+    trace: Option<PathBuf>,
+
+    /// Print without colors, as when $NO_COLOR is set or the output isn't a
+    /// terminal
+    #[arg(long, global = true)]
+    no_color: bool,
+
+    /// Colors of the output [default: dark]
+    #[arg(long, value_enum, global = true)]
+    theme: Option<Theme>,
+
+    /// Threads that days spread their own loops over, e.g. the beams of day
+    /// 16, when solved one at a time; days solved concurrently with --jobs
+    /// share the threads of the jobs instead [default: one per CPU]
+    #[arg(long, global = true)]
+    threads: Option<usize>,
+
+    /// Year of the puzzles [default: 2023]
+    #[arg(long, global = true)]
+    year: Option<u16>,
+
+    /// Config file [default: aoc.toml in the current directory or the
+    /// repository root]
+    #[arg(long, global = true)]
+    config: Option<PathBuf>,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Solve days (the default)
+    Run(RunArgs),
+    /// Solve days and check their answers against the answers file
+    Verify(RunArgs),
+    /// Check the inputs of days, and their alternates, against the
+    /// invariants the days assume of them
+    Validate {
+        /// Days to check; checks all days when none are given
+        days: Vec<u8>,
+    },
+    /// Generate a fake input of a day, shaped like the real ones and valid
+    /// to the day, to share instead of the real one
+    Generate {
+        /// Day to generate an input of
+        day: u8,
+
+        /// Seed of the input; the same seed gives the same input [default:
+        /// taken from the clock, and printed on standard error]
+        #[arg(long)]
+        seed: Option<u64>,
+
+        /// Size of the input as a multiple of the real ones, e.g. 5 for 5
+        /// times the lines, or the rows and columns of a grid, to find out how
+        /// solvers scale
+        #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+        size: u32,
+
+        /// File to write the input to [default: standard output]
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Solve days several times and print a table of their timings
+    Bench {
+        /// Days to time; times all days when none are given
+        days: Vec<u8>,
+
+        /// Number of times to solve each day
+        #[arg(short = 'n', long, default_value_t = 10)]
+        iterations: u32,
+
+        /// Fail days whose allocations grew by more than this percentage
+        /// over their baseline in the baselines file; needs the
+        /// alloc-stats feature, like all allocation counts
+        #[arg(long, value_name = "PERCENT", default_value_t = 10.0)]
+        alloc_threshold: f64,
+
+        /// Write the allocations of the days to the baselines file as their
+        /// new baselines, e.g. after cutting them down
+        #[arg(long)]
+        save_baselines: bool,
+    },
+    /// Solve today's puzzle (US/Eastern) and print its answers
+    Today,
+    /// Interactive dashboard to browse days, run them and view their output
+    Tui {
+        /// Days to show; shows all days when none are given
+        days: Vec<u8>,
+    },
+    /// Re-run a day whenever its source, input or sample files change
+    Watch {
+        /// Day to watch
+        day: u8,
+    },
+    /// Store the adventofcode.com session cookie in the platform keyring,
+    /// for fetching inputs and submitting answers
+    Login,
+    /// Remove the session cookie from the platform keyring
+    Logout,
+    /// Download the input of a day from adventofcode.com, using the session
+    /// cookie in $AOC_SESSION, the keyring or the config's session file
+    Fetch {
+        /// Day to fetch
+        day: u8,
+
+        /// Download the input again, or overwrite the samples, even if
+        /// they're already there
+        #[arg(short, long)]
+        force: bool,
+
+        /// Extract the samples from the puzzle page instead of downloading
+        /// the input
+        #[arg(long)]
+        samples: bool,
+
+        /// Code blocks of the puzzle page to take as samples, numbered from
+        /// 1 [default: the first example of each part]
+        #[arg(long = "block", requires = "samples")]
+        blocks: Vec<usize>,
+    },
+    /// Encrypt inputs with the key in $AOC_INPUT_KEY, to keep them in the
+    /// repository as dayNN.txt.enc; encrypted inputs are read transparently
+    Encrypt {
+        /// Days whose input to encrypt; encrypts all inputs when none are
+        /// given
+        days: Vec<u8>,
+    },
+    /// Solve a part of a day and send its answer to adventofcode.com,
+    /// unless earlier answers tell it's wrong, or one was sent too recently
+    Submit {
+        /// Day to solve
+        day: u8,
+
+        /// Part whose answer to send
+        part: u8,
+    },
+    /// Solve days and write a report of their answers and timings
+    Report {
+        #[command(subcommand)]
+        command: ReportCommand,
+    },
+    /// Compare the answers and timings of two runs recorded with `report
+    /// json`, or of the inputs and those of another input directory;
+    /// fails if any answer differs
+    Diff {
+        /// Recorded runs to compare, the earlier one first
+        #[arg(num_args = 2, value_name = "RUN", required_unless_present = "against")]
+        runs: Vec<PathBuf>,
+
+        /// Input directory, laid out as the input directory, to solve the
+        /// days with and compare against the inputs, e.g. a friend's
+        #[arg(long, value_name = "DIR", conflicts_with = "runs")]
+        against: Option<PathBuf>,
+
+        /// Number of days to solve concurrently with --against; 0 uses one
+        /// thread per CPU [default: 1]
+        #[arg(short, long, requires = "against")]
+        jobs: Option<usize>,
+    },
+    /// Manage the downloaded inputs
+    Cache {
+        #[command(subcommand)]
+        command: CacheCommand,
+    },
+    /// Create the source, input and sample files of a new day and register it
+    NewDay {
+        /// Day to create
+        day: u8,
+    },
+    /// Solve a day under a sampling profiler and write a flamegraph SVG
+    Profile {
+        /// Day to profile
+        day: u8,
+
+        /// Number of times to solve the day, for more samples of fast days
+        #[arg(short = 'n', long, default_value_t = 1)]
+        iterations: u32,
+
+        /// Samples per second
+        #[arg(long, default_value_t = 997)]
+        frequency: i32,
+
+        /// Flamegraph file [default: flamegraph-dayNN.svg]
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Show the settings in effect, after reading the config file
+    Config,
+}
+
+#[derive(Debug, Subcommand)]
+enum CacheCommand {
+    /// Check whether the downloaded inputs are intact, i.e. neither
+    /// truncated nor edited since they were fetched
+    Status,
+}
+
+#[derive(Debug, Subcommand)]
+enum ReportCommand {
+    /// Markdown table of the answers and timings, to paste into a write-up
+    Md(ReportArgs),
+    /// Self-contained HTML page with charts of the timings, and badges
+    /// telling whether the answers match the answers file
+    Html(ReportArgs),
+    /// JSON record of the answers and timings, to compare later runs
+    /// against with `diff`
+    Json(ReportArgs),
+}
+
+#[derive(Debug, clap::Args)]
+struct ReportArgs {
+    /// Days to report; reports all days when none are given
+    days: Vec<u8>,
+
+    /// Number of days to solve concurrently; 0 uses one thread per CPU
+    /// [default: 1]
+    #[arg(short, long)]
+    jobs: Option<usize>,
+
+    /// File to write the report to [default: standard output]
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+}
+
+#[derive(Debug, clap::Args)]
+struct RunArgs {
+    /// Days to run; runs all days when none are given
+    days: Vec<u8>,
+
+    /// Number of days to solve concurrently; 0 uses one thread per CPU
+    /// [default: 1]
+    #[arg(short, long)]
+    jobs: Option<usize>,
+
+    /// Input file of the day to run instead of the one in the input
+    /// directory; needs a single day
+    #[arg(long)]
+    input: Option<PathBuf>,
+
+    /// Solve each day against its input and every alternate input, e.g.
+    /// input/2023/day05.alt1.txt, and print their answers side by side
+    #[arg(long, conflicts_with = "input")]
+    input_set: bool,
+
+    /// Print the key intermediate results of solving each day, e.g. the
+    /// ranked hands of day 7, after its answers
+    #[arg(long, conflicts_with = "input_set")]
+    explain: bool,
+
+    /// Parameter of the day to run that changes its puzzle, e.g. "bag=20
+    /// red" for the bag of part 1 of day 2; may be repeated; needs a single
+    /// day
+    #[arg(long = "param", value_name = "KEY=VALUE")]
+    params: Vec<Params>,
+}
+
+fn main() -> Result<ExitCode> {
+    let args = Args::parse();
+    let config = Config::load(args.config.as_deref())?;
+    // command line options take precedence over the config file
+    let level = args.log_level.or(config.log_level);
+    let year = args.year.or(config.year).unwrap_or(config::DEFAULT_YEAR);
+    let color = style::enabled(args.no_color);
+    if let Some(path) = &args.log_file {
+        logging::log_to(path, args.log_file_format)?;
+    }
+    runner::init_threads(args.threads.unwrap_or(0))?;
+    // the trace is written out when this is dropped, on return
+    let _trace = args.trace.as_deref().map(logging::trace_to).transpose()?;
+    let styles = Styles::new(args.theme.or(config.theme).unwrap_or_default(), color);
+
+    match args.command {
+        None | Some(Command::Run(_)) | Some(Command::Verify(_)) => {
+            // days are printed as they're done, or checked; their log
+            // output is for debugging
+            logging::init(level.unwrap_or(Level::WARN), args.log_format, color);
+            match args.command {
+                Some(Command::Verify(run_args)) => verify(year, run_args, &config, &styles),
+                Some(Command::Run(run_args)) => run(year, run_args, &config, &styles),
+                _ => run(year, args.run, &config, &styles),
+            }
+        }
+        Some(Command::Validate { days }) => {
+            logging::init(level.unwrap_or(Level::WARN), args.log_format, color);
+            if validate::run(&select_days(year, &days, &config)?)? {
+                Ok(ExitCode::SUCCESS)
+            } else {
+                Ok(ExitCode::FAILURE)
+            }
+        }
+        Some(Command::Generate {
+            day,
+            seed,
+            size,
+            output,
+        }) => {
+            logging::init(level.unwrap_or(Level::WARN), args.log_format, color);
+            let days = select_days(year, &[day], &config)?;
+            generate::run(&days[0], seed, size as usize, output.as_deref())?;
+            Ok(ExitCode::SUCCESS)
+        }
+        Some(Command::Bench {
+            days,
+            iterations,
+            alloc_threshold,
+            save_baselines,
+        }) => {
+            logging::init(level.unwrap_or(Level::WARN), args.log_format, color);
+            let guard = bench::Guard {
+                path: &config.baselines_file,
+                threshold: alloc_threshold,
+                save: save_baselines,
+            };
+            if bench::run(&select_days(year, &days, &config)?, iterations, &guard)? {
+                Ok(ExitCode::SUCCESS)
+            } else {
+                Ok(ExitCode::FAILURE)
+            }
+        }
+        Some(Command::Today) => {
+            logging::init(level.unwrap_or(Level::WARN), args.log_format, color);
+            if today::run(&config)? {
+                Ok(ExitCode::SUCCESS)
+            } else {
+                Ok(ExitCode::FAILURE)
+            }
+        }
+        Some(Command::Tui { days }) => {
+            logging::init(level.unwrap_or(Level::DEBUG), args.log_format, false);
+            tui::run(&select_days(year, &days, &config)?)?;
+            Ok(ExitCode::SUCCESS)
+        }
+        Some(Command::Watch { day }) => {
+            logging::init(level.unwrap_or(Level::INFO), args.log_format, color);
+            select_days(year, &[day], &config)?;
+            watch::run(year, day, &config)?;
+            Ok(ExitCode::SUCCESS)
+        }
+        Some(Command::Login) => {
+            logging::init(level.unwrap_or(Level::INFO), args.log_format, color);
+            session::login()?;
+            Ok(ExitCode::SUCCESS)
+        }
+        Some(Command::Logout) => {
+            logging::init(level.unwrap_or(Level::INFO), args.log_format, color);
+            session::logout()?;
+            Ok(ExitCode::SUCCESS)
+        }
+        Some(Command::Fetch {
+            day,
+            force,
+            samples,
+            blocks,
+        }) => {
+            logging::init(level.unwrap_or(Level::INFO), args.log_format, color);
+            if samples {
+                fetch::samples(year, day, &blocks, force, &config)?;
+            } else {
+                fetch::run(year, day, force, &config)?;
+            }
+            Ok(ExitCode::SUCCESS)
+        }
+        Some(Command::Encrypt { days }) => {
+            logging::init(level.unwrap_or(Level::INFO), args.log_format, color);
+            encrypt::run(year, &days, &config)?;
+            Ok(ExitCode::SUCCESS)
+        }
+        Some(Command::Submit { day, part }) => {
+            logging::init(level.unwrap_or(Level::WARN), args.log_format, color);
+            let day = select_days(year, &[day], &config)?.remove(0);
+            if submit::run(&day, part, &config)? {
+                Ok(ExitCode::SUCCESS)
+            } else {
+                Ok(ExitCode::FAILURE)
+            }
+        }
+        Some(Command::Report { command }) => {
+            logging::init(level.unwrap_or(Level::WARN), args.log_format, color);
+            let (ReportCommand::Md(report_args)
+            | ReportCommand::Html(report_args)
+            | ReportCommand::Json(report_args)) = &command;
+            let days = select_days(year, &report_args.days, &config)?;
+            let jobs = report_args.jobs.or(config.jobs).unwrap_or(1);
+            let outcomes = runner::run(&days, jobs)?;
+            let report = match command {
+                ReportCommand::Md(_) => report::markdown(&outcomes),
+                ReportCommand::Html(_) => {
+                    let expected = Expected::load(&config.answers_file)?;
+                    report::html(year, &outcomes, &expected)
+                }
+                ReportCommand::Json(_) => report::json(&outcomes)?,
+            };
+            write_report(&report, report_args.output.as_deref())?;
+            if outcomes.iter().all(|outcome| outcome.result.is_ok()) {
+                Ok(ExitCode::SUCCESS)
+            } else {
+                Ok(ExitCode::FAILURE)
+            }
+        }
+        Some(Command::Diff {
+            runs,
+            against,
+            jobs,
+        }) => {
+            logging::init(level.unwrap_or(Level::WARN), args.log_format, color);
+            let label = |path: &Path| {
+                path.file_stem().map_or(path.display().to_string(), |stem| {
+                    stem.to_string_lossy().to_string()
+                })
+            };
+            let (records, labels) = match &against {
+                Some(dir) => {
+                    let days = select_days(year, &[], &config)?;
+                    let jobs = jobs.or(config.jobs).unwrap_or(1);
+                    let labels = [label(&config.input_dir), label(dir)];
+                    (diff::against(&days, dir, jobs)?, labels)
+                }
+                None => (
+                    [report::load_json(&runs[0])?, report::load_json(&runs[1])?],
+                    [label(&runs[0]), label(&runs[1])],
+                ),
+            };
+            let [a, b] = &records;
+            if diff::print([a, b], [&labels[0], &labels[1]], &styles) {
+                Ok(ExitCode::SUCCESS)
+            } else {
+                Ok(ExitCode::FAILURE)
+            }
+        }
+        Some(Command::Cache {
+            command: CacheCommand::Status,
+        }) => {
+            logging::init(level.unwrap_or(Level::WARN), args.log_format, color);
+            if cache::report(year, &config)? {
+                Ok(ExitCode::SUCCESS)
+            } else {
+                Ok(ExitCode::FAILURE)
+            }
+        }
+        Some(Command::NewDay { day }) => {
+            logging::init(level.unwrap_or(Level::INFO), args.log_format, color);
+            scaffold::new_day(year, day, &config)?;
+            Ok(ExitCode::SUCCESS)
+        }
+        Some(Command::Profile {
+            day,
+            iterations,
+            frequency,
+            output,
+        }) => {
+            logging::init(level.unwrap_or(Level::INFO), args.log_format, color);
+            let day = select_days(year, &[day], &config)?.remove(0);
+            let output = output
+                .unwrap_or_else(|| PathBuf::from(format!("flamegraph-day{:02}.svg", day.day)));
+            profile::run(day, iterations, frequency, &output)?;
+            Ok(ExitCode::SUCCESS)
+        }
+        Some(Command::Config) => {
+            show_config(year, &config);
+            Ok(ExitCode::SUCCESS)
+        }
+    }
+}
+
+fn run(year: u16, args: RunArgs, config: &Config, styles: &Styles) -> Result<ExitCode> {
+    let days = select_run_days(year, &args, config)?;
+    let jobs = args.jobs.or(config.jobs).unwrap_or(1);
+    if args.input_set {
+        return if runner::run_input_sets(&days, jobs)? {
+            Ok(ExitCode::SUCCESS)
+        } else {
+            Ok(ExitCode::FAILURE)
+        };
+    }
+    // answers of other inputs or parameters can't be checked
+    let expected = if args.input.is_none()
+        && days.iter().all(|day| day.params.is_empty())
+        && config.answers_file.is_file()
+    {
+        Some(Expected::load(&config.answers_file)?)
+    } else {
+        None
+    };
+    let start = Instant::now();
+    let outcomes = runner::run_with(&days, jobs, |outcome| {
+        styles.print_day(outcome);
+        if !args.explain || outcome.result.is_err() {
+            return;
+        }
+        let Some(day) = days.iter().find(|day| day.day == outcome.day) else {
+            return;
+        };
+        match day.explain() {
+            Ok(artifacts) => styles.print_artifacts(&artifacts),
+            Err(e) => println!("  failed to explain: {:#}", e),
+        }
+    })?;
+    if runner::summarize(&outcomes, start.elapsed(), expected.as_ref(), styles) {
+        Ok(ExitCode::SUCCESS)
+    } else {
+        Ok(ExitCode::FAILURE)
+    }
+}
+
+fn verify(year: u16, args: RunArgs, config: &Config, styles: &Styles) -> Result<ExitCode> {
+    anyhow::ensure!(
+        !args.input_set,
+        "--input-set can't be verified; the answers are of the main inputs"
+    );
+    let expected = Expected::load(&config.answers_file)?;
+    let days = select_run_days(year, &args, config)?;
+    let jobs = args.jobs.or(config.jobs).unwrap_or(1);
+    let outcomes = runner::run(&days, jobs)?;
+
+    let failed = outcomes
+        .iter()
+        .filter(|outcome| !expected.verify(outcome, styles))
+        .map(|outcome| format!("{:02}", outcome.day))
+        .collect::<Vec<_>>();
+    if failed.is_empty() {
+        println!(
+            "{} all {} days verified",
+            styles.marker(true),
+            outcomes.len()
+        );
+        Ok(ExitCode::SUCCESS)
+    } else {
+        println!(
+            "{} {} of {} days failed verification: {}",
+            styles.marker(false),
+            failed.len(),
+            outcomes.len(),
+            failed.join(", ")
+        );
+        Ok(ExitCode::FAILURE)
+    }
+}
+
+// Print a report, or write it to `output`
+fn write_report(report: &str, output: Option<&Path>) -> Result<()> {
+    match output {
+        Some(path) => {
+            fs::write(path, report).with_context(|| format!("failed to write {}", path.display()))
+        }
+        None => {
+            println!("{}", report);
+            Ok(())
+        }
+    }
+}
+
+fn show_config(year: u16, config: &Config) {
+    let path = |path: Option<&std::path::Path>| {
+        path.map_or("-".to_string(), |path| path.display().to_string())
+    };
+    let days = config.days.iter().map(u8::to_string).collect::<Vec<_>>();
+    let level = config.log_level.map(|level| level.to_string());
+    let jobs = config.jobs.map(|jobs| jobs.to_string());
+    let theme = config
+        .theme
+        .and_then(|theme| theme.to_possible_value())
+        .map(|theme| theme.get_name().to_string());
+
+    println!("config file:  {}", path(config.path.as_deref()));
+    println!("year:         {}", year);
+    println!("input dir:    {}", config.input_dir.display());
+    println!("sample dir:   {}", config.sample_dir.display());
+    println!("answers file: {}", config.answers_file.display());
+    println!("submissions:  {}", config.submissions_file.display());
+    println!("baselines:    {}", config.baselines_file.display());
+    println!("session file: {}", path(config.session_file.as_deref()));
+    println!(
+        "days:         {}",
+        if days.is_empty() {
+            "all".to_string()
+        } else {
+            days.join(", ")
+        }
+    );
+    println!("log level:    {}", level.as_deref().unwrap_or("-"));
+    println!("jobs:         {}", jobs.as_deref().unwrap_or("-"));
+    println!("theme:        {}", theme.as_deref().unwrap_or("-"));
+}
+
+// Days to run, reading the input given with --input if any
+fn select_run_days(year: u16, args: &RunArgs, config: &Config) -> Result<Vec<Day>> {
+    let mut days = select_days(year, &args.days, config)?;
+    if let Some(input) = &args.input {
+        anyhow::ensure!(days.len() == 1, "--input needs a single day to run");
+        days[0].input = Some(input.clone());
+    }
+    if !args.params.is_empty() {
+        anyhow::ensure!(days.len() == 1, "--param needs a single day to run");
+        days[0].params = args
+            .params
+            .iter()
+            .cloned()
+            .fold(Params::new(), Params::merge);
+    }
+    Ok(days)
+}
+
+// Days of `year` with the given numbers, or the config's days if none are
+// given; all days of the year if neither specify any
+fn select_days(year: u16, days: &[u8], config: &Config) -> Result<Vec<Day>> {
+    let days = if days.is_empty() { &config.days } else { days };
+    let implemented = runner::days(config)
+        .into_iter()
+        .filter(|d| d.year == year)
+        .collect::<Vec<_>>();
+    anyhow::ensure!(
+        !implemented.is_empty(),
+        "no days of {} are implemented or compiled in",
+        year
+    );
+    if let Some(day) = days
+        .iter()
+        .find(|&&day| implemented.iter().all(|d| d.day != day))
+    {
+        anyhow::bail!("day {} of {} is not implemented or compiled in", day, year);
+    }
+
+    Ok(implemented
+        .into_iter()
+        .filter(|day| days.is_empty() || days.contains(&day.day))
+        .collect())
+}
@@ -0,0 +1,39 @@
+use std::{fs::File, path::Path, time::Instant};
+
+use anyhow::{Context, Result};
+
+use crate::runner::Day;
+
+// Run `day` `iterations` times under a sampling profiler taking `frequency`
+// samples per second, and write a flamegraph of where the time went to
+// `output`.
+//
+// Build with --release for a representative profile.
+pub fn run(day: Day, iterations: u32, frequency: i32, output: &Path) -> Result<()> {
+    let guard = pprof::ProfilerGuardBuilder::default()
+        .frequency(frequency)
+        .blocklist(&["libc", "libgcc", "pthread", "vdso"])
+        .build()?;
+
+    let start = Instant::now();
+    for _ in 0..iterations {
+        day.run().result?;
+    }
+    let elapsed = start.elapsed();
+
+    let report = guard.report().build()?;
+    let samples = report.data.values().sum::<isize>();
+    let file =
+        File::create(output).with_context(|| format!("failed to create {}", output.display()))?;
+    report.flamegraph(file)?;
+
+    tracing::info!(
+        "Day {:02}: {} iteration(s) in {:.2?}, {} samples",
+        day.day,
+        iterations,
+        elapsed,
+        samples
+    );
+    tracing::info!("wrote flamegraph to {}", output.display());
+    Ok(())
+}
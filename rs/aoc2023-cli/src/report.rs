@@ -0,0 +1,237 @@
+use std::{collections::BTreeMap, fmt::Write, fs, path::Path, time::Duration};
+
+use anyhow::{Context, Result};
+use aoc2023_core::{Answer, DayResult};
+use serde::{Deserialize, Serialize};
+
+use crate::answers::Expected;
+
+// Stylesheet of the HTML report
+const STYLE: &str = "
+body { font-family: sans-serif; margin: 2em; color: #222; }
+table { border-collapse: collapse; }
+th, td { padding: 0.3em 0.8em; text-align: right; }
+th { border-bottom: 1px solid #999; }
+tr.total td { border-top: 1px solid #999; font-weight: bold; }
+.badge { border-radius: 0.6em; padding: 0.1em 0.6em; color: white; font-size: 0.8em; }
+.pass { background: #2a7d2a; }
+.fail { background: #c0392b; }
+.unchecked { background: #888; }
+td.chart { width: 40em; text-align: left; }
+.bar { display: flex; height: 1em; }
+.bar span { height: 100%; }
+.parse, .legend .parse { background: #8e9aaf; }
+.part1, .legend .part1 { background: #4a90d9; }
+.part2, .legend .part2 { background: #e6a23c; }
+.legend span { display: inline-block; width: 1em; height: 1em; margin: 0 0.3em 0 1em; }
+";
+
+// Markdown table of the answers of the days and their timings, e.g.
+//
+//   | day | part 1 | part 2 | parse | part 1 time | part 2 time | total |
+//   |----:|-------:|-------:|------:|------------:|------------:|------:|
+//   | 01 | 54927 | 54581 | 12.40µs | 31.20µs | 270.10µs | 315.03µs |
+//
+// with the sums of the timings in the last row. Parts without an answer
+// show as `-`, and both parts of failed days as `failed`.
+pub fn markdown(outcomes: &[DayResult]) -> String {
+    let mut lines = vec![
+        "| day | part 1 | part 2 | parse | part 1 time | part 2 time | total |".to_string(),
+        "|----:|-------:|-------:|------:|------------:|------------:|------:|".to_string(),
+    ];
+    for outcome in outcomes {
+        let answer = |part| match &outcome.result {
+            Ok(()) => outcome
+                .answers
+                .get(&part)
+                .map_or("-".to_string(), |answer| {
+                    answer.to_string().replace('|', "\\|")
+                }),
+            Err(_) => "failed".to_string(),
+        };
+        lines.push(format!(
+            "| {:02} | {} | {} | {} | {} | {} | {} |",
+            outcome.day,
+            answer(1),
+            answer(2),
+            format_time(outcome.parse_time),
+            format_time(outcome.part1_time),
+            format_time(outcome.part2_time),
+            format_time(Some(outcome.elapsed)),
+        ));
+    }
+    let total = |time: fn(&DayResult) -> Option<Duration>| {
+        outcomes.iter().filter_map(time).sum::<Duration>()
+    };
+    lines.push(format!(
+        "| **all** | | | {} | {} | {} | {} |",
+        format_time(Some(total(|outcome| outcome.parse_time))),
+        format_time(Some(total(|outcome| outcome.part1_time))),
+        format_time(Some(total(|outcome| outcome.part2_time))),
+        format_time(Some(total(|outcome| Some(outcome.elapsed)))),
+    ));
+    lines.join("\n")
+}
+
+// Answers and timings of a day as recorded in a JSON report, to compare
+// runs with `diff`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DayRecord {
+    pub year: u16,
+    pub day: u8,
+    // error the day failed with, if it did
+    pub error: Option<String>,
+    pub answers: BTreeMap<u8, Answer>,
+    pub parse_time: Option<Duration>,
+    pub part1_time: Option<Duration>,
+    pub part2_time: Option<Duration>,
+    pub elapsed: Duration,
+}
+
+impl From<&DayResult> for DayRecord {
+    fn from(outcome: &DayResult) -> Self {
+        DayRecord {
+            year: outcome.year,
+            day: outcome.day,
+            error: outcome.result.as_ref().err().map(|e| format!("{:#}", e)),
+            answers: outcome.answers.clone(),
+            parse_time: outcome.parse_time,
+            part1_time: outcome.part1_time,
+            part2_time: outcome.part2_time,
+            elapsed: outcome.elapsed,
+        }
+    }
+}
+
+// JSON array of the records of the days, e.g. a known-good run to `diff`
+// later runs against
+pub fn json(outcomes: &[DayResult]) -> Result<String> {
+    let records = outcomes.iter().map(DayRecord::from).collect::<Vec<_>>();
+    Ok(serde_json::to_string_pretty(&records)?)
+}
+
+// Records of the days of a JSON report
+pub fn load_json(path: &Path) -> Result<Vec<DayRecord>> {
+    let s = fs::read_to_string(path)
+        .with_context(|| format!("failed to read report {}", path.display()))?;
+    serde_json::from_str(&s).with_context(|| format!("failed to parse report {}", path.display()))
+}
+
+// Self-contained HTML page of the answers of the days, with a badge telling
+// whether they match the expected answers, and a bar chart of their timings.
+// Timings of days differ by orders of magnitude, so bars are as long as the
+// logarithm of a day's time, from 1µs to the slowest day, and split between
+// parsing and the parts in proportion to their times.
+pub fn html(year: u16, outcomes: &[DayResult], expected: &Expected) -> String {
+    let micros = |time: Duration| (time.as_secs_f64() * 1e6).max(1.0);
+    let slowest = outcomes
+        .iter()
+        .map(|outcome| micros(outcome.elapsed))
+        .fold(1.0, f64::max);
+    let (mut passed, mut failed, mut unchecked) = (0, 0, 0);
+
+    let mut rows = String::new();
+    for outcome in outcomes {
+        let badge = match expected.check(outcome) {
+            Some(true) => {
+                passed += 1;
+                "<span class=\"badge pass\">pass</span>"
+            }
+            Some(false) => {
+                failed += 1;
+                "<span class=\"badge fail\">fail</span>"
+            }
+            None if outcome.result.is_err() => {
+                failed += 1;
+                "<span class=\"badge fail\">failed</span>"
+            }
+            None => {
+                unchecked += 1;
+                "<span class=\"badge unchecked\">unchecked</span>"
+            }
+        };
+        let answer = |part| match &outcome.result {
+            Ok(()) => outcome
+                .answers
+                .get(&part)
+                .map_or("-".to_string(), |answer| escape(&answer.to_string())),
+            Err(e) => escape(&format!("{:#}", e)),
+        };
+        let length = if slowest > 1.0 {
+            micros(outcome.elapsed).ln() / slowest.ln() * 100.0
+        } else {
+            0.0
+        };
+        let mut bar = String::new();
+        for (class, name, time) in [
+            ("parse", "parse", outcome.parse_time),
+            ("part1", "part 1", outcome.part1_time),
+            ("part2", "part 2", outcome.part2_time),
+        ] {
+            let Some(time) = time else { continue };
+            let share = time.as_secs_f64() / outcome.elapsed.as_secs_f64().max(f64::EPSILON);
+            let _ = write!(
+                bar,
+                "<span class=\"{}\" style=\"width: {:.2}%\" title=\"{} {:.2?}\"></span>",
+                class,
+                length * share,
+                name,
+                time
+            );
+        }
+        let _ = writeln!(
+            rows,
+            "<tr><td>{:02}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td>\
+             <td class=\"chart\"><div class=\"bar\">{}</div></td></tr>",
+            outcome.day,
+            badge,
+            answer(1),
+            answer(2),
+            format_time(Some(outcome.elapsed)),
+            bar
+        );
+    }
+    let total = outcomes
+        .iter()
+        .map(|outcome| outcome.elapsed)
+        .sum::<Duration>();
+    let _ = writeln!(
+        rows,
+        "<tr class=\"total\"><td>all</td><td></td><td></td><td></td><td>{}</td><td></td></tr>",
+        format_time(Some(total))
+    );
+
+    format!(
+        "<!DOCTYPE html>
+<html lang=\"en\">
+<head>
+<meta charset=\"utf-8\">
+<title>Advent of Code {year}</title>
+<style>{STYLE}</style>
+</head>
+<body>
+<h1>Advent of Code {year}</h1>
+<p>{days} days solved in {total}: {passed} passed, {failed} failed, {unchecked} unchecked</p>
+<table>
+<tr><th>day</th><th></th><th>part 1</th><th>part 2</th><th>time</th><th></th></tr>
+{rows}</table>
+<p class=\"legend\"><span class=\"parse\"></span>parse<span class=\"part1\"></span>part 1<span class=\"part2\"></span>part 2</p>
+</body>
+</html>
+",
+        days = outcomes.len(),
+        total = format_time(Some(total)),
+    )
+}
+
+// `s` with the characters special to HTML escaped
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn format_time(time: Option<Duration>) -> String {
+    time.map_or("-".to_string(), |time| format!("{:.2?}", time))
+}
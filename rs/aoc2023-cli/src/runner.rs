@@ -0,0 +1,414 @@
+use std::{
+    cell::RefCell,
+    collections::BTreeMap,
+    fmt,
+    io::{self, Write},
+    path::PathBuf,
+    sync::mpsc,
+    time::Duration,
+};
+
+use anyhow::Result;
+use tracing_subscriber::fmt::MakeWriter;
+
+use owo_colors::OwoColorize;
+
+use crate::{answers::Expected, config::Config, style::Styles};
+use aoc2023_core::{input, Answer, Artifact, DayResult, InputStore, Params, RunOptions, Solver};
+
+// A puzzle day, where its input is and the solver of its parts
+#[derive(Clone)]
+pub struct Day {
+    pub year: u16,
+    pub day: u8,
+    // file of the input instead of the one in the input store, e.g. given
+    // with --input
+    pub input: Option<PathBuf>,
+    pub inputs: InputStore,
+    // parameters of the day, e.g. given with --param
+    pub params: Params,
+    pub solver: &'static dyn Solver,
+}
+
+impl fmt::Debug for Day {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Day")
+            .field("year", &self.year)
+            .field("day", &self.day)
+            .field("input", &self.input_path())
+            .field("params", &self.params)
+            .finish_non_exhaustive()
+    }
+}
+
+// Every implemented day, in order of year and day, with its input in the
+// config's input directory
+pub fn days(config: &Config) -> Vec<Day> {
+    aoc2023_core::solvers()
+        .map(|solver| Day {
+            year: solver.year(),
+            day: solver.day(),
+            input: None,
+            inputs: config.inputs(),
+            params: Params::default(),
+            solver,
+        })
+        .collect()
+}
+
+impl Day {
+    pub fn input_path(&self) -> PathBuf {
+        match &self.input {
+            Some(input) => input.clone(),
+            None => self.inputs.input_path(self.year, self.day),
+        }
+    }
+
+    // Key intermediate results of solving the day, see `Puzzle::explain`
+    pub fn explain(&self) -> Result<Vec<Artifact>> {
+        let input = match &self.input {
+            Some(input) => input::read(input)?,
+            None => self.inputs.input(self.year, self.day)?,
+        };
+        let parsed = self.solver.parse_with(&input, &self.params)?;
+        parsed.explain()
+    }
+
+    // Read the input of the day and solve both parts. Errors and panics
+    // (e.g. a failed assert in a solver) are reported in the result instead
+    // of ending the run.
+    pub fn run(&self) -> DayResult {
+        tracing::info!("Day {:02}", self.day);
+        let input = match &self.input {
+            Some(input) => input::read(input),
+            None => self.inputs.input(self.year, self.day),
+        };
+        let result = match input {
+            Ok(input) => {
+                let options = RunOptions {
+                    params: self.params.clone(),
+                    ..RunOptions::default()
+                };
+                aoc2023_core::run_day(self.solver, &input, &options)
+            }
+            Err(e) => DayResult {
+                result: Err(e),
+                ..DayResult::new(self.year, self.day)
+            },
+        };
+
+        if let Err(e) = &result.result {
+            tracing::error!("Day {:02} failed: {:#}", self.day, e);
+        }
+        tracing::info!(
+            elapsed_us = result.elapsed.as_micros() as u64,
+            "Day {:02} took {:.2?}",
+            self.day,
+            result.elapsed
+        );
+        tracing::info!("---");
+        result
+    }
+}
+
+// Size rayon's global pool, which days solved one at a time spread their
+// own loops over, to `threads` threads (0 means one per CPU)
+pub fn init_threads(threads: usize) -> Result<()> {
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .build_global()?;
+    Ok(())
+}
+
+thread_local! {
+    // Log output of the day being solved on this thread, if it's being captured
+    static CAPTURED: RefCell<Option<Vec<u8>>> = const { RefCell::new(None) };
+}
+
+// Writer for the tracing subscriber that appends to the current thread's
+// capture buffer while a day is solved in parallel, and to stdout otherwise.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CaptureWriter;
+
+impl<'a> MakeWriter<'a> for CaptureWriter {
+    type Writer = CaptureWriter;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        CaptureWriter
+    }
+}
+
+impl Write for CaptureWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        CAPTURED.with(|captured| match captured.borrow_mut().as_mut() {
+            Some(captured) => {
+                captured.extend_from_slice(buf);
+                Ok(buf.len())
+            }
+            None => io::stdout().write(buf),
+        })
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        io::stdout().flush()
+    }
+}
+
+// Run `f`, returning its result along with everything it logged
+pub fn capture<T>(f: impl FnOnce() -> T) -> (T, Vec<u8>) {
+    CAPTURED.with(|captured| *captured.borrow_mut() = Some(vec![]));
+    let res = f();
+    let output = CAPTURED
+        .with(|captured| captured.borrow_mut().take())
+        .unwrap_or_default();
+    (res, output)
+}
+
+// Solve the given days using `jobs` threads (0 means one per CPU), and
+// return their outcomes in day order.
+//
+// With a single job days are solved one after the other on the current
+// thread. Otherwise days are solved concurrently, and the log output of each
+// day is buffered and printed in day order as soon as all earlier days are
+// done, so the output reads the same as a serial run.
+pub fn run(days: &[Day], jobs: usize) -> Result<Vec<DayResult>> {
+    run_with(days, jobs, |_| {})
+}
+
+// Like `run`, calling `done` with the outcome of each day in day order, as
+// soon as it's done and its log output is printed
+pub fn run_with(
+    days: &[Day],
+    jobs: usize,
+    mut done: impl FnMut(&DayResult),
+) -> Result<Vec<DayResult>> {
+    if jobs == 1 {
+        return Ok(days
+            .iter()
+            .map(|day| {
+                let outcome = day.run();
+                done(&outcome);
+                outcome
+            })
+            .collect());
+    }
+
+    let pool = rayon::ThreadPoolBuilder::new().num_threads(jobs).build()?;
+    let (tx, rx) = mpsc::channel();
+    let mut outcomes = vec![];
+
+    pool.in_place_scope(|scope| {
+        for (idx, day) in days.iter().enumerate() {
+            let tx = tx.clone();
+            scope.spawn(move |_| {
+                let (outcome, output) = capture(|| day.run());
+                // receiver outlives the scope, so this can't fail
+                let _ = tx.send((idx, outcome, output));
+            });
+        }
+        drop(tx);
+
+        // print outputs in day order as they become available
+        let mut next = 0;
+        let mut pending = BTreeMap::new();
+        for (idx, outcome, output) in rx {
+            pending.insert(idx, (outcome, output));
+            while let Some((outcome, output)) = pending.remove(&next) {
+                io::stdout().write_all(&output)?;
+                done(&outcome);
+                outcomes.push(outcome);
+                next += 1;
+            }
+        }
+        io::stdout().flush()
+    })?;
+
+    Ok(outcomes)
+}
+
+// Solve each day against every input of its input set (see
+// `InputStore::input_set`) and print their answers side by side, e.g.
+//
+//   day 05  main       alt1
+//   part 1  388071289  35
+//   part 2  84206669   46
+//
+// Returns true if all runs succeeded.
+pub fn run_input_sets(days: &[Day], jobs: usize) -> Result<bool> {
+    let mut names = vec![];
+    let mut runs = vec![];
+    for day in days {
+        let set = day.inputs.input_set(day.year, day.day)?;
+        if set.is_empty() {
+            tracing::warn!("day {:02} has no inputs", day.day);
+        }
+        for (name, path) in set {
+            names.push(name);
+            runs.push(Day {
+                input: Some(path),
+                ..day.clone()
+            });
+        }
+    }
+    let outcomes = run(&runs, jobs)?;
+
+    let mut ok = true;
+    let mut i = 0;
+    while i < outcomes.len() {
+        let day = outcomes[i].day;
+        let len = outcomes[i..].iter().take_while(|o| o.day == day).count();
+        let (names, outcomes) = (&names[i..i + len], &outcomes[i..i + len]);
+        i += len;
+
+        let mut rows = vec![std::iter::once(format!("day {:02}", day))
+            .chain(names.iter().cloned())
+            .collect::<Vec<_>>()];
+        for part in [1, 2] {
+            let answers = outcomes.iter().map(|outcome| match &outcome.result {
+                Ok(()) => outcome
+                    .answers
+                    .get(&part)
+                    .map_or("-".to_string(), Answer::to_string),
+                Err(_) => "failed".to_string(),
+            });
+            rows.push(
+                std::iter::once(format!("part {}", part))
+                    .chain(answers)
+                    .collect(),
+            );
+        }
+        ok &= outcomes.iter().all(|outcome| outcome.result.is_ok());
+
+        let widths = (0..rows[0].len())
+            .map(|col| rows.iter().map(|row| row[col].len()).max().unwrap_or(0))
+            .collect::<Vec<_>>();
+        for row in rows {
+            let cells = row
+                .iter()
+                .zip(&widths)
+                .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+                .collect::<Vec<_>>();
+            println!("{}", cells.join("  ").trim_end());
+        }
+    }
+    Ok(ok)
+}
+
+// Print a summary of a run: after several days, the wall time, the slowest
+// stages and the parts without an answer; how many days have the expected
+// answers, if `expected` is given; then which of the days failed or got
+// other answers than expected, if any. Returns true if all days succeeded
+// with the expected answers.
+pub fn summarize(
+    outcomes: &[DayResult],
+    wall_time: Duration,
+    expected: Option<&Expected>,
+    styles: &Styles,
+) -> bool {
+    if outcomes.len() > 1 {
+        let total = outcomes
+            .iter()
+            .map(|outcome| outcome.elapsed)
+            .sum::<Duration>();
+        println!(
+            "\n{} days in {} ({} solving)",
+            outcomes.len(),
+            format!("{:.2?}", wall_time).style(styles.time),
+            format!("{:.2?}", total).style(styles.time)
+        );
+
+        let mut stages = outcomes
+            .iter()
+            .flat_map(|outcome| {
+                [
+                    ("parse", outcome.parse_time),
+                    ("part 1", outcome.part1_time),
+                    ("part 2", outcome.part2_time),
+                ]
+                .into_iter()
+                .filter_map(move |(stage, time)| Some((outcome.day, stage, time?)))
+            })
+            .collect::<Vec<_>>();
+        stages.sort_by_key(|&(_, _, time)| std::cmp::Reverse(time));
+        let slowest = stages
+            .iter()
+            .take(3)
+            .map(|(day, stage, time)| {
+                format!(
+                    "day {:02} {} {}",
+                    day,
+                    stage,
+                    format!("{:.2?}", time).style(styles.time)
+                )
+            })
+            .collect::<Vec<_>>();
+        println!("slowest: {}", slowest.join(", "));
+
+        let missing = outcomes
+            .iter()
+            .filter(|outcome| outcome.result.is_ok())
+            .flat_map(|outcome| {
+                [1, 2]
+                    .into_iter()
+                    .filter(|part| !outcome.answers.contains_key(part))
+                    .map(|part| format!("day {:02} part {}", outcome.day, part))
+            })
+            .collect::<Vec<_>>();
+        if !missing.is_empty() {
+            println!(
+                "{} {}",
+                "without an answer:".style(styles.muted),
+                missing.join(", ")
+            );
+        }
+    }
+
+    let checks = outcomes
+        .iter()
+        .map(|outcome| expected.and_then(|expected| expected.check(outcome)))
+        .collect::<Vec<_>>();
+    if expected.is_some() {
+        let count = |check| checks.iter().filter(|&&c| c == check).count();
+        println!(
+            "verified: {} passed, {} failed, {} unchecked",
+            count(Some(true)).style(styles.pass),
+            count(Some(false)).style(styles.fail),
+            count(None)
+        );
+    }
+
+    let failed = outcomes
+        .iter()
+        .zip(&checks)
+        .filter(|(outcome, &check)| outcome.result.is_err() || check == Some(false))
+        .map(|(outcome, _)| outcome)
+        .collect::<Vec<_>>();
+
+    if failed.is_empty() {
+        println!(
+            "{} all {} days succeeded",
+            styles.marker(true),
+            outcomes.len()
+        );
+        return true;
+    }
+
+    println!(
+        "{} {} of {} days failed:",
+        styles.marker(false),
+        failed.len(),
+        outcomes.len()
+    );
+    for outcome in failed {
+        match (&outcome.result, expected) {
+            (Err(e), _) => println!("  day {:02}: {:#}", outcome.day, e),
+            (Ok(_), Some(expected)) => println!(
+                "  day {:02}: {}",
+                outcome.day,
+                expected.mismatches(outcome).join(", ")
+            ),
+            (Ok(_), None) => {}
+        }
+    }
+    false
+}
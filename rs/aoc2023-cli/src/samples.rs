@@ -0,0 +1,160 @@
+// Code blocks of puzzle pages, to take the sample inputs from. The pages are
+// regular enough that the blocks are found by scanning for their tags:
+//
+//   <article class="day-desc"><h2>--- Day 1: Trebuchet?! ---</h2>
+//   <p>... For example:</p>
+//   <pre><code>1abc2
+//   pqr3stu8vwx
+//   </code></pre>
+//   ...
+//   </article>
+//
+// with one article per part that is unlocked.
+
+const ARTICLE: &str = "<article class=\"day-desc\">";
+const BLOCK_START: &str = "<pre><code>";
+const BLOCK_END: &str = "</code></pre>";
+
+// Code block of a puzzle page
+#[derive(Debug)]
+pub struct Block {
+    // part of the puzzle whose description has the block, from 1
+    pub part: usize,
+    // whether the text before the block, since the previous block, mentions
+    // an example
+    pub example: bool,
+    pub text: String,
+}
+
+// Code blocks of the page `html`, in order
+pub fn blocks(html: &str) -> Vec<Block> {
+    let mut blocks = vec![];
+    for (part, article) in html.split(ARTICLE).skip(1).enumerate() {
+        let article = article.split("</article>").next().unwrap_or(article);
+        let mut rest = article;
+        while let Some(start) = rest.find(BLOCK_START) {
+            let before = &rest[..start];
+            let code = &rest[start + BLOCK_START.len()..];
+            let end = code.find(BLOCK_END).unwrap_or(code.len());
+            blocks.push(Block {
+                part: part + 1,
+                example: before.to_lowercase().contains("example"),
+                text: text(&code[..end]),
+            });
+            rest = &code[end..];
+        }
+    }
+    blocks
+}
+
+// Indices of the samples of each part: the first block introduced as an
+// example, or else the first block of the part. Parts with the same sample
+// share it.
+pub fn guess(blocks: &[Block]) -> Vec<usize> {
+    let parts = blocks.iter().map(|block| block.part).max().unwrap_or(0);
+    let mut samples = Vec::<usize>::new();
+    for part in 1..=parts {
+        let of_part = |i: &usize| blocks[*i].part == part;
+        let sample = (0..blocks.len())
+            .filter(of_part)
+            .find(|&i| blocks[i].example)
+            .or_else(|| (0..blocks.len()).find(of_part));
+        if let Some(sample) = sample {
+            if samples
+                .iter()
+                .all(|&s| blocks[s].text != blocks[sample].text)
+            {
+                samples.push(sample);
+            }
+        }
+    }
+    samples
+}
+
+// Text of the markup of a code block, i.e. without tags like <em> and with
+// entities decoded
+fn text(code: &str) -> String {
+    let mut text = String::with_capacity(code.len());
+    let mut in_tag = false;
+    for c in code.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    let mut text = text
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&");
+    if !text.ends_with('\n') {
+        text.push('\n');
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Puzzle page of a day with both parts unlocked, cut short
+    const PAGE: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 1: Trebuchet?! ---</h2><p>The newly-improved calibration document consists of lines of text.</p>
+<p>For example:</p>
+<pre><code>1abc2
+pqr3stu8vwx
+a1b2c3d4e5f
+treb7uchet
+</code></pre>
+<p>In this example, the calibration values of these four lines are <code>12</code>, <code>38</code>, <code>15</code>, and <code>77</code>. Adding these together produces <code><em>142</em></code>.</p>
+</article>
+<p>Your puzzle answer was <code>54927</code>.</p><article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>Your calculation isn't quite right.</p>
+<pre><code>one -&gt; 1
+</code></pre>
+<p>Equipped with this new information, you now need to find the real first and last digit on each line. For example:</p>
+<pre><code><em>two</em>1nine
+eightwothree
+</code></pre>
+</article>
+</main>"#;
+
+    #[test]
+    fn test_blocks() {
+        assert!(blocks("<p>no puzzle</p>").is_empty());
+        let blocks = blocks(PAGE);
+        assert_eq!(blocks.len(), 3);
+        assert_eq!(
+            blocks[0].text,
+            "1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet\n"
+        );
+        assert_eq!((blocks[0].part, blocks[0].example), (1, true));
+        assert_eq!(blocks[1].text, "one -> 1\n");
+        assert_eq!((blocks[1].part, blocks[1].example), (2, false));
+        assert_eq!(blocks[2].text, "two1nine\neightwothree\n");
+        assert_eq!((blocks[2].part, blocks[2].example), (2, true));
+    }
+
+    #[test]
+    fn test_guess() {
+        assert_eq!(guess(&blocks(PAGE)), [0, 2]);
+
+        // without an example the first block of the part, and a sample shared
+        // by the parts once
+        let block = |part, example, text: &str| Block {
+            part,
+            example,
+            text: text.to_string(),
+        };
+        let blocks = [
+            block(1, false, "a\n"),
+            block(1, false, "b\n"),
+            block(2, true, "a\n"),
+        ];
+        assert_eq!(guess(&blocks), [0]);
+        assert!(guess(&[]).is_empty());
+    }
+}
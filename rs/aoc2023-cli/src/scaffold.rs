@@ -0,0 +1,182 @@
+use std::fs;
+
+use anyhow::Result;
+
+use crate::config::{self, Config};
+
+// Skeleton of a new day, with `YYYY` replaced by the year and `DAY` by the day
+const TEMPLATE: &str = r#"use anyhow::Result;
+
+use crate::{
+    log,
+    solver::{self, FromInput, Puzzle},
+    Answer,
+};
+
+#[derive(Debug)]
+pub struct Input<'a> {
+    lines: Vec<&'a str>,
+}
+
+impl<'a> FromInput<'a> for Input<'a> {
+    fn from_input(s: &'a str) -> Result<Self> {
+        let lines = s.lines().collect();
+        Ok(Input { lines })
+    }
+}
+
+impl Input<'_> {
+    fn part1(&self) -> usize {
+        self.lines.len()
+    }
+
+    fn part2(&self) -> usize {
+        self.lines.len()
+    }
+}
+
+pub struct Solution;
+
+solver::register!(Solution);
+
+impl Puzzle for Solution {
+    type Input<'a> = Input<'a>;
+
+    fn year(&self) -> u16 {
+        YYYY
+    }
+
+    fn day(&self) -> u8 {
+        DAY
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Input<'a>> {
+        let input = Input::from_input(input)?;
+        log::debug!("input: {:?}", input);
+        Ok(input)
+    }
+
+    fn part1(&self, input: &Input<'_>) -> Result<Option<Answer>> {
+        Ok(Some(input.part1().into()))
+    }
+
+    fn part2(&self, input: &Input<'_>) -> Result<Option<Answer>> {
+        Ok(Some(input.part2().into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::InputStore;
+
+    #[test]
+    fn test_with_sample() -> Result<()> {
+        let input = &InputStore::default().sample(YYYY, DAY)?;
+        let input = Input::from_input(input)?;
+        assert_eq!(input.part1(), 0);
+        assert_eq!(input.part2(), 0);
+        Ok(())
+    }
+}
+"#;
+
+// Create the source, input and sample files of a new day, and declare the
+// day's module in its year's module. The day's solver registers itself.
+pub fn new_day(year: u16, day: u8, config: &Config) -> Result<()> {
+    anyhow::ensure!((1..=25).contains(&day), "day must be in 1..=25: {}", day);
+
+    let src = config::core_dir().join("src");
+    let name = format!("day{:02}", day);
+
+    let module = format!("y{}", year);
+    let dir = src.join(&module);
+    let source = dir.join(format!("{}.rs", name));
+    anyhow::ensure!(!source.exists(), "{} already exists", source.display());
+    fs::create_dir_all(&dir)?;
+    let template = TEMPLATE
+        .replace("YYYY", &year.to_string())
+        .replace("DAY", &day.to_string());
+    fs::write(&source, template)?;
+    tracing::info!("created {}", source.display());
+
+    for path in [config.input_path(year, day), config.sample_path(year, day)] {
+        if !path.exists() {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&path, "")?;
+            tracing::info!("created {}", path.display());
+        }
+    }
+
+    let mod_rs = dir.join("mod.rs");
+    if mod_rs.exists() {
+        fs::write(&mod_rs, register_day(&fs::read_to_string(&mod_rs)?, &name))?;
+    } else {
+        // the first day of a year creates the year's module
+        fs::write(&mod_rs, register_day("", &name))?;
+        let lib = src.join("lib.rs");
+        fs::write(&lib, register_module(&fs::read_to_string(&lib)?, &module))?;
+        tracing::info!("registered {} in {}", module, lib.display());
+        let prelude = src.join("prelude.rs");
+        fs::write(
+            &prelude,
+            register_decl(
+                &fs::read_to_string(&prelude)?,
+                &format!("pub use crate::{};", module),
+                "pub use crate::y",
+            ),
+        )?;
+        tracing::info!("registered {} in {}", module, prelude.display());
+    }
+    tracing::info!("registered {} in {}", name, mod_rs.display());
+
+    Ok(())
+}
+
+// Add `pub mod <name>;` to a module, keeping modules sorted. A commented
+// out declaration of the module is uncommented instead.
+fn register_module(module: &str, name: &str) -> String {
+    register_decl(module, &format!("pub mod {};", name), "pub mod ")
+}
+
+// Add the module of a day to its year's module, compiled in with the day's
+// feature
+fn register_day(module: &str, name: &str) -> String {
+    register_decl(
+        module,
+        &format!("#[cfg(feature = \"{}\")]\npub mod {};", name, name),
+        "pub mod ",
+    )
+}
+
+// Add `decl`, a line optionally preceded by attributes, among the lines
+// starting with `prefix`, keeping them sorted
+fn register_decl(source: &str, decl: &str, prefix: &str) -> String {
+    let key = decl.lines().last().unwrap_or(decl);
+    let mut lines = source
+        .lines()
+        .filter(|line| line.trim_start_matches("// ") != key)
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+    let idx = lines
+        .iter()
+        .position(|line| line.starts_with(prefix) && line.as_str() > key)
+        .map(|mut idx| {
+            // keep the attributes of the next declaration with it
+            while idx > 0 && lines[idx - 1].starts_with("#[") {
+                idx -= 1;
+            }
+            idx
+        })
+        .or_else(|| {
+            lines
+                .iter()
+                .rposition(|line| line.trim_start_matches("// ").starts_with(prefix))
+                .map(|idx| idx + 1)
+        })
+        .unwrap_or(0);
+    lines.insert(idx, decl.to_string());
+    lines.join("\n") + "\n"
+}
@@ -0,0 +1,79 @@
+use std::{env, fs};
+
+use anyhow::{Context, Result};
+
+use crate::config::Config;
+
+// Environment variable with the adventofcode.com session cookie, taking
+// precedence over the keyring and the session file of the config
+const VAR: &str = "AOC_SESSION";
+
+// Entry of the session cookie in the platform keyring
+const SERVICE: &str = "adventofcode.com";
+const USER: &str = "session";
+
+// Session cookie from $AOC_SESSION, the keyring (see `login`), or the session
+// file of the config, whichever comes first
+pub fn get(config: &Config) -> Result<String> {
+    let session = if let Ok(session) = env::var(VAR) {
+        session
+    } else if let Some(session) = from_keyring()? {
+        session
+    } else if let Some(file) = &config.session_file {
+        fs::read_to_string(file)
+            .with_context(|| format!("failed to read session file {}", file.display()))?
+    } else {
+        anyhow::bail!(
+            "no session cookie; run `aoc2023 login`, or set {} or session_file in aoc.toml",
+            VAR
+        );
+    };
+    let session = session.trim();
+    anyhow::ensure!(!session.is_empty(), "the session cookie is empty");
+    Ok(session.to_string())
+}
+
+// Prompt for the session cookie, i.e. the `session` cookie of
+// adventofcode.com in the browser, and store it in the keyring
+pub fn login() -> Result<()> {
+    let session = rpassword::prompt_password("session cookie: ")?;
+    let session = session.trim();
+    anyhow::ensure!(!session.is_empty(), "the session cookie is empty");
+    if !session.bytes().all(|b| b.is_ascii_hexdigit()) {
+        tracing::warn!("the session cookie isn't hex digits, as adventofcode.com's are");
+    }
+    entry()?
+        .set_password(session)
+        .context("failed to store the session cookie in the keyring")?;
+    tracing::info!("stored the session cookie in the keyring");
+    Ok(())
+}
+
+// Remove the session cookie from the keyring
+pub fn logout() -> Result<()> {
+    match entry()?.delete_credential() {
+        Ok(()) => tracing::info!("removed the session cookie from the keyring"),
+        Err(keyring::Error::NoEntry) => tracing::info!("no session cookie in the keyring"),
+        Err(e) => {
+            return Err(e).context("failed to remove the session cookie from the keyring");
+        }
+    }
+    Ok(())
+}
+
+// Session cookie in the keyring, if any. A keyring that can't be reached
+// counts as empty, so the session file still works without one.
+fn from_keyring() -> Result<Option<String>> {
+    match entry()?.get_password() {
+        Ok(session) => Ok(Some(session)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => {
+            tracing::debug!("failed to read the keyring: {}", e);
+            Ok(None)
+        }
+    }
+}
+
+fn entry() -> Result<keyring::Entry> {
+    keyring::Entry::new(SERVICE, USER).context("failed to open the keyring")
+}
@@ -0,0 +1,143 @@
+use std::{
+    env,
+    io::{self, IsTerminal},
+    time::Duration,
+};
+
+use clap::ValueEnum;
+use owo_colors::{OwoColorize, Style, Styled};
+use serde::Deserialize;
+
+use aoc2023_core::{Answer, Artifact, DayResult};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Theme {
+    /// Bright colors, for terminals with a dark background
+    #[default]
+    Dark,
+    /// Deep colors, for terminals with a light background
+    Light,
+}
+
+// Styles of the parts of the output; all plain when colors are off
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Styles {
+    pub header: Style,
+    pub answer: Style,
+    pub time: Style,
+    pub pass: Style,
+    pub fail: Style,
+    pub muted: Style,
+}
+
+// Whether to color the output: not with --no-color or $NO_COLOR (see
+// https://no-color.org), nor when stdout isn't a terminal, e.g. piped to a
+// file
+pub fn enabled(no_color: bool) -> bool {
+    !no_color
+        && env::var_os("NO_COLOR").is_none_or(|value| value.is_empty())
+        && io::stdout().is_terminal()
+}
+
+impl Styles {
+    pub fn new(theme: Theme, color: bool) -> Self {
+        if !color {
+            return Styles::default();
+        }
+        match theme {
+            Theme::Dark => Styles {
+                header: Style::new().bold().bright_cyan(),
+                answer: Style::new().bold().bright_white(),
+                time: Style::new().bright_yellow(),
+                pass: Style::new().bold().bright_green(),
+                fail: Style::new().bold().bright_red(),
+                muted: Style::new().bright_black(),
+            },
+            Theme::Light => Styles {
+                header: Style::new().bold().blue(),
+                answer: Style::new().bold().black(),
+                time: Style::new().magenta(),
+                pass: Style::new().bold().green(),
+                fail: Style::new().bold().red(),
+                muted: Style::new().dimmed(),
+            },
+        }
+    }
+
+    // Marker of a success or a failure
+    pub fn marker(&self, ok: bool) -> Styled<&'static str> {
+        if ok {
+            self.pass.style("✓")
+        } else {
+            self.fail.style("✗")
+        }
+    }
+
+    // Print the answers and timings of a day, aligned in columns, e.g.
+    //
+    //   Day 01    ✓                           7.96ms
+    //     parse                             348.00ns
+    //     part 1  54927                       3.67ms
+    //     part 2  54581                       4.29ms
+    pub fn print_day(&self, outcome: &DayResult) {
+        println!(
+            "{:<10}{:<answer$}{:>12}",
+            format!("Day {:02}", outcome.day).style(self.header),
+            self.marker(outcome.result.is_ok()),
+            format!("{:.2?}", outcome.elapsed).style(self.time),
+            answer = ANSWER_WIDTH
+        );
+        if let Some(time) = outcome.parse_time {
+            println!(
+                "  {:<8}{:answer$}{:>12}",
+                "parse".style(self.muted),
+                "",
+                format!("{:.2?}", time).style(self.time),
+                answer = ANSWER_WIDTH
+            );
+        }
+        for (part, time) in [(1, outcome.part1_time), (2, outcome.part2_time)] {
+            let Some(answer) = outcome.answers.get(&part) else {
+                continue;
+            };
+            println!(
+                "  {:<8}{:<answer$}{:>12}",
+                format!("part {}", part),
+                answer.to_string().style(self.answer),
+                format_time(time).style(self.time),
+                answer = ANSWER_WIDTH
+            );
+        }
+        if let Err(e) = &outcome.result {
+            println!("  {} {:#}", "failed:".style(self.fail), e);
+        }
+    }
+
+    // Print the key intermediate results of a day, each titled, with its
+    // lines indented under it
+    pub fn print_artifacts(&self, artifacts: &[Artifact]) {
+        if artifacts.is_empty() {
+            println!("  {}", "nothing to explain".style(self.muted));
+        }
+        for artifact in artifacts {
+            println!("  {}", artifact.title.style(self.header));
+            for line in &artifact.lines {
+                println!("    {}", line);
+            }
+        }
+    }
+
+    // An answer of a part as printed by `verify`
+    pub fn answer(&self, answer: &Answer) -> Styled<String> {
+        self.answer.style(answer.to_string())
+    }
+}
+
+// Width of the column of answers, enough for the 15 digits of the largest
+// answers
+const ANSWER_WIDTH: usize = 20;
+
+fn format_time(time: Option<Duration>) -> String {
+    time.map_or("-".to_string(), |time| format!("{:.2?}", time))
+}
@@ -0,0 +1,343 @@
+use std::{fmt, fs, io, path::Path, time::Duration};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+
+use aoc2023_core::Answer;
+
+use crate::{config::Config, fetch, runner::Day, session};
+
+// Least time between two submissions, on top of the waits adventofcode.com
+// asks for
+const MIN_INTERVAL: Duration = Duration::from_secs(60);
+
+// What adventofcode.com made of an answer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Outcome {
+    Right,
+    TooHigh,
+    TooLow,
+    Wrong,
+    // answered too soon after the previous answer
+    TooSoon,
+    // the part is solved already, or still locked
+    WrongLevel,
+    Unknown,
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Outcome::Right => "right",
+            Outcome::TooHigh => "too high",
+            Outcome::TooLow => "too low",
+            Outcome::Wrong => "wrong",
+            Outcome::TooSoon => "too soon",
+            Outcome::WrongLevel => "already solved or locked",
+            Outcome::Unknown => "unknown",
+        };
+        f.pad(s)
+    }
+}
+
+// Answer sent to adventofcode.com
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Submission {
+    year: u16,
+    day: u8,
+    part: u8,
+    answer: String,
+    outcome: Outcome,
+    // RFC 3339
+    at: String,
+}
+
+// Answers sent so far, and until when to hold off sending more, read from a
+// file like
+//
+//   wait_until = "2023-12-05T05:11:32Z"
+//
+//   [[submission]]
+//   year = 2023
+//   day = 5
+//   part = 1
+//   answer = "388071289"
+//   outcome = "right"
+//   at = "2023-12-05T05:10:32Z"
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Submissions {
+    wait_until: Option<String>,
+    #[serde(default, rename = "submission")]
+    submissions: Vec<Submission>,
+}
+
+impl Submissions {
+    fn load(path: &Path) -> Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(s) => {
+                toml::from_str(&s).with_context(|| format!("failed to parse {}", path.display()))
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Submissions::default()),
+            Err(e) => Err(e).with_context(|| format!("failed to read {}", path.display())),
+        }
+    }
+
+    fn save(&self, path: &Path) -> Result<()> {
+        let s = toml::to_string(self)?;
+        fs::write(path, s).with_context(|| format!("failed to write {}", path.display()))
+    }
+
+    // Reason not to send `answer`, known from the answers sent before
+    fn check(&self, day: &Day, part: u8, answer: &str) -> Result<()> {
+        let sent = self
+            .submissions
+            .iter()
+            .filter(|s| s.year == day.year && s.day == day.day && s.part == part)
+            .collect::<Vec<_>>();
+        if let Some(right) = sent.iter().find(|s| s.outcome == Outcome::Right) {
+            anyhow::bail!("part {} is solved already, with {}", part, right.answer);
+        }
+        if let Some(same) = sent
+            .iter()
+            .find(|s| s.answer == answer && s.outcome != Outcome::TooSoon)
+        {
+            anyhow::bail!(
+                "{} was sent already at {}: {}",
+                answer,
+                same.at,
+                same.outcome
+            );
+        }
+        // wrong numbers bound the right one
+        if let Ok(n) = answer.parse::<i128>() {
+            for s in &sent {
+                let Ok(m) = s.answer.parse::<i128>() else {
+                    continue;
+                };
+                match s.outcome {
+                    Outcome::TooHigh if n >= m => {
+                        anyhow::bail!("{} is too high; {} was too high already", n, m)
+                    }
+                    Outcome::TooLow if n <= m => {
+                        anyhow::bail!("{} is too low; {} was too low already", n, m)
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        let now = Utc::now();
+        let last = self
+            .submissions
+            .iter()
+            .filter_map(|s| parse_time(&s.at))
+            .max();
+        let wait_until = self.wait_until.as_deref().and_then(parse_time);
+        let earliest = last
+            .map(|last| last + MIN_INTERVAL)
+            .into_iter()
+            .chain(wait_until)
+            .max();
+        if let Some(earliest) = earliest.filter(|&earliest| earliest > now) {
+            anyhow::bail!(
+                "wait {}s before sending another answer",
+                (earliest - now).num_seconds() + 1
+            );
+        }
+        Ok(())
+    }
+}
+
+// Solve a part of `day` and send its answer to adventofcode.com, unless
+// earlier answers tell it's wrong or another answer was sent too recently.
+// Answers and what adventofcode.com made of them are recorded in the
+// submissions file. Returns whether the answer is right.
+pub fn run(day: &Day, part: u8, config: &Config) -> Result<bool> {
+    anyhow::ensure!((1..=2).contains(&part), "part must be 1 or 2: {}", part);
+
+    let path = &config.submissions_file;
+    let mut submissions = Submissions::load(path)?;
+    let outcome = day.run();
+    outcome.result?;
+    let answer = outcome
+        .answers
+        .get(&part)
+        .map(Answer::to_string)
+        .with_context(|| format!("day {} has no answer to part {}", day.day, part))?;
+    submissions.check(day, part, &answer)?;
+
+    let session = session::get(config)?;
+    let url = format!(
+        "https://adventofcode.com/{}/day/{}/answer",
+        day.year, day.day
+    );
+    tracing::info!("sending {} to {}", answer, url);
+    let html = fetch::request("POST", &url, Some(&session))
+        .send_form(&[("level", &part.to_string()), ("answer", &answer)])
+        .with_context(|| format!("failed to send the answer to {}", url))?
+        .into_string()
+        .with_context(|| format!("failed to read the response of {}", url))?;
+
+    let message = message(&html);
+    let (outcome, wait) = outcome_of(&message);
+    let now = Utc::now();
+    if let Some(wait) = wait {
+        submissions.wait_until = Some(format_time(now + wait));
+    }
+    submissions.submissions.push(Submission {
+        year: day.year,
+        day: day.day,
+        part,
+        answer: answer.clone(),
+        outcome,
+        at: format_time(now),
+    });
+    submissions.save(path)?;
+
+    println!("day {:02} part {}: {}: {}", day.day, part, answer, outcome);
+    println!("{}", message);
+    Ok(outcome == Outcome::Right)
+}
+
+// Text of the article of the response page, which tells what became of the
+// answer
+fn message(html: &str) -> String {
+    let article = html
+        .split_once("<article>")
+        .and_then(|(_, rest)| rest.split_once("</article>"))
+        .map_or(html, |(article, _)| article);
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+// Outcome of the answer told by `message`, and how long to wait before
+// sending another answer, if told
+fn outcome_of(message: &str) -> (Outcome, Option<chrono::Duration>) {
+    let outcome = if message.contains("That's the right answer") {
+        Outcome::Right
+    } else if message.contains("That's not the right answer") {
+        if message.contains("too high") {
+            Outcome::TooHigh
+        } else if message.contains("too low") {
+            Outcome::TooLow
+        } else {
+            Outcome::Wrong
+        }
+    } else if message.contains("You gave an answer too recently") {
+        Outcome::TooSoon
+    } else if message.contains("You don't seem to be solving the right level") {
+        Outcome::WrongLevel
+    } else {
+        Outcome::Unknown
+    };
+    (outcome, wait(message))
+}
+
+// Wait told by a message like "You have 1m 12s left to wait." or "Please
+// wait 5 minutes before trying again."
+fn wait(message: &str) -> Option<chrono::Duration> {
+    if let Some((before, _)) = message.split_once(" left to wait") {
+        let mut seconds = 0;
+        for word in before.split_whitespace().rev() {
+            let Some(at) = word.find(|c: char| !c.is_ascii_digit()) else {
+                break;
+            };
+            let (n, unit) = word.split_at(at);
+            let Ok(n) = n.parse::<i64>() else { break };
+            seconds += match unit {
+                "h" => n * 3600,
+                "m" => n * 60,
+                "s" => n,
+                _ => break,
+            };
+        }
+        return (seconds > 0).then(|| chrono::Duration::seconds(seconds));
+    }
+    let (_, after) = message.split_once("wait ")?;
+    let mut words = after.split_whitespace();
+    let n = match words.next()? {
+        "one" => 1,
+        n => n.parse::<i64>().ok()?,
+    };
+    words
+        .next()?
+        .starts_with("minute")
+        .then(|| chrono::Duration::minutes(n))
+}
+
+fn parse_time(s: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(s)
+        .ok()
+        .map(|time| time.with_timezone(&Utc))
+}
+
+fn format_time(time: DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Articles of response pages of adventofcode.com
+    const RIGHT: &str = "<main>\n<article><p>That's the right answer!  You are <span class=\"day-success\">one gold star</span> closer to restoring snow operations. <a href=\"/2023/day/5#part2\">[Continue to Part Two]</a></p></article>\n</main>";
+    const TOO_HIGH: &str = "<article><p>That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data; there are also some general tips on the <a href=\"/2023/about\">about page</a>, or you can ask for hints on the <a href=\"https://www.reddit.com/r/adventofcode/\" target=\"_blank\">subreddit</a>.  Please wait one minute before trying again. <a href=\"/2023/day/5\">[Return to Day 5]</a></p></article>";
+    const TOO_LOW: &str = "<article><p>That's not the right answer; your answer is too low.  If you're stuck, make sure you're using the full input data.  Please wait 5 minutes before trying again. <a href=\"/2023/day/5\">[Return to Day 5]</a></p></article>";
+    const TOO_SOON: &str = "<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 12s left to wait. <a href=\"/2023/day/5\">[Return to Day 5]</a></p></article>";
+    const WRONG_LEVEL: &str = "<article><p>You don't seem to be solving the right level.  Did you already complete it? <a href=\"/2023/day/5\">[Return to Day 5]</a></p></article>";
+
+    #[test]
+    fn test_message() {
+        assert_eq!(
+            message(RIGHT),
+            "That's the right answer! You are one gold star closer to restoring snow operations. [Continue to Part Two]"
+        );
+        assert_eq!(message("no article"), "no article");
+    }
+
+    #[test]
+    fn test_outcome_of() {
+        let outcome = |html| outcome_of(&message(html));
+        assert_eq!(outcome(RIGHT), (Outcome::Right, None));
+        assert_eq!(
+            outcome(TOO_HIGH),
+            (Outcome::TooHigh, Some(chrono::Duration::minutes(1)))
+        );
+        assert_eq!(
+            outcome(TOO_LOW),
+            (Outcome::TooLow, Some(chrono::Duration::minutes(5)))
+        );
+        assert_eq!(
+            outcome(TOO_SOON),
+            (Outcome::TooSoon, Some(chrono::Duration::seconds(72)))
+        );
+        assert_eq!(outcome(WRONG_LEVEL), (Outcome::WrongLevel, None));
+        assert_eq!(outcome("<article>Oops</article>"), (Outcome::Unknown, None));
+    }
+
+    #[test]
+    fn test_wait() {
+        let seconds = |message| wait(message).map(|wait| wait.num_seconds());
+        assert_eq!(seconds("You have 1h 2m 3s left to wait."), Some(3723));
+        assert_eq!(seconds("You have 45s left to wait."), Some(45));
+        // a bare number before the wait ends it, without losing it
+        assert_eq!(seconds("Try 2 of 3 1m 12s left to wait."), Some(72));
+        assert_eq!(seconds("You have 30 left to wait."), None);
+        assert_eq!(
+            seconds("Please wait one minute before trying again."),
+            Some(60)
+        );
+        assert_eq!(seconds("Please wait a moment."), None);
+    }
+}
@@ -0,0 +1,58 @@
+use std::fs;
+
+use anyhow::Result;
+use chrono::{Datelike, Utc};
+use chrono_tz::US::Eastern;
+
+use aoc2023_core::input;
+
+use crate::{config::Config, runner};
+
+// Year and day of today's puzzle; puzzles unlock at midnight US/Eastern,
+// December 1st to 25th
+fn puzzle_day() -> Result<(u16, u8)> {
+    let now = Utc::now().with_timezone(&Eastern);
+    anyhow::ensure!(
+        now.month() == 12 && now.day() <= 25,
+        "no puzzle today ({}); run a day with `aoc2023 run <day>`",
+        now.format("%Y-%m-%d %Z")
+    );
+    Ok((now.year() as u16, now.day() as u8))
+}
+
+// Solve today's puzzle and print its answers and timing
+pub fn run(config: &Config) -> Result<bool> {
+    let (year, day) = puzzle_day()?;
+
+    let input = config.input_path(year, day);
+    let present = input::find(&input)
+        .and_then(|input| fs::metadata(input).ok())
+        .is_some_and(|metadata| metadata.len() > 0);
+    anyhow::ensure!(
+        present,
+        "input {} is missing; download it with `aoc2023 fetch {}`",
+        input.display(),
+        day
+    );
+
+    let days = runner::days(config);
+    let Some(day) = days.iter().find(|d| d.year == year && d.day == day) else {
+        anyhow::bail!(
+            "day {} of {} is not implemented; create it with `aoc2023 new-day --year {} {}`",
+            day,
+            year,
+            year,
+            day
+        );
+    };
+    let outcome = runner::run(std::slice::from_ref(day), 1)?.remove(0);
+
+    for (part, answer) in &outcome.answers {
+        println!("day {:02} part {}: {}", outcome.day, part, answer);
+    }
+    match &outcome.result {
+        Ok(()) => println!("took {:.2?}", outcome.elapsed),
+        Err(e) => println!("failed after {:.2?}: {:#}", outcome.elapsed, e),
+    }
+    Ok(outcome.result.is_ok())
+}
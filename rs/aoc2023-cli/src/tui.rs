@@ -0,0 +1,221 @@
+use std::{
+    sync::mpsc::{self, Receiver, Sender},
+    thread,
+    time::{Duration, Instant},
+};
+
+use anyhow::Result;
+use ratatui::{
+    crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind},
+    layout::{Constraint, Layout},
+    style::{Color, Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, List, ListItem, ListState, Paragraph},
+    DefaultTerminal, Frame,
+};
+
+use aoc2023_core::DayResult;
+
+use crate::runner::{self, Day};
+
+// How often the dashboard redraws, so timings of running days tick along
+const TICK: Duration = Duration::from_millis(100);
+
+// Lines scrolled by page up/down in the output pane
+const PAGE: u16 = 20;
+
+#[derive(Debug, Default)]
+enum Status {
+    #[default]
+    Idle,
+    Running(Instant),
+    Done {
+        elapsed: Duration,
+        error: Option<String>,
+    },
+}
+
+// A day as shown in the dashboard, with the output of its last run
+#[derive(Debug)]
+struct Entry {
+    day: Day,
+    status: Status,
+    output: String,
+}
+
+// A finished run: index of the entry, its outcome and log output
+type Finished = (usize, DayResult, Vec<u8>);
+
+struct App {
+    entries: Vec<Entry>,
+    list: ListState,
+    scroll: u16,
+    tx: Sender<Finished>,
+    rx: Receiver<Finished>,
+    quit: bool,
+}
+
+impl App {
+    fn new(days: &[Day]) -> Self {
+        let entries = days
+            .iter()
+            .map(|day| Entry {
+                day: day.clone(),
+                status: Status::Idle,
+                output: String::new(),
+            })
+            .collect();
+        let (tx, rx) = mpsc::channel();
+        Self {
+            entries,
+            list: ListState::default().with_selected(Some(0)),
+            scroll: 0,
+            tx,
+            rx,
+            quit: false,
+        }
+    }
+
+    fn run(mut self, terminal: &mut DefaultTerminal) -> Result<()> {
+        while !self.quit {
+            terminal.draw(|frame| self.draw(frame))?;
+            if event::poll(TICK)? {
+                if let Event::Key(key) = event::read()? {
+                    self.handle_key(key)?;
+                }
+            }
+            while let Ok(finished) = self.rx.try_recv() {
+                self.finish(finished);
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> Result<()> {
+        if key.kind != KeyEventKind::Press {
+            return Ok(());
+        }
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => self.quit = true,
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.list.select_previous();
+                self.scroll = 0;
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.list.select_next();
+                self.scroll = 0;
+            }
+            KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(PAGE),
+            KeyCode::PageDown => self.scroll = self.scroll.saturating_add(PAGE),
+            KeyCode::Home | KeyCode::Char('g') => self.scroll = 0,
+            KeyCode::Enter | KeyCode::Char('r') => {
+                if let Some(idx) = self.selected() {
+                    self.start(idx)?;
+                }
+            }
+            KeyCode::Char('a') => {
+                for idx in 0..self.entries.len() {
+                    self.start(idx)?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn selected(&self) -> Option<usize> {
+        self.list
+            .selected()
+            .map(|idx| idx.min(self.entries.len() - 1))
+    }
+
+    // Solve the day of entry `idx` in the background, unless it's running
+    fn start(&mut self, idx: usize) -> Result<()> {
+        let entry = &mut self.entries[idx];
+        if matches!(entry.status, Status::Running(_)) {
+            return Ok(());
+        }
+        entry.status = Status::Running(Instant::now());
+        entry.output.clear();
+
+        let day = entry.day.clone();
+        let tx = self.tx.clone();
+        thread::Builder::new()
+            .name(format!("day{:02}", day.day))
+            .spawn(move || {
+                let (outcome, output) = runner::capture(|| day.run());
+                // the dashboard may have quit in the meantime
+                let _ = tx.send((idx, outcome, output));
+            })?;
+        Ok(())
+    }
+
+    fn finish(&mut self, (idx, outcome, output): Finished) {
+        let entry = &mut self.entries[idx];
+        entry.status = Status::Done {
+            elapsed: outcome.elapsed,
+            error: outcome.result.err().map(|e| format!("{:#}", e)),
+        };
+        entry.output = String::from_utf8_lossy(&output).into_owned();
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [main, footer] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(frame.area());
+        let [days, output] =
+            Layout::horizontal([Constraint::Length(28), Constraint::Fill(1)]).areas(main);
+
+        let items = self
+            .entries
+            .iter()
+            .map(|entry| {
+                let (marker, elapsed) = match &entry.status {
+                    Status::Idle => (Span::raw(" "), None),
+                    Status::Running(start) => (Span::raw("…").yellow(), Some(start.elapsed())),
+                    Status::Done { elapsed, error } => match error {
+                        None => (Span::raw("✓").green(), Some(*elapsed)),
+                        Some(_) => (Span::raw("✗").red(), Some(*elapsed)),
+                    },
+                };
+                let elapsed = elapsed
+                    .map(|elapsed| format!("{:>12.2?}", elapsed))
+                    .unwrap_or_default();
+                ListItem::new(Line::from(vec![
+                    Span::raw(format!("Day {:02} ", entry.day.day)),
+                    marker,
+                    Span::raw(elapsed).dim(),
+                ]))
+            })
+            .collect::<Vec<_>>();
+        let list = List::new(items)
+            .block(Block::bordered().title(" Days "))
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(list, days, &mut self.list);
+
+        if let Some(idx) = self.selected() {
+            let entry = &self.entries[idx];
+            let title = match &entry.status {
+                Status::Idle => format!(" Day {:02} ", entry.day.day),
+                Status::Running(_) => format!(" Day {:02}: running ", entry.day.day),
+                Status::Done { error: None, .. } => format!(" Day {:02}: ok ", entry.day.day),
+                Status::Done { error: Some(e), .. } => {
+                    format!(" Day {:02}: failed: {} ", entry.day.day, e)
+                }
+            };
+            let lines = entry.output.lines().count() as u16;
+            self.scroll = self.scroll.min(lines.saturating_sub(1));
+            let paragraph = Paragraph::new(entry.output.as_str())
+                .block(Block::bordered().title(title))
+                .scroll((self.scroll, 0));
+            frame.render_widget(paragraph, output);
+        }
+
+        let help = " ↑/↓ select  enter run  a run all  pgup/pgdn scroll  q quit";
+        frame.render_widget(Line::from(help).fg(Color::DarkGray), footer);
+    }
+}
+
+// Run the interactive dashboard over the given days until the user quits
+pub fn run(days: &[Day]) -> Result<()> {
+    ratatui::run(|terminal| App::new(days).run(terminal))
+}
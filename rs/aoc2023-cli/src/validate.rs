@@ -0,0 +1,48 @@
+use anyhow::Result;
+
+use aoc2023_core::input;
+
+use crate::runner::Day;
+
+// Violations shown per input; the rest are only counted
+const MAX_SHOWN: usize = 10;
+
+// Check the inputs of the days, and their alternates, against the invariants
+// the days assume of them, printing what's broken. Returns true if all
+// inputs are valid.
+pub fn run(days: &[Day]) -> Result<bool> {
+    let mut valid = true;
+    for day in days {
+        let set = day.inputs.input_set(day.year, day.day)?;
+        if set.is_empty() {
+            println!("day {:02}: no input", day.day);
+            valid = false;
+        }
+        for (name, path) in set {
+            let label = if name == "main" {
+                format!("day {:02}", day.day)
+            } else {
+                format!("day {:02} ({})", day.day, name)
+            };
+            let violations = day.solver.validate(&input::read(&path)?);
+            if violations.is_empty() {
+                println!("{}: ok", label);
+                continue;
+            }
+            valid = false;
+            println!(
+                "{}: {} violations in {}",
+                label,
+                violations.len(),
+                path.display()
+            );
+            for violation in violations.iter().take(MAX_SHOWN) {
+                println!("  {}", violation);
+            }
+            if violations.len() > MAX_SHOWN {
+                println!("  and {} more", violations.len() - MAX_SHOWN);
+            }
+        }
+    }
+    Ok(valid)
+}
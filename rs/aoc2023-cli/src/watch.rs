@@ -0,0 +1,157 @@
+use std::{
+    env,
+    path::PathBuf,
+    process::{Command, Stdio},
+    sync::mpsc::{self, Receiver},
+    time::Duration,
+};
+
+use anyhow::Result;
+use notify::{RecursiveMode, Watcher};
+
+use crate::{
+    answers::Answers,
+    config::{self, Config},
+};
+
+// Changes arriving within this window of each other trigger a single re-run,
+// since editors tend to write a file in several steps.
+const DEBOUNCE: Duration = Duration::from_millis(200);
+
+// Answers and timing of one run of a day
+#[derive(Debug, Default)]
+struct Run {
+    answers: Answers,
+    elapsed: Option<Duration>,
+    // first error logged by the run, i.e. why the day failed
+    error: Option<String>,
+}
+
+// Watch the source, input and sample files of `day`, and re-run the day
+// whenever one of them changes.
+//
+// Every run goes through `cargo run` to pick up changes to the solvers;
+// compile errors are shown as they are.
+pub fn run(year: u16, day: u8, config: &Config) -> Result<()> {
+    let dirs = [
+        config::core_dir().join("src").join(format!("y{}", year)),
+        config.input_dir.join(year.to_string()),
+        config.sample_dir.join(year.to_string()),
+    ];
+    let prefix = format!("day{:02}", day);
+
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx)?;
+    for dir in &dirs {
+        watcher.watch(dir, RecursiveMode::NonRecursive)?;
+    }
+    tracing::info!(
+        "watching day {:02} of {} in {}",
+        day,
+        year,
+        config::root().display()
+    );
+
+    let mut previous: Option<Run> = None;
+    loop {
+        match solve(year, day) {
+            Ok(run) => {
+                report(&run, previous.as_ref());
+                previous = Some(run);
+            }
+            Err(e) => tracing::error!("day {:02} failed to run: {:#}", day, e),
+        }
+        wait_for_change(&rx, &prefix)?;
+    }
+}
+
+// Block until a file whose name starts with `prefix` changed
+fn wait_for_change(rx: &Receiver<notify::Result<notify::Event>>, prefix: &str) -> Result<()> {
+    let is_relevant = |path: &PathBuf| {
+        path.file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.starts_with(prefix))
+    };
+
+    loop {
+        let event = rx.recv()??;
+        if event.kind.is_access() || !event.paths.iter().any(is_relevant) {
+            continue;
+        }
+        tracing::info!("changed: {}", event.paths[0].display());
+        // swallow the rest of a burst of changes
+        while rx.recv_timeout(DEBOUNCE).is_ok() {}
+        return Ok(());
+    }
+}
+
+// Build and run `day`, and collect its answers from the JSON log output
+fn solve(year: u16, day: u8) -> Result<Run> {
+    let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let output = Command::new(cargo)
+        .current_dir(config::workspace_dir())
+        .args([
+            "run",
+            "--release",
+            "--quiet",
+            "--package",
+            "aoc2023-cli",
+            "--",
+        ])
+        .args(["run", &day.to_string(), "--year", &year.to_string()])
+        .args(["--log-format", "json"])
+        .args(["--log-level", "info"])
+        .env_remove("RUST_LOG")
+        .stderr(Stdio::inherit())
+        .output()?;
+
+    let mut run = Run::default();
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        let Ok(event) = serde_json::from_str::<serde_json::Value>(line) else {
+            continue;
+        };
+        let fields = &event["fields"];
+        if let Some(elapsed_us) = fields["elapsed_us"].as_u64() {
+            run.elapsed = Some(Duration::from_micros(elapsed_us));
+        }
+        if let (Some(part), Some(answer)) = (fields["part"].as_u64(), fields["answer"].as_str()) {
+            run.answers.insert(part as u8, answer.into());
+        }
+        if event["level"] == "ERROR" {
+            if let Some(message) = fields["message"].as_str() {
+                run.error.get_or_insert_with(|| message.to_string());
+            }
+        }
+    }
+
+    anyhow::ensure!(
+        output.status.success() || run.error.is_some(),
+        "exited with {}",
+        output.status
+    );
+    Ok(run)
+}
+
+// Log the answers and timing of `run`, compared to the previous run
+fn report(run: &Run, previous: Option<&Run>) {
+    for (part, answer) in &run.answers {
+        match previous.and_then(|previous| previous.answers.get(part)) {
+            Some(before) if before != answer => {
+                tracing::warn!("part {}: {} (was {})", part, answer, before)
+            }
+            Some(_) => tracing::info!("part {}: {} (unchanged)", part, answer),
+            None => tracing::info!("part {}: {}", part, answer),
+        }
+    }
+
+    if let Some(elapsed) = run.elapsed {
+        match previous.and_then(|previous| previous.elapsed) {
+            Some(before) => tracing::info!("took {:.2?} (was {:.2?})", elapsed, before),
+            None => tracing::info!("took {:.2?}", elapsed),
+        }
+    }
+
+    if let Some(error) = &run.error {
+        tracing::error!("{}", error);
+    }
+}
@@ -0,0 +1,31 @@
+// Timings of the hot paths of days 1, 3, 4 and 15 on generated inputs larger
+// than the real ones, where the loops dominate:
+//
+//   cargo bench -p aoc2023-core --bench hot_paths
+//
+// Std's and aHash's hashing are compared by running it with
+// `--no-default-features --features all-days,parallel` against the default.
+// Criterion reports the change against the previous run.
+
+use aoc2023_core::{generate, solver};
+use criterion::{criterion_group, criterion_main, Criterion};
+
+// Days and the size of their generated inputs, e.g. 4000 cards on day 4
+const DAYS: [(u8, usize); 4] = [(1, 10), (3, 4), (4, 20), (15, 10)];
+
+fn hot_paths(c: &mut Criterion) {
+    for (day, size) in DAYS {
+        let solver = solver(2023, day).expect("day is registered");
+        let input = generate::generate(solver, 1, size).expect("day has a generator");
+        let parsed = solver.parse(&input).expect("generated input parses");
+
+        let mut group = c.benchmark_group(format!("day{:02}", day));
+        group.bench_function("parse", |b| b.iter(|| solver.parse(&input).unwrap()));
+        group.bench_function("part 1", |b| b.iter(|| parsed.part1().unwrap()));
+        group.bench_function("part 2", |b| b.iter(|| parsed.part2().unwrap()));
+        group.finish();
+    }
+}
+
+criterion_group!(benches, hot_paths);
+criterion_main!(benches);
@@ -0,0 +1,91 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+// Answer to a part of a puzzle. Numbers compare equal whether they're
+// signed or not, so an answer read back from JSON or TOML (where positive
+// numbers are unsigned) matches the answer of a solver that works on signed
+// numbers.
+#[derive(Debug, Clone, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Answer {
+    U64(u64),
+    I64(i64),
+    Text(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::U64(n) => write!(f, "{}", n),
+            Answer::I64(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Answer::U64(a), Answer::U64(b)) => a == b,
+            (Answer::I64(a), Answer::I64(b)) => a == b,
+            (Answer::U64(a), Answer::I64(b)) | (Answer::I64(b), Answer::U64(a)) => {
+                u64::try_from(*b).is_ok_and(|b| b == *a)
+            }
+            (Answer::Text(a), Answer::Text(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+macro_rules! impl_from {
+    ($variant:ident: $($ty:ty),*) => {
+        $(
+            impl From<$ty> for Answer {
+                fn from(n: $ty) -> Self {
+                    Answer::$variant(n as _)
+                }
+            }
+        )*
+    };
+}
+
+impl_from!(U64: u8, u16, u32, u64, usize);
+impl_from!(I64: i8, i16, i32, i64, isize);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Text(s.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signed_and_unsigned_numbers_are_equal() {
+        assert_eq!(Answer::from(993isize), Answer::from(993usize));
+        assert_ne!(Answer::from(-1isize), Answer::from(u64::MAX));
+        assert_ne!(Answer::from(42usize), Answer::from("42"));
+    }
+
+    #[test]
+    fn test_serde() -> anyhow::Result<()> {
+        let answers = vec![
+            Answer::from(54927usize),
+            Answer::from(-3isize),
+            "LRL".into(),
+        ];
+        let json = serde_json::to_string(&answers)?;
+        assert_eq!(json, r#"[54927,-3,"LRL"]"#);
+        assert_eq!(serde_json::from_str::<Vec<Answer>>(&json)?, answers);
+        Ok(())
+    }
+}
@@ -0,0 +1,76 @@
+// Encryption of inputs at rest, so they can be kept in a repository without
+// publishing them. Encrypted inputs, e.g. input/2023/day05.txt.enc, are
+// AES-256-GCM with a random nonce:
+//
+//   nonce (12 bytes) | ciphertext | tag (16 bytes)
+//
+// under a key of 64 hex digits in $AOC_INPUT_KEY, e.g. made with
+// `openssl rand -hex 32`.
+use std::env;
+
+use aes_gcm::{
+    aead::{Aead, AeadCore, KeyInit, OsRng},
+    Aes256Gcm, Key, Nonce,
+};
+use anyhow::{Context, Result};
+
+// Environment variable with the key of the encrypted inputs
+pub const KEY_VAR: &str = "AOC_INPUT_KEY";
+
+const NONCE_LEN: usize = 12;
+
+// Key of the encrypted inputs, from $AOC_INPUT_KEY
+pub fn key() -> Result<[u8; 32]> {
+    let hex = env::var(KEY_VAR)
+        .with_context(|| format!("{} is not set; it's the key of encrypted inputs", KEY_VAR))?;
+    parse_key(hex.trim())
+}
+
+// Key of 64 hex digits
+pub fn parse_key(hex: &str) -> Result<[u8; 32]> {
+    anyhow::ensure!(
+        hex.len() == 64 && hex.bytes().all(|b| b.is_ascii_hexdigit()),
+        "the key of encrypted inputs must be 64 hex digits"
+    );
+    let mut key = [0; 32];
+    for (i, byte) in key.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[2 * i..2 * i + 2], 16)?;
+    }
+    Ok(key)
+}
+
+pub fn encrypt(key: &[u8; 32], plaintext: &[u8]) -> Result<Vec<u8>> {
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key));
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(&nonce, plaintext)
+        .map_err(|_| anyhow::anyhow!("failed to encrypt"))?;
+    Ok([nonce.as_slice(), &ciphertext].concat())
+}
+
+pub fn decrypt(key: &[u8; 32], encrypted: &[u8]) -> Result<Vec<u8>> {
+    anyhow::ensure!(encrypted.len() >= NONCE_LEN, "encrypted input is truncated");
+    let (nonce, ciphertext) = encrypted.split_at(NONCE_LEN);
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key));
+    cipher
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| anyhow::anyhow!("failed to decrypt; wrong key, or the input was altered"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() -> Result<()> {
+        let key = parse_key(&"0123456789abcdef".repeat(4))?;
+        let encrypted = encrypt(&key, b"Time: 7 15 30\n")?;
+        assert_eq!(decrypt(&key, &encrypted)?, b"Time: 7 15 30\n");
+
+        let other = parse_key(&"f".repeat(64))?;
+        assert!(decrypt(&other, &encrypted).is_err());
+        assert!(decrypt(&key, &encrypted[..encrypted.len() - 1]).is_err());
+        assert!(parse_key("0123").is_err());
+        Ok(())
+    }
+}
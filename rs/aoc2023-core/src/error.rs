@@ -0,0 +1,243 @@
+use std::fmt;
+
+use nom::IResult;
+
+// Number of characters of the unparsed input shown in errors
+const SNIPPET_LEN: usize = 32;
+
+// Errors of solvers. Parse errors point at where in the input parsing
+// stopped, e.g.
+//
+//   day 08: failed to parse input (expected Tag) at line 3, column 1: `AAA = (BBB, CCC)`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AocError {
+    // The parser failed at `location`
+    Parse {
+        day: u8,
+        location: Location,
+        reason: String,
+    },
+    // The parser succeeded, but left input from `location` on unparsed
+    Unparsed {
+        day: u8,
+        location: Location,
+    },
+}
+
+// Position in the input, counting lines and columns from 1, and the start
+// of the input from there on
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+    pub snippet: String,
+}
+
+impl Location {
+    // Location of `at`, a suffix of `input` or of one of its slices
+    pub fn new(input: &[u8], at: &[u8]) -> Self {
+        let offset = (at.as_ptr() as usize)
+            .checked_sub(input.as_ptr() as usize)
+            .filter(|&offset| offset + at.len() <= input.len())
+            .unwrap_or(input.len().saturating_sub(at.len()));
+        let before = &input[..offset];
+        let line_start = before
+            .iter()
+            .rposition(|&b| b == b'\n')
+            .map_or(0, |idx| idx + 1);
+        let line = before.iter().filter(|&&b| b == b'\n').count() + 1;
+        let column = String::from_utf8_lossy(&before[line_start..])
+            .chars()
+            .count()
+            + 1;
+
+        let rest = &input[offset..];
+        let rest = &rest[..rest.iter().position(|&b| b == b'\n').unwrap_or(rest.len())];
+        let rest = String::from_utf8_lossy(rest);
+        let mut snippet = rest.chars().take(SNIPPET_LEN).collect::<String>();
+        if rest.chars().count() > SNIPPET_LEN {
+            snippet.push('…');
+        }
+        Location {
+            line,
+            column,
+            snippet,
+        }
+    }
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}: ", self.line, self.column)?;
+        if self.snippet.is_empty() {
+            write!(f, "end of line")
+        } else {
+            write!(f, "`{}`", self.snippet)
+        }
+    }
+}
+
+impl fmt::Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AocError::Parse {
+                day,
+                location,
+                reason,
+            } => write!(
+                f,
+                "day {:02}: failed to parse input ({}) at {}",
+                day, reason, location
+            ),
+            AocError::Unparsed { day, location } => {
+                write!(f, "day {:02}: unparsed input at {}", day, location)
+            }
+        }
+    }
+}
+
+impl std::error::Error for AocError {}
+
+// Parse all of `input` of `day` with a nom parser. Trailing whitespace may
+// be left unparsed.
+pub fn parse<I, O>(day: u8, input: I, parser: impl FnMut(I) -> IResult<I, O>) -> Result<O, AocError>
+where
+    I: AsRef<[u8]> + Copy,
+{
+    parse_within(day, input, input, parser)
+}
+
+// Parse each line of `input` of `day` with a nom parser
+pub fn parse_lines<'a, O>(
+    day: u8,
+    input: &'a str,
+    mut parser: impl FnMut(&'a str) -> IResult<&'a str, O>,
+) -> Result<Vec<O>, AocError> {
+    input
+        .lines()
+        .map(|line| parse_within(day, input, line, &mut parser))
+        .collect()
+}
+
+// Parse `line`, the line numbered `number` from 1 of the input of `day`,
+// with a nom parser, for inputs read one line at a time
+pub fn parse_line<I, O>(
+    day: u8,
+    number: usize,
+    line: I,
+    parser: impl FnMut(I) -> IResult<I, O>,
+) -> Result<O, AocError>
+where
+    I: AsRef<[u8]> + Copy,
+{
+    parse(day, line, parser).map_err(|mut err| {
+        let (AocError::Parse { location, .. } | AocError::Unparsed { location, .. }) = &mut err;
+        location.line += number - 1;
+        err
+    })
+}
+
+// Parse all of `fragment`, a slice of `input` of `day`, with a nom parser.
+// Errors are located in `input`.
+pub fn parse_within<I, O>(
+    day: u8,
+    input: impl AsRef<[u8]>,
+    fragment: I,
+    mut parser: impl FnMut(I) -> IResult<I, O>,
+) -> Result<O, AocError>
+where
+    I: AsRef<[u8]> + Copy,
+{
+    let input = input.as_ref();
+    match parser(fragment) {
+        Ok((rest, output)) if rest.as_ref().iter().all(u8::is_ascii_whitespace) => Ok(output),
+        Ok((rest, _)) => {
+            // point at the first unparsed input, not at the end of the line
+            // parsing stopped at
+            let rest = rest.as_ref();
+            let start = rest
+                .iter()
+                .position(|b| !b.is_ascii_whitespace())
+                .unwrap_or(0);
+            Err(AocError::Unparsed {
+                day,
+                location: Location::new(input, &rest[start..]),
+            })
+        }
+        Err(nom::Err::Error(e) | nom::Err::Failure(e)) => Err(AocError::Parse {
+            day,
+            location: Location::new(input, e.input.as_ref()),
+            reason: format!("expected {:?}", e.code),
+        }),
+        Err(nom::Err::Incomplete(_)) => Err(AocError::Parse {
+            day,
+            location: Location::new(input, &fragment.as_ref()[fragment.as_ref().len()..]),
+            reason: "incomplete input".to_string(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nom::{
+        bytes::complete::tag, character::complete::digit1, multi::separated_list1,
+        sequence::preceded,
+    };
+
+    use super::*;
+
+    fn numbers(input: &str) -> IResult<&str, Vec<&str>> {
+        preceded(tag("n: "), separated_list1(tag(" "), digit1))(input)
+    }
+
+    #[test]
+    fn test_parse_error_location() {
+        let input = "n: 1 2\nn: 3 x\nn 4\n";
+        let err = parse_lines(8, input, numbers).unwrap_err();
+        assert_eq!(
+            err,
+            AocError::Unparsed {
+                day: 8,
+                location: Location {
+                    line: 2,
+                    column: 6,
+                    snippet: "x".to_string(),
+                },
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "day 08: unparsed input at line 2, column 6: `x`"
+        );
+
+        let err = parse(8, &input[14..], numbers).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "day 08: failed to parse input (expected Tag) at line 1, column 1: `n 4`"
+        );
+
+        let err = parse_line(8, 3, "n 4", numbers).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "day 08: failed to parse input (expected Tag) at line 3, column 1: `n 4`"
+        );
+    }
+
+    #[test]
+    fn test_unparsed_lines() {
+        fn lines(input: &str) -> IResult<&str, Vec<Vec<&str>>> {
+            separated_list1(tag("\n"), numbers)(input)
+        }
+
+        let err = parse(8, "n: 1\n\nn: 2\n", lines).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "day 08: unparsed input at line 3, column 1: `n: 2`"
+        );
+    }
+
+    #[test]
+    fn test_trailing_whitespace() {
+        assert_eq!(parse(8, "n: 1 2\n\n", numbers), Ok(vec!["1", "2"]));
+    }
+}
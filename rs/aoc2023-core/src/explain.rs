@@ -0,0 +1,22 @@
+// Key intermediate result of solving a day, in human-readable form, e.g.
+//
+//   soil ranges (3)
+//   81 .. 95
+//   57 .. 70
+//
+// Days explain themselves with `Puzzle::explain`, from the model parsed from
+// their input, rather than through debug logs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Artifact {
+    pub title: String,
+    pub lines: Vec<String>,
+}
+
+impl Artifact {
+    pub fn new<T: ToString>(title: impl Into<String>, lines: impl IntoIterator<Item = T>) -> Self {
+        Artifact {
+            title: title.into(),
+            lines: lines.into_iter().map(|line| line.to_string()).collect(),
+        }
+    }
+}
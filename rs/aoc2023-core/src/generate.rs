@@ -0,0 +1,66 @@
+use rand::{distributions::WeightedIndex, prelude::Distribution, SeedableRng};
+
+use crate::Solver;
+
+// Random generator of fake inputs; ChaCha gives the same numbers for a seed
+// on every platform and release, so a seed is enough to share an input
+pub type Rng = rand_chacha::ChaCha8Rng;
+
+// Fake input of the day of `solver`, shaped like the real ones and valid to
+// the day, from `seed`, if the day has a generator. Fake inputs can be shared
+// where the real ones can't, e.g. to reproduce a bug in public. Inputs of
+// `size` 1 are about as large as the real ones; larger sizes multiply the
+// lines of an input, or the rows and columns of a grid, to find out how the
+// solvers scale, e.g. size 5 for 500 by 500 platforms on day 14.
+pub fn generate(solver: &dyn Solver, seed: u64, size: usize) -> Option<String> {
+    solver.generate(&mut Rng::seed_from_u64(seed), size)
+}
+
+// Grid of `rows` by `cols` tiles, each drawn from `tiles` with the odds of
+// its weight
+pub fn tiles(rng: &mut Rng, rows: usize, cols: usize, tiles: &[(u8, u32)]) -> Vec<Vec<u8>> {
+    let weights = WeightedIndex::new(tiles.iter().map(|&(_, weight)| weight))
+        .expect("tiles have positive weights");
+    (0..rows)
+        .map(|_| (0..cols).map(|_| tiles[weights.sample(rng)].0).collect())
+        .collect()
+}
+
+// Grid of tiles as the lines of an input
+pub fn grid(grid: &[Vec<u8>]) -> String {
+    grid.iter()
+        .map(|row| String::from_utf8_lossy(row))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solvers;
+
+    #[test]
+    fn test_generated_inputs_are_valid() {
+        for solver in solvers() {
+            for seed in 0..3 {
+                let Some(input) = generate(solver, seed, 1) else {
+                    continue;
+                };
+                let day = solver.day();
+                assert_eq!(
+                    generate(solver, seed, 1),
+                    Some(input.clone()),
+                    "day {}",
+                    day
+                );
+                assert_eq!(solver.validate(&input), [], "day {} seed {}", day, seed);
+                let parsed = solver.parse(&input).unwrap();
+                parsed.part1().unwrap();
+                parsed.part2().unwrap();
+            }
+            if let Some(input) = generate(solver, 0, 2) {
+                assert_eq!(solver.validate(&input), [], "day {} size 2", solver.day());
+            }
+        }
+    }
+}
@@ -0,0 +1,279 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+
+use crate::Part;
+
+// Environment variable with the root of the input and sample directories,
+// taking precedence over the root of this repository
+const ROOT_VAR: &str = "AOC_ROOT";
+
+// Extensions of compressed or encrypted inputs, tried in order when the
+// plain file of an input isn't there, e.g. input/2023/day05.txt.gz for
+// input/2023/day05.txt
+const ENCODED: &[&str] = &[
+    #[cfg(feature = "compression")]
+    "gz",
+    #[cfg(feature = "compression")]
+    "zst",
+    #[cfg(feature = "encryption")]
+    "enc",
+];
+
+// Root of the input and sample directories: $AOC_ROOT, or else the root of
+// this repository
+pub fn root() -> PathBuf {
+    match env::var_os(ROOT_VAR) {
+        Some(root) => PathBuf::from(root),
+        None => {
+            let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+            manifest_dir
+                .ancestors()
+                .nth(2)
+                .unwrap_or(manifest_dir)
+                .to_path_buf()
+        }
+    }
+}
+
+/// Inputs and samples of the days, read at runtime from files like
+/// `input/2023/day05.txt` and `sample/2023/day05.txt`, e.g.
+/// `InputStore::default().sample(2023, 5)`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputStore {
+    input_dir: PathBuf,
+    sample_dir: PathBuf,
+}
+
+impl Default for InputStore {
+    fn default() -> Self {
+        InputStore::new(root())
+    }
+}
+
+impl InputStore {
+    /// Store with the input and sample directories under `root`
+    pub fn new(root: impl AsRef<Path>) -> Self {
+        let root = root.as_ref();
+        InputStore {
+            input_dir: root.join("input"),
+            sample_dir: root.join("sample"),
+        }
+    }
+
+    /// Directory of the inputs, per year
+    pub fn input_dir(self, input_dir: impl Into<PathBuf>) -> Self {
+        InputStore {
+            input_dir: input_dir.into(),
+            ..self
+        }
+    }
+
+    /// Directory of the samples, per year
+    pub fn sample_dir(self, sample_dir: impl Into<PathBuf>) -> Self {
+        InputStore {
+            sample_dir: sample_dir.into(),
+            ..self
+        }
+    }
+
+    /// File of the input of a day, e.g. input/2023/day01.txt
+    pub fn input_path(&self, year: u16, day: u8) -> PathBuf {
+        self.input_dir
+            .join(year.to_string())
+            .join(file_name(day, ""))
+    }
+
+    /// File of the sample of a day, e.g. sample/2023/day01.txt
+    pub fn sample_path(&self, year: u16, day: u8) -> PathBuf {
+        self.sample_dir
+            .join(year.to_string())
+            .join(file_name(day, ""))
+    }
+
+    /// File of the sample of a part of a day whose parts have different
+    /// samples, e.g. sample/2023/day01b.txt
+    pub fn sample_part_path(&self, year: u16, day: u8, part: Part) -> PathBuf {
+        let suffix = match part {
+            Part::One => "a",
+            Part::Two => "b",
+        };
+        self.sample_dir
+            .join(year.to_string())
+            .join(file_name(day, suffix))
+    }
+
+    /// Input of a day
+    pub fn input(&self, year: u16, day: u8) -> Result<String> {
+        let path = self.input_path(year, day);
+        anyhow::ensure!(
+            find(&path).is_some(),
+            "{} not found; run `aoc2023 fetch {}` or pass --input",
+            path.display(),
+            day
+        );
+        read(&path)
+    }
+
+    /// Sample of a day
+    pub fn sample(&self, year: u16, day: u8) -> Result<String> {
+        read_sample(&self.sample_path(year, day), day)
+    }
+
+    /// Sample of a part of a day whose parts have different samples
+    pub fn sample_part(&self, year: u16, day: u8, part: Part) -> Result<String> {
+        read_sample(&self.sample_part_path(year, day, part), day)
+    }
+
+    /// Names and files of the inputs of a day: "main" for the input, e.g.
+    /// input/2023/day05.txt, followed by its alternates in order of name,
+    /// e.g. "alt1" for input/2023/day05.alt1.txt. Compressed or encrypted
+    /// inputs count as well.
+    pub fn input_set(&self, year: u16, day: u8) -> Result<Vec<(String, PathBuf)>> {
+        let dir = self.input_dir.join(year.to_string());
+        let prefix = format!("day{:02}.", day);
+        let mut alternates = vec![];
+        if dir.is_dir() {
+            for entry in
+                fs::read_dir(&dir).with_context(|| format!("failed to read {}", dir.display()))?
+            {
+                let file_name = entry?.file_name();
+                let Some(file_name) = file_name.to_str() else {
+                    continue;
+                };
+                let file_name = ENCODED
+                    .iter()
+                    .find_map(|ext| file_name.strip_suffix(&format!(".{}", ext)))
+                    .unwrap_or(file_name);
+                if let Some(name) = file_name
+                    .strip_prefix(&prefix)
+                    .and_then(|name| name.strip_suffix(".txt"))
+                    .filter(|name| !name.is_empty())
+                {
+                    alternates.push((name.to_string(), dir.join(file_name)));
+                }
+            }
+        }
+        alternates.sort();
+        alternates.dedup();
+
+        let mut inputs = vec![];
+        let main = self.input_path(year, day);
+        if find(&main).is_some() {
+            inputs.push(("main".to_string(), main));
+        }
+        inputs.extend(alternates);
+        Ok(inputs)
+    }
+}
+
+fn read_sample(path: &Path, day: u8) -> Result<String> {
+    anyhow::ensure!(
+        find(path).is_some(),
+        "{} not found; run `aoc2023 fetch --samples {}`",
+        path.display(),
+        day
+    );
+    read(path)
+}
+
+fn file_name(day: u8, suffix: &str) -> String {
+    format!("day{:02}{}.txt", day, suffix)
+}
+
+// File with the input at `path`: the file itself, or else a compressed or
+// encrypted copy of it
+pub fn find(path: &Path) -> Option<PathBuf> {
+    if path.is_file() {
+        return Some(path.to_path_buf());
+    }
+    ENCODED
+        .iter()
+        .map(|ext| with_extension(path, ext))
+        .find(|path| path.is_file())
+}
+
+// `path` with `ext` appended, e.g. day05.txt.gz for day05.txt
+pub fn with_extension(path: &Path, ext: &str) -> PathBuf {
+    let mut path = path.as_os_str().to_owned();
+    path.push(".");
+    path.push(ext);
+    PathBuf::from(path)
+}
+
+// Read the input at `path`, decoding it if it's only there compressed or
+// encrypted
+pub fn read(path: &Path) -> Result<String> {
+    let file = find(path).unwrap_or_else(|| path.to_path_buf());
+    decode(&file).with_context(|| format!("failed to read input {}", file.display()))
+}
+
+fn decode(file: &Path) -> Result<String> {
+    match file.extension().and_then(|ext| ext.to_str()) {
+        #[cfg(feature = "compression")]
+        Some("gz") => read_all(flate2::read::MultiGzDecoder::new(fs::File::open(file)?)),
+        #[cfg(feature = "compression")]
+        Some("zst") => read_all(zstd::Decoder::new(fs::File::open(file)?)?),
+        #[cfg(feature = "encryption")]
+        Some("enc") => {
+            let input = crate::crypt::decrypt(&crate::crypt::key()?, &fs::read(file)?)?;
+            Ok(String::from_utf8(input)?)
+        }
+        _ => Ok(fs::read_to_string(file)?),
+    }
+}
+
+#[cfg(feature = "compression")]
+fn read_all(mut reader: impl std::io::Read) -> Result<String> {
+    let mut input = String::new();
+    reader.read_to_string(&mut input)?;
+    Ok(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_input() {
+        let store = InputStore::new("/nonexistent");
+        let e = store.input(2023, 5).unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "/nonexistent/input/2023/day05.txt not found; run `aoc2023 fetch 5` or pass --input"
+        );
+        assert!(store.sample(2023, 5).is_err());
+
+        let store = InputStore::default();
+        assert!(store.sample(2023, 5).is_ok());
+        assert!(store.sample_part(2023, 1, Part::Two).is_ok());
+    }
+
+    #[test]
+    fn test_input_set() -> Result<()> {
+        let root = env::temp_dir().join(format!("aoc2023-input-set-{}", std::process::id()));
+        let dir = root.join("input/2023");
+        fs::create_dir_all(&dir)?;
+        for file in [
+            "day05.txt",
+            "day05.b.txt",
+            "day05.a.txt",
+            "day05.a.txt.gz",
+            "day06.c.txt",
+        ] {
+            fs::write(dir.join(file), "")?;
+        }
+        let names = InputStore::new(&root)
+            .input_set(2023, 5)?
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect::<Vec<_>>();
+        fs::remove_dir_all(&root)?;
+
+        assert_eq!(names, ["main", "a", "b"]);
+        Ok(())
+    }
+}
@@ -0,0 +1,41 @@
+use std::fmt;
+
+use anyhow::Result;
+use num_integer::Integer;
+use num_traits::{CheckedMul, FromPrimitive};
+
+// Integers of the days whose arithmetic can overflow the machine's integers,
+// chosen by the caller, e.g. u64, u128 or `num_bigint::BigUint`, and shared
+// across threads by parallel loops
+pub trait Int:
+    Integer + CheckedMul + FromPrimitive + Clone + fmt::Debug + fmt::Display + Send + Sync
+{
+}
+
+impl<T> Int for T where
+    T: Integer + CheckedMul + FromPrimitive + Clone + fmt::Debug + fmt::Display + Send + Sync
+{
+}
+
+// Parse the decimal digits of `s`, failing if they don't fit
+pub fn parse<T: Int>(s: &str) -> Result<T> {
+    T::from_str_radix(s, 10).map_err(|_| anyhow::anyhow!("{} doesn't fit in the integer", s))
+}
+
+// Convert `n`, failing if it doesn't fit
+pub fn from_usize<T: Int>(n: usize) -> Result<T> {
+    T::from_usize(n).ok_or_else(|| anyhow::anyhow!("{} doesn't fit in the integer", n))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_overflow() -> Result<()> {
+        assert!(parse::<u32>("4294967296").is_err());
+        assert_eq!(parse::<u64>("4294967296")?, 1 << 32);
+        assert!(from_usize::<u8>(256).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,27 @@
+pub mod answer;
+#[cfg(feature = "encryption")]
+pub mod crypt;
+pub mod error;
+pub mod explain;
+pub mod generate;
+pub mod input;
+pub mod int;
+mod log;
+pub mod memory;
+pub mod par;
+pub mod params;
+pub mod prelude;
+pub mod run;
+pub mod solver;
+pub mod util;
+pub mod validate;
+pub mod y2023;
+
+pub use answer::Answer;
+pub use error::AocError;
+pub use explain::Artifact;
+pub use input::InputStore;
+pub use params::{FromParams, Params};
+pub use run::{run_day, solve, DayResult, Part, RunOptions};
+pub use solver::{solver, solvers, FromInput, Puzzle, Solver};
+pub use validate::Violation;
@@ -0,0 +1,23 @@
+// Logging of the solvers. With the `tracing` feature, logs go through
+// tracing; without it, they're compiled out, with their arguments still
+// type checked. (Unused when no days are compiled in.)
+#[cfg(feature = "tracing")]
+#[allow(unused_macros)]
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        ::tracing::debug!($($arg)*)
+    };
+}
+
+#[cfg(not(feature = "tracing"))]
+#[allow(unused_macros)]
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        if false {
+            let _ = format_args!($($arg)*);
+        }
+    };
+}
+
+#[allow(unused_imports)]
+pub(crate) use debug;
@@ -0,0 +1,200 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    ptr,
+    sync::{
+        atomic::{AtomicBool, AtomicI64, AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+// Allocations of a stage of solving a day, e.g. parsing or a part
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    // number of allocations, reallocations included
+    pub allocations: u64,
+    // most bytes allocated at once during the stage, over what was
+    // allocated before it
+    pub peak_bytes: u64,
+}
+
+// Allocations of a measure so far, by all the threads working for it
+#[derive(Debug, Default)]
+struct Counters {
+    allocations: AtomicU64,
+    bytes: AtomicI64,
+    peak: AtomicI64,
+    // the measure this one is nested in, which counts its allocations too
+    outer: Option<Arc<Counters>>,
+}
+
+thread_local! {
+    // Counters of the measure this thread is working for, if any, kept
+    // alive by the `Enter` that set them
+    static CURRENT: Cell<*const Counters> = const { Cell::new(ptr::null()) };
+}
+
+// Whether `Counting` is the global allocator, i.e. it has allocated anything
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+
+// Global allocator of the system that counts allocations, and the bytes
+// allocated at once, per measure. Threads of a pool count in the measure of
+// the thread they work for within `Scope::enter`, e.g. in `par::map`.
+//
+//   #[global_allocator]
+//   static ALLOC: Counting = Counting;
+pub struct Counting;
+
+fn record(allocations: u64, bytes: i64) {
+    // the counters are gone while the thread is torn down
+    let Ok(current) = CURRENT.try_with(Cell::get) else {
+        return;
+    };
+    // SAFETY: the `Enter` that set `current` keeps it alive
+    let mut counters = unsafe { current.as_ref() };
+    while let Some(c) = counters {
+        c.allocations.fetch_add(allocations, Ordering::Relaxed);
+        let now = c.bytes.fetch_add(bytes, Ordering::Relaxed) + bytes;
+        c.peak.fetch_max(now, Ordering::Relaxed);
+        counters = c.outer.as_deref();
+    }
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        INSTALLED.store(true, Ordering::Relaxed);
+        record(1, layout.size() as i64);
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        INSTALLED.store(true, Ordering::Relaxed);
+        record(1, layout.size() as i64);
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        record(0, -(layout.size() as i64));
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        record(1, new_size as i64 - layout.size() as i64);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+// Makes the thread work for a measure until dropped, then for the one it
+// worked for before, even if unwinding
+struct Enter {
+    prev: *const Counters,
+    _counters: Option<Arc<Counters>>,
+}
+
+impl Enter {
+    fn new(counters: Option<Arc<Counters>>) -> Self {
+        let current = counters.as_ref().map_or(ptr::null(), Arc::as_ptr);
+        let prev = CURRENT.with(|c| c.replace(current));
+        Enter {
+            prev,
+            _counters: counters,
+        }
+    }
+}
+
+impl Drop for Enter {
+    fn drop(&mut self) {
+        CURRENT.with(|c| c.set(self.prev));
+    }
+}
+
+// Measure a thread works for, to have the threads of a pool count their
+// allocations in it too
+#[derive(Debug, Clone, Default)]
+pub struct Scope(Option<Arc<Counters>>);
+
+impl Scope {
+    // Measure of this thread
+    pub fn current() -> Self {
+        let current = CURRENT.with(Cell::get);
+        if current.is_null() {
+            return Scope(None);
+        }
+        // SAFETY: the `Enter` that set `current` holds an `Arc` of it
+        unsafe {
+            Arc::increment_strong_count(current);
+            Scope(Some(Arc::from_raw(current)))
+        }
+    }
+
+    // Run `f`, counting its allocations in the measure
+    pub fn enter<T>(&self, f: impl FnOnce() -> T) -> T {
+        let _enter = Enter::new(self.0.clone());
+        f()
+    }
+}
+
+// Run `f`, measuring its allocations on this thread, and on the threads
+// working for it, if `Counting` is the global allocator. Measures nest: the
+// allocations of an inner one count in the outer ones too.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<AllocStats>) {
+    if !INSTALLED.load(Ordering::Relaxed) {
+        return (f(), None);
+    }
+    // the counters themselves aren't counted, in this measure or others
+    let outer = Scope::current();
+    let counters = Scope::default().enter(|| {
+        Arc::new(Counters {
+            outer: outer.0,
+            ..Counters::default()
+        })
+    });
+    let res = {
+        let _enter = Enter::new(Some(counters.clone()));
+        f()
+    };
+    let stats = AllocStats {
+        allocations: counters.allocations.load(Ordering::Relaxed),
+        peak_bytes: counters.peak.load(Ordering::Relaxed).max(0) as u64,
+    };
+    Scope::default().enter(|| drop(counters));
+    (res, Some(stats))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[global_allocator]
+    static ALLOC: Counting = Counting;
+
+    #[test]
+    fn test_measure() {
+        let (_, outer) = measure(|| {
+            let (v, inner) = measure(|| vec![0u8; 1000]);
+            let inner = inner.unwrap();
+            assert_eq!(inner.allocations, 1);
+            assert_eq!(inner.peak_bytes, 1000);
+            drop(v);
+            let (_, inner) = measure(|| drop(vec![0u8; 500]));
+            assert_eq!(inner.unwrap().peak_bytes, 500);
+        });
+        let outer = outer.unwrap();
+        assert!(outer.allocations >= 2);
+        assert_eq!(outer.peak_bytes, 1000);
+    }
+
+    // Allocations of the threads of a parallel loop count in the measure it
+    // runs in
+    #[test]
+    fn test_measure_parallel() {
+        let items = vec![1000; 64];
+        let (_, stats) = measure(|| {
+            let vecs = crate::par::map(&items, |&len| vec![0u8; len]);
+            assert_eq!(vecs.len(), 64);
+        });
+        let stats = stats.unwrap();
+        assert!(stats.allocations >= 65);
+        assert!(stats.peak_bytes >= 64 * 1000);
+    }
+}
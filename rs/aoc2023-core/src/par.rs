@@ -0,0 +1,31 @@
+// Parallel loops of the solvers. With the `parallel` feature, items are
+// spread over the threads of rayon's current pool; without it, e.g. on wasm,
+// where there are no threads, they run one after the other on the calling
+// thread. Either way results come back in the order of the items, and their
+// allocations count in the measure of the calling thread (see `memory`).
+
+#[cfg(feature = "parallel")]
+pub fn map<T: Sync, R: Send>(items: &[T], f: impl Fn(&T) -> R + Sync + Send) -> Vec<R> {
+    use rayon::prelude::*;
+    let scope = crate::memory::Scope::current();
+    items
+        .par_iter()
+        .map(|item| scope.enter(|| f(item)))
+        .collect()
+}
+
+#[cfg(not(feature = "parallel"))]
+pub fn map<T: Sync, R: Send>(items: &[T], f: impl Fn(&T) -> R + Sync + Send) -> Vec<R> {
+    items.iter().map(f).collect()
+}
+
+// Number of threads parallel loops run on
+#[cfg(feature = "parallel")]
+pub fn threads() -> usize {
+    rayon::current_num_threads()
+}
+
+#[cfg(not(feature = "parallel"))]
+pub fn threads() -> usize {
+    1
+}
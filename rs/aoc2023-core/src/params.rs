@@ -0,0 +1,202 @@
+use std::{collections::BTreeMap, fmt, str::FromStr};
+
+use anyhow::{bail, Context, Result};
+
+// Parameters of a day that change the puzzle, e.g. for exploring what the
+// answers would be under other rules, given as `key=value`:
+//
+//   bag=20 red, 13 green
+//
+// Days with parameters read them as typed options, see `FromParams`; days
+// without take none.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Params(BTreeMap<String, String>);
+
+impl Params {
+    pub fn new() -> Self {
+        Params::default()
+    }
+
+    pub fn with(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.0.insert(key.into(), value.into());
+        self
+    }
+
+    // These parameters and `other`'s, which take precedence
+    pub fn merge(mut self, other: Params) -> Self {
+        self.0.extend(other.0);
+        self
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).map(String::as_str)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    // Fail on keys other than `known`, e.g. misspelled ones, which would
+    // otherwise be silently ignored
+    pub fn check(&self, day: u8, known: &[&str]) -> Result<()> {
+        let unknown = self
+            .0
+            .keys()
+            .filter(|key| !known.contains(&key.as_str()))
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+        match (unknown.is_empty(), known.is_empty()) {
+            (true, _) => Ok(()),
+            (false, true) => bail!(
+                "day {:02} takes no parameters, got {}",
+                day,
+                unknown.join(", ")
+            ),
+            (false, false) => bail!(
+                "unknown parameters of day {:02}: {} (known: {})",
+                day,
+                unknown.join(", "),
+                known.join(", ")
+            ),
+        }
+    }
+}
+
+impl Params {
+    // Options of `day` set from these parameters, the defaults for the keys
+    // not given. Every key must be one of the options', and every value
+    // valid for its option; errors read e.g.
+    //
+    //   invalid parameter expansion=0 of day 11: expansion must be at least 1
+    pub fn options<O: FromParams>(&self, day: u8) -> Result<O> {
+        self.check(day, O::KEYS)?;
+        let mut options = O::defaults();
+        for (key, value) in &self.0 {
+            options.set(key, value).with_context(|| {
+                format!("invalid parameter {}={} of day {:02}", key, value, day)
+            })?;
+        }
+        Ok(options)
+    }
+}
+
+// Typed options of a day, which parameters set by key
+pub trait FromParams: Sized {
+    // Keys of the options
+    const KEYS: &'static [&'static str];
+
+    // Options of the puzzle as given
+    fn defaults() -> Self;
+
+    // Set the option of `key`, one of KEYS, from its `value`
+    fn set(&mut self, key: &str, value: &str) -> Result<()>;
+}
+
+impl FromIterator<(String, String)> for Params {
+    fn from_iter<I: IntoIterator<Item = (String, String)>>(iter: I) -> Self {
+        Params(iter.into_iter().collect())
+    }
+}
+
+// A parameter as `key=value`
+impl FromStr for Params {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.split_once('=') {
+            Some((key, value)) if !key.trim().is_empty() => {
+                Ok(Params::new().with(key.trim(), value.trim()))
+            }
+            _ => bail!("expected KEY=VALUE, got `{}`", s),
+        }
+    }
+}
+
+impl fmt::Display for Params {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let params = self
+            .0
+            .iter()
+            .map(|(key, value)| format!("{}={}", key, value));
+        write!(f, "{}", params.collect::<Vec<_>>().join(" "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_params() -> Result<()> {
+        let params = "bag = 20 red".parse::<Params>()?;
+        assert_eq!(params.get("bag"), Some("20 red"));
+        assert_eq!(params.to_string(), "bag=20 red");
+        assert!("bag".parse::<Params>().is_err());
+        assert!("=20 red".parse::<Params>().is_err());
+
+        assert!(params.check(2, &["bag"]).is_ok());
+        assert!(Params::new().check(1, &[]).is_ok());
+        let err = params.check(1, &[]).unwrap_err();
+        assert_eq!(err.to_string(), "day 01 takes no parameters, got bag");
+        let err = params.check(2, &["bags"]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unknown parameters of day 02: bag (known: bags)"
+        );
+        Ok(())
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Options {
+        size: usize,
+        name: String,
+    }
+
+    impl FromParams for Options {
+        const KEYS: &'static [&'static str] = &["size", "name"];
+
+        fn defaults() -> Self {
+            Options {
+                size: 1,
+                name: "one".to_string(),
+            }
+        }
+
+        fn set(&mut self, key: &str, value: &str) -> Result<()> {
+            match key {
+                "size" => self.size = value.parse()?,
+                _ => self.name = value.to_string(),
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_options() -> Result<()> {
+        let options = Params::new().with("size", "3").options::<Options>(7)?;
+        assert_eq!(
+            options,
+            Options {
+                size: 3,
+                name: "one".to_string()
+            }
+        );
+        let err = Params::new()
+            .with("size", "x")
+            .options::<Options>(7)
+            .unwrap_err();
+        assert_eq!(
+            format!("{:#}", err),
+            "invalid parameter size=x of day 07: invalid digit found in string"
+        );
+        let err = Params::new()
+            .with("sizes", "3")
+            .options::<Options>(7)
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unknown parameters of day 07: sizes (known: size, name)"
+        );
+        Ok(())
+    }
+}
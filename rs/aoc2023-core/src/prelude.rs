@@ -0,0 +1,27 @@
+// Everything needed to solve days through the library:
+//
+//   use aoc2023_core::prelude::*;
+//
+//   let input = InputStore::default().input(2023, 1)?;
+//   let answer = solve(1, Part::One, &input)?;
+//
+// or, for the answers of both parts with their timings:
+//
+//   let solver = solver(2023, 1).unwrap();
+//   let result = run_day(solver, &input, &RunOptions::default());
+//   let answer = result.answers.get(&1);
+//
+// Days are reached through their year, e.g. `y2023::day01::{parse, part1,
+// part2}`. New days find here what the days share: the grid, and the
+// parsing of input with nom parsers, with errors pointing at the input.
+pub use crate::answer::Answer;
+pub use crate::error::{parse, parse_line, parse_lines, parse_within, AocError, Location};
+pub use crate::explain::Artifact;
+pub use crate::input::InputStore;
+pub use crate::params::{FromParams, Params};
+pub use crate::run::{run_day, solve, DayResult, Part, RunOptions};
+pub use crate::solver::{solver, solvers, FromInput, Puzzle, Solver};
+pub use crate::util::grid::{Direction, Grid, Pos};
+pub use crate::validate::Violation;
+
+pub use crate::y2023;
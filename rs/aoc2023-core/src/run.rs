@@ -0,0 +1,235 @@
+use std::{
+    any::Any,
+    collections::BTreeMap,
+    fmt,
+    panic::{self, AssertUnwindSafe},
+    time::{Duration, Instant},
+};
+
+use anyhow::{Context, Result};
+
+use crate::{
+    memory::{self, AllocStats},
+    solver, Answer, Params, Solver,
+};
+
+// Year of the days solved by `solve`
+const YEAR: u16 = 2023;
+
+// Part of a day
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub fn number(self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+// Which parts of a day to solve, and the parameters of the day
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RunOptions {
+    pub part1: bool,
+    pub part2: bool,
+    pub params: Params,
+}
+
+impl Default for RunOptions {
+    fn default() -> Self {
+        RunOptions {
+            part1: true,
+            part2: true,
+            params: Params::default(),
+        }
+    }
+}
+
+// Answers and timings of a day, as far as solving it got before it finished
+// or failed
+#[derive(Debug)]
+pub struct DayResult {
+    pub year: u16,
+    pub day: u8,
+    pub result: Result<()>,
+    pub answers: BTreeMap<u8, Answer>,
+    pub parse_time: Option<Duration>,
+    // times of the parts with an answer
+    pub part1_time: Option<Duration>,
+    pub part2_time: Option<Duration>,
+    pub elapsed: Duration,
+    // allocations of the stages, when they're counted (see
+    // `memory::Counting`)
+    pub parse_alloc: Option<AllocStats>,
+    pub part1_alloc: Option<AllocStats>,
+    pub part2_alloc: Option<AllocStats>,
+    pub alloc: Option<AllocStats>,
+}
+
+impl DayResult {
+    // Result of a day that hasn't been solved (yet)
+    pub fn new(year: u16, day: u8) -> Self {
+        DayResult {
+            year,
+            day,
+            result: Ok(()),
+            answers: BTreeMap::new(),
+            parse_time: None,
+            part1_time: None,
+            part2_time: None,
+            elapsed: Duration::ZERO,
+            parse_alloc: None,
+            part1_alloc: None,
+            part2_alloc: None,
+            alloc: None,
+        }
+    }
+}
+
+// Parse `input` with the solver of a day and solve its parts. Errors and
+// panics (e.g. a failed assert in a solver) are reported in the result
+// instead of being returned or unwinding.
+pub fn run_day(solver: &dyn Solver, input: &str, options: &RunOptions) -> DayResult {
+    let mut result = DayResult::new(solver.year(), solver.day());
+    // spans of the day and its stages, e.g. for a chrome trace of a run
+    #[cfg(feature = "tracing")]
+    let _span = tracing::info_span!("day", year = solver.year(), day = solver.day()).entered();
+    let start = Instant::now();
+    let (res, alloc) = memory::measure(|| {
+        panic::catch_unwind(AssertUnwindSafe(|| {
+            solve_parts(solver, input, options, &mut result)
+        }))
+    });
+    result.result = res
+        .unwrap_or_else(|payload| Err(anyhow::anyhow!("panicked: {}", panic_message(&*payload))));
+    result.elapsed = start.elapsed();
+    result.alloc = alloc;
+    result
+}
+
+// Solve the parts of a day, recording answers and timings in `result` as
+// they come
+fn solve_parts(
+    solver: &dyn Solver,
+    input: &str,
+    options: &RunOptions,
+    result: &mut DayResult,
+) -> Result<()> {
+    let start = Instant::now();
+    let (parsed, alloc) = memory::measure(|| {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!("parse").entered();
+        solver.parse_with(input, &options.params)
+    });
+    let parsed = parsed?;
+    result.parse_time = Some(start.elapsed());
+    result.parse_alloc = alloc;
+
+    for (part, enabled) in [(1, options.part1), (2, options.part2)] {
+        if !enabled {
+            continue;
+        }
+        let start = Instant::now();
+        let (answer, alloc) = memory::measure(|| match part {
+            1 => {
+                #[cfg(feature = "tracing")]
+                let _span = tracing::info_span!("part 1").entered();
+                parsed.part1()
+            }
+            _ => {
+                #[cfg(feature = "tracing")]
+                let _span = tracing::info_span!("part 2").entered();
+                parsed.part2()
+            }
+        });
+        let answer = answer?;
+        let elapsed = start.elapsed();
+        if let Some(answer) = answer {
+            match part {
+                1 => (result.part1_time, result.part1_alloc) = (Some(elapsed), alloc),
+                _ => (result.part2_time, result.part2_alloc) = (Some(elapsed), alloc),
+            }
+            #[cfg(feature = "tracing")]
+            tracing::info!(part, answer = %answer, "[part {}] answer: {}", part, answer);
+            result.answers.insert(part, answer);
+        }
+    }
+    Ok(())
+}
+
+// Answer of a part of a day of 2023, solved through the registered solvers.
+// Days that aren't implemented, parts without an answer, errors and panics
+// are all errors.
+pub fn solve(day: u8, part: Part, input: &str) -> Result<Answer> {
+    let solver = solver::solver(YEAR, day)
+        .with_context(|| format!("day {} is not implemented or compiled in", day))?;
+    let options = RunOptions {
+        part1: part == Part::One,
+        part2: part == Part::Two,
+        ..RunOptions::default()
+    };
+    let mut result = run_day(solver, input, &options);
+    result.result?;
+    result
+        .answers
+        .remove(&part.number())
+        .with_context(|| format!("part {} of day {} has no answer", part, day))
+}
+
+// Message of a panic, if it has one
+pub fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("unknown panic payload")
+}
+
+#[cfg(all(test, feature = "day09"))]
+mod tests {
+    use super::*;
+    use crate::{y2023::day09, InputStore};
+
+    #[test]
+    fn test_run_day() {
+        let input = &InputStore::default().sample(2023, 9).unwrap();
+        let result = run_day(&day09::Solution, input, &RunOptions::default());
+        assert!(result.result.is_ok());
+        assert_eq!(result.answers.get(&1), Some(&Answer::from(114)));
+        assert_eq!(result.answers.get(&2), Some(&Answer::from(2)));
+        assert!(result.parse_time.is_some());
+        assert!(result.part1_time.is_some() && result.part2_time.is_some());
+
+        let options = RunOptions {
+            part1: false,
+            ..RunOptions::default()
+        };
+        let result = run_day(&day09::Solution, input, &options);
+        assert_eq!(result.answers.len(), 1);
+        assert!(result.part1_time.is_none());
+
+        let result = run_day(&day09::Solution, "0 3 x", &options);
+        assert!(result.result.is_err());
+        assert!(result.answers.is_empty());
+    }
+
+    #[test]
+    fn test_solve() {
+        let input = &InputStore::default().sample(2023, 9).unwrap();
+        let answer = solve(9, Part::Two, input).unwrap();
+        assert_eq!(answer, Answer::from(2));
+        assert!(solve(9, Part::One, "0 3 x").is_err());
+        assert!(solve(26, Part::One, input).is_err());
+    }
+}
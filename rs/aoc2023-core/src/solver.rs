@@ -0,0 +1,209 @@
+use std::sync::LazyLock;
+
+use anyhow::Result;
+
+use crate::{generate::Rng, Answer, Artifact, Params, Violation};
+
+// A model parsed from the input of a puzzle, which may borrow from the input
+// rather than copy it
+pub trait FromInput<'a>: Sized {
+    fn from_input(s: &'a str) -> Result<Self>;
+}
+
+// The input itself, for puzzles whose parts read it differently
+impl<'a> FromInput<'a> for &'a str {
+    fn from_input(s: &'a str) -> Result<Self> {
+        Ok(s)
+    }
+}
+
+// A puzzle of a day. The input is parsed once into the day's model, and both
+// parts are solved from the model. Parts without a solution (yet) have no
+// answer. Reading the input is left to the caller. Days may check the
+// invariants their solutions assume of the input, which the parser doesn't,
+// e.g. that a grid is rectangular, generate fake inputs that hold them, and
+// explain how they solve their parts with key intermediate results. Days
+// with parameters (see `Params`) apply them to the model once it's parsed.
+pub trait Puzzle: Sync {
+    type Input<'a>: FromInput<'a>;
+
+    fn year(&self) -> u16;
+
+    fn day(&self) -> u8;
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Self::Input<'a>> {
+        Self::Input::from_input(input)
+    }
+
+    fn configure(&self, _input: &mut Self::Input<'_>, params: &Params) -> Result<()> {
+        params.check(self.day(), &[])
+    }
+
+    fn part1(&self, input: &Self::Input<'_>) -> Result<Option<Answer>>;
+
+    fn part2(&self, input: &Self::Input<'_>) -> Result<Option<Answer>>;
+
+    fn validate(&self, _input: &str) -> Vec<Violation> {
+        vec![]
+    }
+
+    fn generate(&self, _rng: &mut Rng, _size: usize) -> Option<String> {
+        None
+    }
+
+    fn explain(&self, _input: &Self::Input<'_>) -> Result<Vec<Artifact>> {
+        Ok(vec![])
+    }
+}
+
+// A puzzle with the type of its model erased, so puzzles of all days can be
+// registered and run alike
+pub trait Solver: Sync {
+    fn year(&self) -> u16;
+
+    fn day(&self) -> u8;
+
+    fn parse<'a>(&'a self, input: &'a str) -> Result<Box<dyn Parsed + 'a>> {
+        self.parse_with(input, &Params::default())
+    }
+
+    // Parse `input` and apply the day's `params` to the model
+    fn parse_with<'a>(&'a self, input: &'a str, params: &Params) -> Result<Box<dyn Parsed + 'a>>;
+
+    fn validate(&self, input: &str) -> Vec<Violation>;
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String>;
+}
+
+// The parsed input of a puzzle, held together with the input it borrows from
+pub trait Parsed {
+    fn part1(&self) -> Result<Option<Answer>>;
+
+    fn part2(&self) -> Result<Option<Answer>>;
+
+    fn explain(&self) -> Result<Vec<Artifact>>;
+}
+
+struct Model<'a, P: Puzzle> {
+    puzzle: &'a P,
+    input: P::Input<'a>,
+}
+
+impl<P: Puzzle> Parsed for Model<'_, P> {
+    fn part1(&self) -> Result<Option<Answer>> {
+        self.puzzle.part1(&self.input)
+    }
+
+    fn part2(&self) -> Result<Option<Answer>> {
+        self.puzzle.part2(&self.input)
+    }
+
+    fn explain(&self) -> Result<Vec<Artifact>> {
+        self.puzzle.explain(&self.input)
+    }
+}
+
+impl<P: Puzzle> Solver for P {
+    fn year(&self) -> u16 {
+        Puzzle::year(self)
+    }
+
+    fn day(&self) -> u8 {
+        Puzzle::day(self)
+    }
+
+    fn parse_with<'a>(&'a self, input: &'a str, params: &Params) -> Result<Box<dyn Parsed + 'a>> {
+        let mut input = Puzzle::parse(self, input)?;
+        Puzzle::configure(self, &mut input, params)?;
+        Ok(Box::new(Model {
+            puzzle: self,
+            input,
+        }))
+    }
+
+    fn validate(&self, input: &str) -> Vec<Violation> {
+        Puzzle::validate(self, input)
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        Puzzle::generate(self, rng, size)
+    }
+}
+
+// Solver of a day, registered by the day's module with
+//
+//   solver::register!(Solution);
+pub struct Registration(pub &'static dyn Solver);
+
+inventory::collect!(Registration);
+
+// Register the puzzle of a day, and give the day's module the same shape as
+// every other day's:
+//
+//   let model = dayNN::parse(input)?;
+//   let answer = dayNN::part1(&model)?;
+//
+// (unused when no days are compiled in)
+#[allow(unused_macros)]
+macro_rules! register {
+    ($puzzle:ident) => {
+        inventory::submit!($crate::solver::Registration(&$puzzle));
+
+        /// Parse the input of the day into its model
+        pub fn parse(
+            input: &str,
+        ) -> anyhow::Result<<$puzzle as $crate::solver::Puzzle>::Input<'_>> {
+            $crate::solver::Puzzle::parse(&$puzzle, input)
+        }
+
+        /// Answer of part 1 of the day, if it's solved
+        pub fn part1(
+            input: &<$puzzle as $crate::solver::Puzzle>::Input<'_>,
+        ) -> anyhow::Result<Option<$crate::Answer>> {
+            $crate::solver::Puzzle::part1(&$puzzle, input)
+        }
+
+        /// Answer of part 2 of the day, if it's solved
+        pub fn part2(
+            input: &<$puzzle as $crate::solver::Puzzle>::Input<'_>,
+        ) -> anyhow::Result<Option<$crate::Answer>> {
+            $crate::solver::Puzzle::part2(&$puzzle, input)
+        }
+    };
+}
+
+#[allow(unused_imports)]
+pub(crate) use register;
+
+// Registered solvers, in order of year and day
+static SOLVERS: LazyLock<Vec<&'static dyn Solver>> = LazyLock::new(|| {
+    let mut solvers = inventory::iter::<Registration>
+        .into_iter()
+        .map(|registration| registration.0)
+        .collect::<Vec<_>>();
+    solvers.sort_by_key(|solver| (solver.year(), solver.day()));
+    solvers
+});
+
+// All solvers, in order of year and day
+pub fn solvers() -> impl Iterator<Item = &'static dyn Solver> {
+    SOLVERS.iter().copied()
+}
+
+// Solver of the given day, if it's implemented
+pub fn solver(year: u16, day: u8) -> Option<&'static dyn Solver> {
+    solvers().find(|solver| solver.year() == year && solver.day() == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solvers_are_registered_once() {
+        let days = solvers()
+            .map(|solver| (solver.year(), solver.day()))
+            .collect::<Vec<_>>();
+        assert!(days.windows(2).all(|w| w[0] < w[1]), "{:?}", days);
+    }
+}
@@ -0,0 +1,45 @@
+// Scanning of input bytes, without decoding them as UTF-8 first
+
+// Lines of `bytes`, without their "\n" or "\r\n", like `str::lines`
+pub fn lines(bytes: &[u8]) -> impl Iterator<Item = &[u8]> {
+    let empty = bytes.is_empty();
+    let bytes = bytes.strip_suffix(b"\n").unwrap_or(bytes);
+    split(bytes, b'\n')
+        .take(if empty { 0 } else { usize::MAX })
+        .map(|line| line.strip_suffix(b"\r").unwrap_or(line))
+}
+
+// Index of the first ASCII digit of `bytes`
+pub fn find_digit(bytes: &[u8]) -> Option<usize> {
+    bytes.iter().position(u8::is_ascii_digit)
+}
+
+// Index of the last ASCII digit of `bytes`
+pub fn rfind_digit(bytes: &[u8]) -> Option<usize> {
+    bytes.iter().rposition(u8::is_ascii_digit)
+}
+
+fn split(bytes: &[u8], sep: u8) -> impl Iterator<Item = &[u8]> {
+    bytes.split(move |&b| b == sep)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lines() {
+        for s in ["", "\n", "a", "a\nb", "a\r\nb\n", "a\n\nb\n\n"] {
+            let expected = s.lines().map(str::as_bytes).collect::<Vec<_>>();
+            assert_eq!(lines(s.as_bytes()).collect::<Vec<_>>(), expected, "{:?}", s);
+        }
+    }
+
+    #[test]
+    fn test_find_digit() {
+        assert_eq!(find_digit(b"abcdefgh1jk2"), Some(8));
+        assert_eq!(rfind_digit(b"1abcdefgh2jk"), Some(9));
+        assert_eq!(find_digit(b"abc"), None);
+        assert_eq!(rfind_digit(b""), None);
+    }
+}
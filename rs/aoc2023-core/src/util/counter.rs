@@ -0,0 +1,111 @@
+use std::{collections::HashMap, hash::Hash};
+
+// Number of times each item was seen, e.g. the cards of a hand
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Counter<T: Eq + Hash> {
+    counts: HashMap<T, usize>,
+}
+
+impl<T: Eq + Hash> Default for Counter<T> {
+    fn default() -> Self {
+        Counter {
+            counts: HashMap::new(),
+        }
+    }
+}
+
+impl<T: Eq + Hash> FromIterator<T> for Counter<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut counter = Counter::default();
+        counter.extend(iter);
+        counter
+    }
+}
+
+impl<T: Eq + Hash> Extend<T> for Counter<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.add(item, 1);
+        }
+    }
+}
+
+impl<T: Eq + Hash> Counter<T> {
+    pub fn new() -> Self {
+        Counter::default()
+    }
+
+    // Count `item` `n` more times
+    pub fn add(&mut self, item: T, n: usize) {
+        *self.counts.entry(item).or_default() += n;
+    }
+
+    // Times `item` was seen, 0 if never
+    pub fn count(&self, item: &T) -> usize {
+        self.counts.get(item).copied().unwrap_or(0)
+    }
+
+    // Forget `item`, returning the times it was seen
+    pub fn remove(&mut self, item: &T) -> usize {
+        self.counts.remove(item).unwrap_or(0)
+    }
+
+    // Number of different items
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    // Number of items seen, counting each time
+    pub fn total(&self) -> usize {
+        self.counts.values().sum()
+    }
+
+    // Items with their counts, in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = (&T, usize)> {
+        self.counts.iter().map(|(item, &count)| (item, count))
+    }
+}
+
+impl<T: Eq + Hash + Ord> Counter<T> {
+    // Items with their counts, the most common first, and items as common
+    // in their order
+    pub fn most_common(&self) -> Vec<(&T, usize)> {
+        let mut counts = self.iter().collect::<Vec<_>>();
+        counts.sort_by(|(a, a_count), (b, b_count)| b_count.cmp(a_count).then(a.cmp(b)));
+        counts
+    }
+
+    // Items with their counts, in the order of the items
+    pub fn into_sorted(self) -> Vec<(T, usize)> {
+        let mut counts = self.counts.into_iter().collect::<Vec<_>>();
+        counts.sort();
+        counts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counter() {
+        let mut counter = "abracadabra".chars().collect::<Counter<_>>();
+        assert_eq!(counter.count(&'a'), 5);
+        assert_eq!(counter.count(&'z'), 0);
+        assert_eq!((counter.len(), counter.total()), (5, 11));
+        assert_eq!(
+            counter.most_common(),
+            [(&'a', 5), (&'b', 2), (&'r', 2), (&'c', 1), (&'d', 1)]
+        );
+        assert_eq!(counter.remove(&'a'), 5);
+        counter.add('d', 2);
+        assert_eq!(
+            counter.into_sorted(),
+            [('b', 2), ('c', 1), ('d', 3), ('r', 2)]
+        );
+    }
+}
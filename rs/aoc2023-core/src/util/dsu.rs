@@ -0,0 +1,111 @@
+// Disjoint sets of the elements 0..len, e.g. the components of a graph as its
+// edges are added with `union`
+#[derive(Debug, Clone)]
+pub struct Dsu {
+    // parent of each element, itself for the root of its set
+    parent: Vec<usize>,
+    // size of each set, at its root
+    size: Vec<usize>,
+    sets: usize,
+}
+
+impl Dsu {
+    // `len` sets of one element each
+    pub fn new(len: usize) -> Self {
+        Dsu {
+            parent: (0..len).collect(),
+            size: vec![1; len],
+            sets: len,
+        }
+    }
+
+    // Number of elements
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    // Number of sets
+    pub fn sets(&self) -> usize {
+        self.sets
+    }
+
+    // Root of the set of `x`, pointing the elements on the way at it
+    pub fn find(&mut self, x: usize) -> usize {
+        let mut root = x;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+        let mut x = x;
+        while self.parent[x] != root {
+            x = std::mem::replace(&mut self.parent[x], root);
+        }
+        root
+    }
+
+    // Merge the sets of `a` and `b`, the smaller one into the larger one;
+    // false if they were already the same set
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        let (large, small) = if self.size[a] >= self.size[b] {
+            (a, b)
+        } else {
+            (b, a)
+        };
+        self.parent[small] = large;
+        self.size[large] += self.size[small];
+        self.sets -= 1;
+        true
+    }
+
+    pub fn same(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    // Number of elements in the set of `x`
+    pub fn size(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+        self.size[root]
+    }
+
+    // Elements of each set, sorted, with the sets in the order of their
+    // smallest elements
+    pub fn components(&mut self) -> Vec<Vec<usize>> {
+        let mut index = vec![usize::MAX; self.len()];
+        let mut components: Vec<Vec<usize>> = vec![];
+        for x in 0..self.len() {
+            let root = self.find(x);
+            if index[root] == usize::MAX {
+                index[root] = components.len();
+                components.push(vec![]);
+            }
+            components[index[root]].push(x);
+        }
+        components
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dsu() {
+        let mut dsu = Dsu::new(6);
+        assert!(dsu.union(0, 1));
+        assert!(dsu.union(3, 4));
+        assert!(dsu.union(4, 1));
+        assert!(!dsu.union(0, 3));
+        assert!(dsu.same(0, 4));
+        assert!(!dsu.same(2, 5));
+        assert_eq!(dsu.size(3), 4);
+        assert_eq!(dsu.sets(), 3);
+        assert_eq!(dsu.components(), [vec![0, 1, 3, 4], vec![2], vec![5]]);
+    }
+}
@@ -0,0 +1,258 @@
+use std::{
+    cmp::Ordering,
+    fmt,
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+
+use anyhow::Result;
+use num_integer::Integer;
+
+// Exact fraction of i128s, always in lowest terms with a positive
+// denominator, for math that floating point would get subtly wrong, e.g.
+// where lines cross. The `checked_` operations fail when a result doesn't
+// fit; the operators panic then, like integer operators in debug builds.
+// i128::MIN is never a numerator or denominator, so negating never overflows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rational {
+    num: i128,
+    den: i128,
+}
+
+impl Rational {
+    pub const ZERO: Rational = Rational { num: 0, den: 1 };
+    pub const ONE: Rational = Rational { num: 1, den: 1 };
+
+    // `num / den` in lowest terms, failing if `den` is 0
+    pub fn new(num: i128, den: i128) -> Result<Self> {
+        anyhow::ensure!(den != 0, "{}/{} divides by zero", num, den);
+        anyhow::ensure!(
+            num != i128::MIN && den != i128::MIN,
+            "{}/{} doesn't fit",
+            num,
+            den
+        );
+        let g = num.gcd(&den);
+        let sign = den.signum();
+        Ok(Rational {
+            num: sign * num / g,
+            den: sign * den / g,
+        })
+    }
+
+    pub fn numer(self) -> i128 {
+        self.num
+    }
+
+    pub fn denom(self) -> i128 {
+        self.den
+    }
+
+    pub fn is_integer(self) -> bool {
+        self.den == 1
+    }
+
+    // The integer it is, if it is one
+    pub fn to_integer(self) -> Option<i128> {
+        self.is_integer().then_some(self.num)
+    }
+
+    // Largest integer not above it
+    pub fn floor(self) -> i128 {
+        Integer::div_floor(&self.num, &self.den)
+    }
+
+    pub fn checked_add(self, other: Rational) -> Result<Rational> {
+        // over the lcm of the denominators, to keep the numbers small
+        let g = self.den.gcd(&other.den);
+        let num = (self.num.checked_mul(other.den / g))
+            .zip(other.num.checked_mul(self.den / g))
+            .and_then(|(a, b)| a.checked_add(b));
+        let den = (self.den / g).checked_mul(other.den);
+        match (num, den) {
+            (Some(num), Some(den)) => Rational::new(num, den),
+            _ => anyhow::bail!("{} + {} doesn't fit", self, other),
+        }
+    }
+
+    pub fn checked_sub(self, other: Rational) -> Result<Rational> {
+        self.checked_add(-other)
+    }
+
+    pub fn checked_mul(self, other: Rational) -> Result<Rational> {
+        // cancel across first, to keep the numbers small
+        let (g1, g2) = (self.num.gcd(&other.den), other.num.gcd(&self.den));
+        let (g1, g2) = (g1.max(1), g2.max(1));
+        let num = (self.num / g1).checked_mul(other.num / g2);
+        let den = (self.den / g2).checked_mul(other.den / g1);
+        match (num, den) {
+            (Some(num), Some(den)) => Rational::new(num, den),
+            _ => anyhow::bail!("{} * {} doesn't fit", self, other),
+        }
+    }
+
+    pub fn checked_div(self, other: Rational) -> Result<Rational> {
+        anyhow::ensure!(other.num != 0, "{} / {} divides by zero", self, other);
+        self.checked_mul(Rational {
+            num: other.den * other.num.signum(),
+            den: other.num.abs(),
+        })
+    }
+}
+
+impl From<i64> for Rational {
+    fn from(n: i64) -> Self {
+        Rational {
+            num: n.into(),
+            den: 1,
+        }
+    }
+}
+
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.den == 1 {
+            write!(f, "{}", self.num)
+        } else {
+            write!(f, "{}/{}", self.num, self.den)
+        }
+    }
+}
+
+impl Neg for Rational {
+    type Output = Rational;
+
+    fn neg(self) -> Rational {
+        Rational {
+            num: -self.num,
+            den: self.den,
+        }
+    }
+}
+
+impl Add for Rational {
+    type Output = Rational;
+
+    fn add(self, other: Rational) -> Rational {
+        self.checked_add(other).unwrap()
+    }
+}
+
+impl Sub for Rational {
+    type Output = Rational;
+
+    fn sub(self, other: Rational) -> Rational {
+        self.checked_sub(other).unwrap()
+    }
+}
+
+impl Mul for Rational {
+    type Output = Rational;
+
+    fn mul(self, other: Rational) -> Rational {
+        self.checked_mul(other).unwrap()
+    }
+}
+
+impl Div for Rational {
+    type Output = Rational;
+
+    fn div(self, other: Rational) -> Rational {
+        self.checked_div(other).unwrap()
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// Compared by their continued fractions rather than by multiplying across,
+// which could overflow
+impl Ord for Rational {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let ((mut a, mut b), (mut c, mut d)) = ((self.num, self.den), (other.num, other.den));
+        // a/b against c/d, the ordering flipped every time they're inverted
+        let mut flipped = false;
+        loop {
+            let (q1, r1) = a.div_mod_floor(&b);
+            let (q2, r2) = c.div_mod_floor(&d);
+            let ordering = match (q1.cmp(&q2), r1, r2) {
+                (Ordering::Equal, 0, 0) => Ordering::Equal,
+                (Ordering::Equal, 0, _) => Ordering::Less,
+                (Ordering::Equal, _, 0) => Ordering::Greater,
+                (Ordering::Equal, _, _) => {
+                    // r1/b against r2/d is b/r1 against d/r2, flipped
+                    ((a, b), (c, d)) = ((b, r1), (d, r2));
+                    flipped = !flipped;
+                    continue;
+                }
+                (ordering, _, _) => ordering,
+            };
+            return if flipped {
+                ordering.reverse()
+            } else {
+                ordering
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn r(num: i128, den: i128) -> Rational {
+        Rational::new(num, den).unwrap()
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        assert_eq!(r(6, -4), r(-3, 2));
+        assert_eq!((r(-3, 2).numer(), r(-3, 2).denom()), (-3, 2));
+        assert_eq!(r(1, 6) + r(1, 3), r(1, 2));
+        assert_eq!(r(1, 6) - r(1, 3), r(-1, 6));
+        assert_eq!(r(2, 3) * r(9, 4), r(3, 2));
+        assert_eq!(r(2, 3) / r(-4, 9), r(-3, 2));
+        assert_eq!(r(-7, 2).floor(), -4);
+        assert_eq!(
+            (r(8, 4).to_integer(), r(7, 4).to_integer()),
+            (Some(2), None)
+        );
+        assert_eq!(r(-7, 2).to_string(), "-7/2");
+        assert!(Rational::new(1, 0).is_err());
+        assert!(r(1, 2).checked_div(Rational::ZERO).is_err());
+    }
+
+    #[test]
+    fn test_overflow() {
+        let big = Rational::from(i64::MAX) * Rational::from(i64::MAX);
+        assert!(big.checked_mul(big).is_err());
+        assert!(big.checked_add(big).is_ok());
+        assert!(r(1, i128::MAX).checked_add(r(1, i128::MAX - 1)).is_err());
+        // cancelled across before multiplying, so it fits
+        assert_eq!(r(i128::MAX, 3) * r(3, i128::MAX), Rational::ONE);
+    }
+
+    #[test]
+    fn test_ordering() {
+        assert!(r(1, 3) < r(1, 2));
+        assert!(r(-1, 2) < r(-1, 3));
+        assert!(r(22, 7) > r(355, 113));
+        assert_eq!(r(2, 4).cmp(&r(1, 2)), Ordering::Equal);
+        // cross-multiplying these would overflow
+        let (a, b) = (r(i128::MAX - 1, i128::MAX), r(i128::MAX - 2, i128::MAX - 1));
+        assert!(a > b);
+        assert!(-a < -b);
+        for (a, b, c, d) in itertools::iproduct!(-6..=6, 1..=6, -6..=6, 1..=6) {
+            assert_eq!(
+                r(a, b).cmp(&r(c, d)),
+                (a * d).cmp(&(c * b)),
+                "{a}/{b} {c}/{d}"
+            );
+        }
+        let mut sorted = [r(3, 2), r(-5, 3), Rational::ZERO, r(4, 3)];
+        sorted.sort();
+        assert_eq!(sorted, [r(-5, 3), Rational::ZERO, r(4, 3), r(3, 2)]);
+    }
+}
@@ -0,0 +1,201 @@
+use std::{fmt::Display, hash::Hash};
+
+use super::hash::HashMap;
+
+// Directed graph of nodes labelled `L`, e.g. the labels of a network, with
+// edges carrying an `E`. Nodes are numbered in the order they're added, and
+// edges out of each node kept in the order they're added.
+#[derive(Debug, Clone)]
+pub struct Graph<L, E = ()> {
+    labels: Vec<L>,
+    indices: HashMap<L, usize>,
+    edges: Vec<Vec<(usize, E)>>,
+}
+
+impl<L, E> Default for Graph<L, E> {
+    fn default() -> Self {
+        Graph {
+            labels: vec![],
+            indices: HashMap::default(),
+            edges: vec![],
+        }
+    }
+}
+
+impl<L: Clone + Eq + Hash, E> Graph<L, E> {
+    pub fn new() -> Self {
+        Graph::default()
+    }
+
+    // Index of the node `label`, added without edges if it's new
+    pub fn add_node(&mut self, label: L) -> usize {
+        if let Some(&index) = self.indices.get(&label) {
+            return index;
+        }
+        let index = self.labels.len();
+        self.indices.insert(label.clone(), index);
+        self.labels.push(label);
+        self.edges.push(vec![]);
+        index
+    }
+
+    // Edge from node `from` to node `to`, adding the nodes if they're new
+    pub fn add_edge(&mut self, from: L, to: L, edge: E) {
+        let (from, to) = (self.add_node(from), self.add_node(to));
+        self.edges[from].push((to, edge));
+    }
+
+    pub fn index(&self, label: &L) -> Option<usize> {
+        self.indices.get(label).copied()
+    }
+
+    pub fn label(&self, index: usize) -> &L {
+        &self.labels[index]
+    }
+
+    // Number of nodes
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+
+    // Edges out of node `index`, as the index of the node they lead to
+    pub fn edges(&self, index: usize) -> &[(usize, E)] {
+        &self.edges[index]
+    }
+
+    // Strongly connected components, the sets of nodes reaching each other,
+    // by Tarjan's algorithm without recursion. Components come after the
+    // components they lead to.
+    pub fn sccs(&self) -> Vec<Vec<usize>> {
+        const UNVISITED: usize = usize::MAX;
+        let len = self.len();
+        let (mut order, mut low) = (vec![UNVISITED; len], vec![0; len]);
+        let mut on_stack = vec![false; len];
+        let (mut stack, mut sccs) = (vec![], vec![]);
+        let mut next = 0;
+        for root in 0..len {
+            if order[root] != UNVISITED {
+                continue;
+            }
+            // nodes being visited, with the next of their edges to follow
+            let mut visiting = vec![(root, 0)];
+            order[root] = next;
+            low[root] = next;
+            next += 1;
+            stack.push(root);
+            on_stack[root] = true;
+            while let Some(&(node, edge)) = visiting.last() {
+                if let Some(&(to, _)) = self.edges[node].get(edge) {
+                    visiting.last_mut().unwrap().1 += 1;
+                    if order[to] == UNVISITED {
+                        order[to] = next;
+                        low[to] = next;
+                        next += 1;
+                        stack.push(to);
+                        on_stack[to] = true;
+                        visiting.push((to, 0));
+                    } else if on_stack[to] {
+                        low[node] = low[node].min(order[to]);
+                    }
+                    continue;
+                }
+                visiting.pop();
+                if let Some(&(parent, _)) = visiting.last() {
+                    low[parent] = low[parent].min(low[node]);
+                }
+                if low[node] == order[node] {
+                    let mut scc = vec![];
+                    while let Some(member) = stack.pop() {
+                        on_stack[member] = false;
+                        scc.push(member);
+                        if member == node {
+                            break;
+                        }
+                    }
+                    sccs.push(scc);
+                }
+            }
+        }
+        sccs
+    }
+
+    // Whether some path leads from a node back to itself
+    pub fn has_cycle(&self) -> bool {
+        self.sccs()
+            .iter()
+            .any(|scc| scc.len() > 1 || self.edges[scc[0]].iter().any(|&(to, _)| to == scc[0]))
+    }
+}
+
+impl<L: Display, E> Graph<L, E> {
+    // Graphviz DOT of the graph, with the edges labelled by `edge_label`
+    pub fn to_dot(&self, edge_label: impl Fn(&E) -> Option<String>) -> String {
+        let quote = |s: &str| format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""));
+        let mut dot = String::from("digraph {\n");
+        for label in &self.labels {
+            dot += &format!("  {};\n", quote(&label.to_string()));
+        }
+        for (from, edges) in self.edges.iter().enumerate() {
+            for (to, edge) in edges {
+                let from = quote(&self.labels[from].to_string());
+                let to = quote(&self.labels[*to].to_string());
+                match edge_label(edge) {
+                    Some(label) => {
+                        dot += &format!("  {} -> {} [label={}];\n", from, to, quote(&label))
+                    }
+                    None => dot += &format!("  {} -> {};\n", from, to),
+                }
+            }
+        }
+        dot + "}\n"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_graph() {
+        // a <-> b -> c -> d -> c, e alone
+        let mut graph = Graph::new();
+        for (from, to) in [("a", "b"), ("b", "a"), ("b", "c"), ("c", "d"), ("d", "c")] {
+            graph.add_edge(from, to, ());
+        }
+        graph.add_node("e");
+        assert_eq!(graph.len(), 5);
+        assert_eq!(graph.index(&"c"), Some(2));
+        assert_eq!(graph.label(4), &"e");
+        assert_eq!(graph.edges(1).len(), 2);
+
+        let mut sccs = graph.sccs();
+        assert_eq!(sccs.len(), 3);
+        // c and d come before a and b, which lead to them
+        sccs.iter_mut().for_each(|scc| scc.sort());
+        assert_eq!(sccs, [vec![2, 3], vec![0, 1], vec![4]]);
+        assert!(graph.has_cycle());
+
+        let mut dag = Graph::new();
+        dag.add_edge(1, 2, "x");
+        dag.add_edge(2, 3, "y");
+        assert!(!dag.has_cycle());
+        assert_eq!(
+            dag.to_dot(|&edge| (edge == "x").then(|| edge.to_string())),
+            "digraph {\n  \"1\";\n  \"2\";\n  \"3\";\n  \"1\" -> \"2\" [label=\"x\"];\n  \"2\" -> \"3\";\n}\n"
+        );
+    }
+
+    // Deep graphs don't overflow the stack
+    #[test]
+    fn test_long_cycle() {
+        let mut graph = Graph::new();
+        for i in 0..100_000 {
+            graph.add_edge(i, (i + 1) % 100_000, ());
+        }
+        assert_eq!(graph.sccs().len(), 1);
+    }
+}
@@ -0,0 +1,286 @@
+use std::{
+    fmt,
+    ops::{Index, IndexMut},
+};
+
+use anyhow::Result;
+
+// Position in a grid, as (row, col) from the top-left (0, 0)
+pub type Pos = (usize, usize);
+
+// Direction to move in a grid, up being toward row 0
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+    ];
+
+    pub fn reverse(self) -> Self {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+        }
+    }
+}
+
+// Rectangle of cells in row-major order, e.g. parsed from
+//
+//   #.#
+//   ..#
+//
+// with `Grid::parse`, a cell per byte, to a grid of 2 rows and 3 columns
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    rows: usize,
+    cols: usize,
+}
+
+impl<T> Grid<T> {
+    // Grid of the given rows of cells, which must all be of the same length
+    pub fn new(rows: Vec<Vec<T>>) -> Result<Self> {
+        let cols = rows.first().map_or(0, Vec::len);
+        anyhow::ensure!(
+            rows.iter().all(|row| row.len() == cols),
+            "rows of the grid must be of the same length"
+        );
+        let rows_len = rows.len();
+        Ok(Grid {
+            cells: rows.into_iter().flatten().collect(),
+            rows: rows_len,
+            cols,
+        })
+    }
+
+    // Grid of `rows` rows of `cols` copies of `cell`
+    pub fn filled(rows: usize, cols: usize, cell: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            cells: vec![cell; rows * cols],
+            rows,
+            cols,
+        }
+    }
+
+    // Grid of the lines of `s`, with a cell parsed from each byte
+    pub fn parse(s: &[u8], cell: impl FnMut(u8) -> Result<T>) -> Result<Self> {
+        let mut cell = cell;
+        let rows = s
+            .split(|&b| b == b'\n')
+            .map(|line| line.strip_suffix(b"\r").unwrap_or(line))
+            .filter(|line| !line.is_empty())
+            .map(|line| line.iter().map(|&b| cell(b)).collect::<Result<Vec<_>>>())
+            .collect::<Result<Vec<_>>>()?;
+        Grid::new(rows)
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn get(&self, (row, col): Pos) -> Option<&T> {
+        (row < self.rows && col < self.cols).then(|| &self.cells[row * self.cols + col])
+    }
+
+    pub fn get_mut(&mut self, (row, col): Pos) -> Option<&mut T> {
+        (row < self.rows && col < self.cols).then(|| &mut self.cells[row * self.cols + col])
+    }
+
+    // Position next to `pos` in direction `dir`, if it's in the grid
+    pub fn step(&self, (row, col): Pos, dir: Direction) -> Option<Pos> {
+        let pos = match dir {
+            Direction::Up => (row.checked_sub(1)?, col),
+            Direction::Down => (row + 1, col),
+            Direction::Left => (row, col.checked_sub(1)?),
+            Direction::Right => (row, col + 1),
+        };
+        (pos.0 < self.rows && pos.1 < self.cols).then_some(pos)
+    }
+
+    // Positions up, down, left and right of `pos` that are in the grid
+    pub fn neighbors(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |dir| self.step(pos, dir))
+    }
+
+    // Positions of all cells, row by row
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let cols = self.cols;
+        (0..self.rows).flat_map(move |row| (0..cols).map(move |col| (row, col)))
+    }
+
+    // Cells of row `row`, left to right
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.cols..(row + 1) * self.cols]
+    }
+
+    // Cells of column `col`, top to bottom
+    pub fn col(&self, col: usize) -> impl DoubleEndedIterator<Item = &T> {
+        assert!(col < self.cols, "column {} out of {}", col, self.cols);
+        self.cells[col..].iter().step_by(self.cols)
+    }
+
+    // Rows of cells, top to bottom
+    pub fn iter_rows(&self) -> impl DoubleEndedIterator<Item = &[T]> {
+        self.cells.chunks_exact(self.cols.max(1))
+    }
+
+    // Columns of cells, left to right, each top to bottom
+    pub fn iter_cols(&self) -> impl Iterator<Item = impl DoubleEndedIterator<Item = &T>> {
+        (0..self.cols).map(|col| self.col(col))
+    }
+
+    // Cells with their positions, row by row
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    // Move every cell that `is_mover` as far as it goes toward `dir`, stopped
+    // by the edge, cells that `is_blocker` and the movers already stopped,
+    // swapping places with the cells it passes over. Returns the moves made,
+    // as (from, to).
+    pub fn slide(
+        &mut self,
+        dir: Direction,
+        is_blocker: impl Fn(&T) -> bool,
+        is_mover: impl Fn(&T) -> bool,
+    ) -> Vec<(Pos, Pos)> {
+        let (lines, len) = match dir {
+            Direction::Up | Direction::Down => (self.cols, self.rows),
+            Direction::Left | Direction::Right => (self.rows, self.cols),
+        };
+        let mut moves = vec![];
+        for line in 0..lines {
+            // i-th position of the line, counting from the edge toward `dir`
+            let pos = |i: usize| match dir {
+                Direction::Up => (i, line),
+                Direction::Down => (len - 1 - i, line),
+                Direction::Left => (line, i),
+                Direction::Right => (line, len - 1 - i),
+            };
+            let mut free = 0;
+            for i in 0..len {
+                let cell = &self[pos(i)];
+                if is_blocker(cell) {
+                    free = i + 1;
+                } else if is_mover(cell) {
+                    if free != i {
+                        let ((from_row, from_col), (to_row, to_col)) = (pos(i), pos(free));
+                        self.cells
+                            .swap(from_row * self.cols + from_col, to_row * self.cols + to_col);
+                        moves.push((pos(i), pos(free)));
+                    }
+                    free += 1;
+                }
+            }
+        }
+        moves
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (row, col): Pos) -> &T {
+        assert!(
+            row < self.rows && col < self.cols,
+            "{:?} out of the grid",
+            (row, col)
+        );
+        &self.cells[row * self.cols + col]
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, (row, col): Pos) -> &mut T {
+        assert!(
+            row < self.rows && col < self.cols,
+            "{:?} out of the grid",
+            (row, col)
+        );
+        &mut self.cells[row * self.cols + col]
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.iter_rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Result<Grid<char>> {
+        Grid::parse(b"#.#\n..#\n", |b| Ok(b as char))
+    }
+
+    #[test]
+    fn test_grid() -> Result<()> {
+        let grid = grid()?;
+        assert_eq!((grid.rows(), grid.cols()), (2, 3));
+        assert_eq!(grid[(1, 2)], '#');
+        assert_eq!(grid.get((2, 0)), None);
+        assert_eq!(grid.row(1), ['.', '.', '#']);
+        assert_eq!(grid.col(2).collect::<String>(), "##");
+        assert_eq!(grid.iter_cols().count(), 3);
+        assert_eq!(grid.to_string(), "#.#\n..#\n");
+        assert!(Grid::parse(b"##\n#\n", Ok).is_err());
+        assert_eq!(Grid::filled(2, 3, '.').to_string(), "...\n...\n");
+        Ok(())
+    }
+
+    #[test]
+    fn test_neighbors() -> Result<()> {
+        let grid = grid()?;
+        assert_eq!(grid.neighbors((0, 0)).collect::<Vec<_>>(), [(1, 0), (0, 1)]);
+        assert_eq!(
+            grid.neighbors((1, 1)).collect::<Vec<_>>(),
+            [(0, 1), (1, 0), (1, 2)]
+        );
+        assert_eq!(grid.step((1, 2), Direction::Right), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_slide() -> Result<()> {
+        let mut grid = Grid::parse(b"O.#O.\n.O..O\n", |b| Ok(b as char))?;
+        let moves = grid.slide(Direction::Right, |&c| c == '#', |&c| c == 'O');
+        assert_eq!(grid.to_string(), ".O#.O\n...OO\n");
+        assert_eq!(moves.len(), 3);
+        assert!(moves.contains(&((1, 1), (1, 3))));
+
+        grid.slide(Direction::Up, |&c| c == '#', |&c| c == 'O');
+        assert_eq!(grid.to_string(), ".O#OO\n....O\n");
+        assert!(grid
+            .slide(Direction::Up, |&c| c == '#', |&c| c == 'O')
+            .is_empty());
+        Ok(())
+    }
+}
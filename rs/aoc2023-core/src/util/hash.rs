@@ -0,0 +1,19 @@
+// Hash maps and sets for the small keys of the days, e.g. positions and
+// labels. With the `fast-hash` feature they hash with aHash, which is much
+// quicker on those than the std SipHash; without it, with SipHash. Make them
+// with `default()`, `with_capacity_and_hasher` or `collect()`, which both
+// hashers have, rather than `new()` and `with_capacity()`, which only
+// SipHash does.
+
+#[cfg(feature = "fast-hash")]
+pub type RandomState = ahash::RandomState;
+#[cfg(not(feature = "fast-hash"))]
+pub type RandomState = std::collections::hash_map::RandomState;
+
+pub type HashMap<K, V> = std::collections::HashMap<K, V, RandomState>;
+pub type HashSet<T> = std::collections::HashSet<T, RandomState>;
+
+// Empty map with room for `capacity` entries
+pub fn map_with_capacity<K, V>(capacity: usize) -> HashMap<K, V> {
+    HashMap::with_capacity_and_hasher(capacity, RandomState::default())
+}
@@ -0,0 +1,349 @@
+use std::fmt;
+
+// Half-open interval of the numbers from `start` up to `end`, empty if `end`
+// isn't past `start`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Interval {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl fmt::Display for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}..{}", self.start, self.end)
+    }
+}
+
+impl From<std::ops::Range<usize>> for Interval {
+    fn from(range: std::ops::Range<usize>) -> Self {
+        Interval::new(range.start, range.end)
+    }
+}
+
+impl Interval {
+    pub fn new(start: usize, end: usize) -> Self {
+        Interval { start, end }
+    }
+
+    // Interval of the `len` numbers from `start` on
+    pub fn with_len(start: usize, len: usize) -> Self {
+        Interval::new(start, start + len)
+    }
+
+    pub fn len(&self) -> usize {
+        self.end.saturating_sub(self.start)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.end <= self.start
+    }
+
+    pub fn contains(&self, n: usize) -> bool {
+        self.start <= n && n < self.end
+    }
+
+    // Numbers in both intervals, if any
+    pub fn intersection(&self, other: &Interval) -> Option<Interval> {
+        let overlap = Interval::new(self.start.max(other.start), self.end.min(other.end));
+        (!overlap.is_empty()).then_some(overlap)
+    }
+
+    // Numbers of this interval that aren't in `other`: up to two intervals,
+    // below and above it
+    pub fn difference(&self, other: &Interval) -> impl Iterator<Item = Interval> {
+        let below = Interval::new(self.start, self.end.min(other.start));
+        let above = Interval::new(self.start.max(other.end), self.end);
+        [below, above]
+            .into_iter()
+            .filter(|interval| !interval.is_empty())
+    }
+}
+
+// Set of numbers as the fewest intervals covering them, sorted, e.g. the
+// intervals 3..5, 0..2 and 4..7 are the set {0..2, 3..7}
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct IntervalSet(Vec<Interval>);
+
+impl fmt::Display for IntervalSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        for (i, interval) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", interval)?;
+        }
+        write!(f, "}}")
+    }
+}
+
+impl<I: Into<Interval>> FromIterator<I> for IntervalSet {
+    fn from_iter<T: IntoIterator<Item = I>>(iter: T) -> Self {
+        let mut intervals = iter
+            .into_iter()
+            .map(Into::into)
+            .filter(|interval: &Interval| !interval.is_empty())
+            .collect::<Vec<_>>();
+        intervals.sort();
+        let mut merged: Vec<Interval> = vec![];
+        for interval in intervals {
+            match merged.last_mut() {
+                Some(last) if interval.start <= last.end => last.end = last.end.max(interval.end),
+                _ => merged.push(interval),
+            }
+        }
+        IntervalSet(merged)
+    }
+}
+
+impl IntervalSet {
+    // Intervals of the set, sorted and apart from each other
+    pub fn intervals(&self) -> &[Interval] {
+        &self.0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    // Number of numbers in the set
+    pub fn len(&self) -> usize {
+        self.0.iter().map(Interval::len).sum()
+    }
+
+    // Smallest number in the set
+    pub fn min(&self) -> Option<usize> {
+        self.0.first().map(|interval| interval.start)
+    }
+
+    pub fn contains(&self, n: usize) -> bool {
+        let i = self.0.partition_point(|interval| interval.end <= n);
+        self.0.get(i).is_some_and(|interval| interval.contains(n))
+    }
+
+    pub fn union(&self, other: &IntervalSet) -> IntervalSet {
+        self.0.iter().chain(&other.0).copied().collect()
+    }
+
+    pub fn intersection(&self, other: &IntervalSet) -> IntervalSet {
+        let mut intervals = vec![];
+        let (mut i, mut j) = (0, 0);
+        while i < self.0.len() && j < other.0.len() {
+            let (a, b) = (self.0[i], other.0[j]);
+            intervals.extend(a.intersection(&b));
+            if a.end <= b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        IntervalSet(intervals)
+    }
+
+    pub fn difference(&self, other: &IntervalSet) -> IntervalSet {
+        let mut intervals = vec![];
+        for &interval in &self.0 {
+            let mut rest = interval;
+            for cut in &other.0 {
+                if cut.end <= rest.start {
+                    continue;
+                }
+                if cut.start >= rest.end {
+                    break;
+                }
+                if rest.start < cut.start {
+                    intervals.push(Interval::new(rest.start, cut.start));
+                }
+                rest.start = cut.end;
+            }
+            if !rest.is_empty() {
+                intervals.push(rest);
+            }
+        }
+        IntervalSet(intervals)
+    }
+}
+
+// Mapping of the numbers of some intervals to other numbers, keeping their
+// order within an interval, e.g. 5..8 to 20..23 maps 6 to 21. Numbers outside
+// all intervals map to themselves.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Mapping {
+    // intervals, sorted and apart from each other, with where their start
+    // maps to
+    shifts: Vec<(Interval, usize)>,
+}
+
+impl fmt::Display for Mapping {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (src, dst) in &self.shifts {
+            writeln!(f, "{} -> {}", src, Interval::with_len(*dst, src.len()))?;
+        }
+        Ok(())
+    }
+}
+
+impl Mapping {
+    // Mapping of each interval to the one of the same length from the number
+    // paired with it; where intervals overlap, the first one maps the numbers
+    pub fn new(shifts: impl IntoIterator<Item = (Interval, usize)>) -> Self {
+        let mut mapped = IntervalSet::default();
+        let mut disjoint = vec![];
+        for (src, dst) in shifts {
+            for part in IntervalSet::from_iter([src]).difference(&mapped).0 {
+                disjoint.push((part, dst + (part.start - src.start)));
+            }
+            mapped = mapped.union(&IntervalSet::from_iter([src]));
+        }
+        disjoint.sort();
+        Mapping { shifts: disjoint }
+    }
+
+    // Intervals mapped, sorted, with where their start maps to
+    pub fn shifts(&self) -> &[(Interval, usize)] {
+        &self.shifts
+    }
+
+    pub fn map(&self, n: usize) -> usize {
+        let i = self.shifts.partition_point(|(src, _)| src.end <= n);
+        match self.shifts.get(i) {
+            Some((src, dst)) if src.contains(n) => dst + (n - src.start),
+            _ => n,
+        }
+    }
+
+    // Pieces `interval` splits into at the bounds of the intervals mapped, as
+    // each piece and the number its start maps to
+    fn split(&self, interval: Interval) -> Vec<(Interval, usize)> {
+        let mut pieces = vec![];
+        let mut start = interval.start;
+        let first = self
+            .shifts
+            .partition_point(|(src, _)| src.end <= interval.start);
+        for &(src, dst) in &self.shifts[first..] {
+            if src.start >= interval.end {
+                break;
+            }
+            if start < src.start {
+                pieces.push((Interval::new(start, src.start), start));
+                start = src.start;
+            }
+            let end = src.end.min(interval.end);
+            pieces.push((Interval::new(start, end), dst + (start - src.start)));
+            start = end;
+        }
+        if start < interval.end {
+            pieces.push((Interval::new(start, interval.end), start));
+        }
+        pieces
+    }
+
+    // Numbers the numbers of `set` map to
+    pub fn map_set(&self, set: &IntervalSet) -> IntervalSet {
+        set.intervals()
+            .iter()
+            .flat_map(|&interval| self.split(interval))
+            .map(|(piece, dst)| Interval::with_len(dst, piece.len()))
+            .collect()
+    }
+
+    // Mapping of the numbers through this mapping and then `next`
+    pub fn then(&self, next: &Mapping) -> Mapping {
+        // the numbers this maps, and the ones only `next` does
+        let mapped = self
+            .shifts
+            .iter()
+            .map(|(src, _)| *src)
+            .collect::<IntervalSet>();
+        let rest = next
+            .shifts
+            .iter()
+            .map(|(src, _)| *src)
+            .collect::<IntervalSet>()
+            .difference(&mapped);
+        let shifts = self
+            .shifts
+            .iter()
+            .flat_map(|&(src, dst)| {
+                next.split(Interval::with_len(dst, src.len()))
+                    .into_iter()
+                    .map(move |(piece, to)| {
+                        (
+                            Interval::with_len(src.start + (piece.start - dst), piece.len()),
+                            to,
+                        )
+                    })
+            })
+            .chain(rest.0.into_iter().flat_map(|interval| next.split(interval)))
+            .filter(|(src, dst)| src.start != *dst)
+            .collect::<Vec<_>>();
+        Mapping::new(shifts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(intervals: &[(usize, usize)]) -> IntervalSet {
+        intervals.iter().map(|&(start, end)| start..end).collect()
+    }
+
+    #[test]
+    fn test_interval() {
+        let a = Interval::new(2, 8);
+        assert_eq!(
+            a.intersection(&Interval::new(5, 10)),
+            Some(Interval::new(5, 8))
+        );
+        assert_eq!(a.intersection(&Interval::new(8, 10)), None);
+        assert_eq!(
+            a.difference(&Interval::new(4, 6)).collect::<Vec<_>>(),
+            [Interval::new(2, 4), Interval::new(6, 8)]
+        );
+        assert_eq!(a.difference(&Interval::new(0, 10)).count(), 0);
+    }
+
+    #[test]
+    fn test_interval_set() {
+        let a = set(&[(3, 5), (0, 2), (4, 7), (9, 9)]);
+        assert_eq!(a.to_string(), "{0..2, 3..7}");
+        assert_eq!(a.len(), 6);
+        assert!(a.contains(4) && !a.contains(2) && !a.contains(7));
+        let b = set(&[(1, 4), (6, 10)]);
+        assert_eq!(a.union(&b), set(&[(0, 10)]));
+        assert_eq!(a.intersection(&b), set(&[(1, 2), (3, 4), (6, 7)]));
+        assert_eq!(a.difference(&b), set(&[(0, 1), (4, 6)]));
+    }
+
+    #[test]
+    fn test_mapping() {
+        // seed-to-soil and soil-to-fertilizer maps of the sample of day 5
+        let seed_to_soil = Mapping::new([
+            (Interval::with_len(98, 2), 50),
+            (Interval::with_len(50, 48), 52),
+        ]);
+        let soil_to_fertilizer = Mapping::new([
+            (Interval::with_len(15, 37), 0),
+            (Interval::with_len(52, 2), 37),
+            (Interval::with_len(0, 15), 39),
+        ]);
+        assert_eq!(seed_to_soil.map(79), 81);
+        assert_eq!(seed_to_soil.map(99), 51);
+        assert_eq!(seed_to_soil.map(10), 10);
+        assert_eq!(
+            seed_to_soil.map_set(&set(&[(79, 93), (55, 68)])),
+            set(&[(57, 70), (81, 95)])
+        );
+
+        let composed = seed_to_soil.then(&soil_to_fertilizer);
+        for seed in 0..120 {
+            assert_eq!(
+                composed.map(seed),
+                soil_to_fertilizer.map(seed_to_soil.map(seed)),
+                "seed {}",
+                seed
+            );
+        }
+    }
+}
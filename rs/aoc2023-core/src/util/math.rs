@@ -0,0 +1,119 @@
+use anyhow::Result;
+use num_traits::Signed;
+
+use crate::int::Int;
+
+// Greatest common divisor, 0 only if both are
+pub fn gcd<T: Int>(a: &T, b: &T) -> T {
+    a.gcd(b)
+}
+
+// Least common multiple, failing if it doesn't fit
+pub fn lcm<T: Int>(a: &T, b: &T) -> Result<T> {
+    (a.clone() / a.gcd(b))
+        .checked_mul(b)
+        .ok_or_else(|| anyhow::anyhow!("lcm of {} and {} doesn't fit in the integer", a, b))
+}
+
+// Least common multiple of all of `ns`, 1 if there are none, failing if it
+// doesn't fit
+pub fn lcm_of_set<T: Int>(ns: impl IntoIterator<Item = T>) -> Result<T> {
+    ns.into_iter().try_fold(T::one(), |acc, n| lcm(&acc, &n))
+}
+
+// Greatest common divisor `g` of `a` and `b`, with the Bézout coefficients
+// `x` and `y` such that `a * x + b * y = g`, as (g, x, y)
+pub fn extended_gcd<T: Int + Signed>(a: &T, b: &T) -> (T, T, T) {
+    let (mut old_r, mut r) = (a.clone(), b.clone());
+    let (mut old_x, mut x) = (T::one(), T::zero());
+    let (mut old_y, mut y) = (T::zero(), T::one());
+    while !r.is_zero() {
+        let q = old_r.div_floor(&r);
+        (old_r, r) = (r.clone(), old_r - q.clone() * r);
+        (old_x, x) = (x.clone(), old_x - q.clone() * x);
+        (old_y, y) = (y.clone(), old_y - q * y);
+    }
+    if old_r.is_negative() {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+// Smallest non-negative `x` with `x ≡ residue (mod modulus)` for each of the
+// congruences, e.g. 23 for [(2, 3), (3, 5), (2, 7)], with the modulus it
+// repeats with, the lcm of the moduli. Moduli must be positive but needn't be
+// coprime; fails if the congruences contradict each other, or the numbers
+// don't fit.
+pub fn crt<T: Int + Signed>(congruences: &[(T, T)]) -> Result<(T, T)> {
+    let overflow = || anyhow::anyhow!("solution of the congruences doesn't fit in the integer");
+    let mut solution = (T::zero(), T::one());
+    for (residue, modulus) in congruences {
+        anyhow::ensure!(modulus.is_positive(), "modulus {} isn't positive", modulus);
+        let (x, m) = solution;
+        let (g, p, _) = extended_gcd(&m, modulus);
+        let diff = residue.clone() - x.clone();
+        anyhow::ensure!(
+            diff.is_multiple_of(&g),
+            "x ≡ {} (mod {}) contradicts the congruences before it",
+            residue,
+            modulus
+        );
+        // x + m * t solves both, for t ≡ diff / g * p (mod modulus / g)
+        let step = modulus.clone() / g.clone();
+        let t = (diff / g)
+            .mod_floor(&step)
+            .checked_mul(&p.mod_floor(&step))
+            .ok_or_else(overflow)?
+            .mod_floor(&step);
+        let lcm = m.checked_mul(&step).ok_or_else(overflow)?;
+        let x = m
+            .checked_mul(&t)
+            .map(|mt| mt + x)
+            .ok_or_else(overflow)?
+            .mod_floor(&lcm);
+        solution = (x, lcm);
+    }
+    Ok(solution)
+}
+
+#[cfg(test)]
+mod tests {
+    use num_bigint::BigUint;
+
+    use super::*;
+
+    #[test]
+    fn test_lcm() -> Result<()> {
+        assert_eq!(gcd(&12u64, &18), 6);
+        assert_eq!(lcm_of_set([4u64, 6, 10])?, 60);
+        assert_eq!(lcm_of_set::<u64>([])?, 1);
+
+        let (a, b) = (u64::MAX - 1, u64::MAX - 2);
+        assert!(lcm(&a, &b).is_err());
+        let lcm = lcm(&BigUint::from(a), &BigUint::from(b))?;
+        assert_eq!(lcm, BigUint::from(a) * BigUint::from(b));
+        Ok(())
+    }
+
+    #[test]
+    fn test_extended_gcd() {
+        for (a, b) in [(240i64, 46), (-240, 46), (7, 0), (0, -5), (17, 5)] {
+            let (g, x, y) = extended_gcd(&a, &b);
+            assert_eq!(g, gcd(&a, &b));
+            assert_eq!(a * x + b * y, g);
+        }
+    }
+
+    #[test]
+    fn test_crt() -> Result<()> {
+        assert_eq!(crt(&[(2i64, 3), (3, 5), (2, 7)])?, (23, 105));
+        // moduli that aren't coprime
+        assert_eq!(crt(&[(3i64, 4), (5, 6)])?, (11, 12));
+        assert!(crt(&[(1i64, 4), (2, 6)]).is_err());
+        assert_eq!(crt(&[(-1i64, 5)])?, (4, 5));
+        assert_eq!(crt::<i64>(&[])?, (0, 1));
+        assert!(crt(&[(1i64, i64::MAX), (2, i64::MAX - 1)]).is_err());
+        Ok(())
+    }
+}
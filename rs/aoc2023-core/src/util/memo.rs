@@ -0,0 +1,103 @@
+use std::{collections::HashMap, hash::Hash};
+
+// Recursive function `f` of a state `K` with its values cached, for counting
+// solutions that recurse into the same states over and over. `f` reaches
+// what it works on, e.g. the input, through `context`, and recurses through
+// `get`:
+//
+//   fn ways(memo: &mut Memo<&[u8], usize, u64>, i: usize) -> u64 {
+//       ...
+//       memo.get(i + 1) + memo.get(i + 2)
+//   }
+//
+//   let ways = Memo::new(steps, ways).get(0);
+pub struct Memo<C, K, V> {
+    context: C,
+    cache: HashMap<K, V>,
+    f: fn(&mut Memo<C, K, V>, K) -> V,
+}
+
+impl<C: Copy, K: Clone + Eq + Hash, V: Clone> Memo<C, K, V> {
+    pub fn new(context: C, f: fn(&mut Memo<C, K, V>, K) -> V) -> Self {
+        Memo {
+            context,
+            cache: HashMap::new(),
+            f,
+        }
+    }
+
+    pub fn context(&self) -> C {
+        self.context
+    }
+
+    // Value of `f` for `key`, computed only the first time it's asked for
+    pub fn get(&mut self, key: K) -> V {
+        if let Some(value) = self.cache.get(&key) {
+            return value.clone();
+        }
+        let value = (self.f)(self, key.clone());
+        self.cache.insert(key, value.clone());
+        value
+    }
+
+    // Number of states cached
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Arrangements of the damaged springs `#` in groups of the given sizes,
+    // from spring `i` and group `j` on, with `?` either damaged or not
+    type Arrangements<'a> = Memo<(&'a [u8], &'a [usize]), (usize, usize), u64>;
+
+    fn arrangements(memo: &mut Arrangements, (i, j): (usize, usize)) -> u64 {
+        let (springs, groups) = memo.context();
+        let Some(&spring) = springs.get(i) else {
+            return (j == groups.len()) as u64;
+        };
+        let mut count = 0;
+        if spring != b'#' {
+            count += memo.get((i + 1, j));
+        }
+        if spring != b'.' {
+            if let Some(&len) = groups.get(j) {
+                let fits = springs.len() >= i + len
+                    && springs[i..i + len].iter().all(|&s| s != b'.')
+                    && springs.get(i + len) != Some(&b'#');
+                if fits {
+                    count += memo.get(((i + len + 1).min(springs.len()), j + 1));
+                }
+            }
+        }
+        count
+    }
+
+    #[test]
+    fn test_memo() {
+        for (springs, groups, expected) in [
+            ("???.###", &[1, 1, 3][..], 1),
+            (".??..??...?##.", &[1, 1, 3], 4),
+            ("?###????????", &[3, 2, 1], 10),
+        ] {
+            let mut memo = Memo::new((springs.as_bytes(), groups), arrangements);
+            assert_eq!(memo.get((0, 0)), expected, "{}", springs);
+            assert!(!memo.is_empty());
+        }
+
+        // the same springs unfolded five times, whose recursion only ends
+        // in time with the states cached
+        let springs = ["?###????????"; 5].join("?");
+        let groups = [3, 2, 1].repeat(5);
+        let mut memo = Memo::new((springs.as_bytes(), &groups[..]), arrangements);
+        assert_eq!(memo.get((0, 0)), 506250);
+        assert!(memo.len() <= (springs.len() + 1) * (groups.len() + 1));
+    }
+}
@@ -0,0 +1,17 @@
+// Building blocks shared by the days
+pub mod bytes;
+pub mod counter;
+pub mod dsu;
+pub mod exact;
+pub mod graph;
+pub mod grid;
+pub mod hash;
+pub mod interval;
+pub mod math;
+pub mod memo;
+pub mod pathfind;
+pub mod polygon;
+pub mod priority;
+pub mod sparse;
+pub mod vec3;
+pub mod zobrist;
@@ -0,0 +1,194 @@
+use std::{
+    collections::{hash_map::Entry, HashMap, VecDeque},
+    hash::Hash,
+};
+
+use super::priority::PriorityQueue;
+
+// Shortest distances from a start to the states reached from it, with the
+// state each one was first reached from, to follow paths back to the start
+#[derive(Debug, Clone)]
+pub struct Paths<S> {
+    dist: HashMap<S, usize>,
+    prev: HashMap<S, S>,
+}
+
+impl<S: Clone + Eq + Hash> Paths<S> {
+    fn new(start: S) -> Self {
+        Paths {
+            dist: HashMap::from([(start, 0)]),
+            prev: HashMap::new(),
+        }
+    }
+
+    // Distances of all states reached, the start's 0
+    pub fn distances(&self) -> &HashMap<S, usize> {
+        &self.dist
+    }
+
+    pub fn distance(&self, state: &S) -> Option<usize> {
+        self.dist.get(state).copied()
+    }
+
+    // Longest of the shortest distances, with the state it leads to
+    pub fn farthest(&self) -> Option<(&S, usize)> {
+        self.dist
+            .iter()
+            .map(|(state, &dist)| (state, dist))
+            .max_by_key(|&(_, dist)| dist)
+    }
+
+    // States of the shortest path from the start to `state`, both included
+    pub fn path_to(&self, state: &S) -> Option<Vec<S>> {
+        self.dist.get(state)?;
+        let mut path = vec![state.clone()];
+        while let Some(prev) = self.prev.get(path.last()?) {
+            path.push(prev.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+// Breadth-first search from `start` over the states `neighbors` leads to from
+// each state, every step counting 1
+pub fn bfs<S, I>(start: S, mut neighbors: impl FnMut(&S) -> I) -> Paths<S>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut paths = Paths::new(start.clone());
+    let mut queue = VecDeque::from([(start, 0)]);
+    while let Some((state, dist)) = queue.pop_front() {
+        for next in neighbors(&state) {
+            if let Entry::Vacant(entry) = paths.dist.entry(next.clone()) {
+                entry.insert(dist + 1);
+                paths.prev.insert(next.clone(), state.clone());
+                queue.push_back((next, dist + 1));
+            }
+        }
+    }
+    paths
+}
+
+// Dijkstra's search from `start` over the states `neighbors` leads to from
+// each state, with the cost of the step to each
+pub fn dijkstra<S, I>(start: S, neighbors: impl FnMut(&S) -> I) -> Paths<S>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, usize)>,
+{
+    search(start, neighbors, |_| 0, |_| false).0
+}
+
+// A* search from `start` to the first state that `is_goal`, guided by
+// `heuristic`, which must never overestimate the cost left to a goal. Returns
+// the cost of the cheapest path and its states, both ends included.
+pub fn astar<S, I>(
+    start: S,
+    neighbors: impl FnMut(&S) -> I,
+    heuristic: impl Fn(&S) -> usize,
+    is_goal: impl Fn(&S) -> bool,
+) -> Option<(usize, Vec<S>)>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, usize)>,
+{
+    let (paths, goal) = search(start, neighbors, heuristic, is_goal);
+    let goal = goal?;
+    Some((paths.distance(&goal)?, paths.path_to(&goal)?))
+}
+
+// Best-first search shared by Dijkstra and A*, stopping at the first goal
+fn search<S, I>(
+    start: S,
+    mut neighbors: impl FnMut(&S) -> I,
+    heuristic: impl Fn(&S) -> usize,
+    is_goal: impl Fn(&S) -> bool,
+) -> (Paths<S>, Option<S>)
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, usize)>,
+{
+    let mut paths = Paths::new(start.clone());
+    let mut queue = PriorityQueue::new();
+    queue.push((heuristic(&start), 0), start);
+    // skipping stale entries of states reached more cheaply since
+    while let Some(((_, dist), state)) =
+        queue.pop_current(|&(_, dist), state| paths.dist[state] == dist)
+    {
+        if is_goal(&state) {
+            return (paths, Some(state));
+        }
+        for (next, cost) in neighbors(&state) {
+            let next_dist = dist + cost;
+            if paths.dist.get(&next).is_some_and(|&d| d <= next_dist) {
+                continue;
+            }
+            paths.dist.insert(next.clone(), next_dist);
+            paths.prev.insert(next.clone(), state.clone());
+            queue.push((next_dist + heuristic(&next), next_dist), next);
+        }
+    }
+    (paths, None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Maze of open cells, stepped through up, down, left and right
+    const MAZE: [&str; 4] = ["..#....", ".##.##.", "...#...", ".#...#."];
+
+    fn open((row, col): (usize, usize)) -> bool {
+        MAZE.get(row)
+            .and_then(|line| line.as_bytes().get(col))
+            .is_some_and(|&b| b == b'.')
+    }
+
+    fn neighbors(&(row, col): &(usize, usize)) -> Vec<(usize, usize)> {
+        [
+            (row.wrapping_sub(1), col),
+            (row + 1, col),
+            (row, col.wrapping_sub(1)),
+            (row, col + 1),
+        ]
+        .into_iter()
+        .filter(|&pos| open(pos))
+        .collect()
+    }
+
+    #[test]
+    fn test_bfs() {
+        let paths = bfs((0, 0), neighbors);
+        assert_eq!(paths.distance(&(0, 1)), Some(1));
+        assert_eq!(paths.distance(&(0, 6)), Some(12));
+        assert_eq!(paths.distance(&(0, 2)), None);
+        let path = paths.path_to(&(3, 2)).unwrap();
+        assert_eq!(path.len(), 6);
+        assert_eq!((path[0], path[5]), ((0, 0), (3, 2)));
+        assert_eq!(paths.farthest(), Some((&(1, 3), 16)));
+    }
+
+    #[test]
+    fn test_dijkstra_and_astar() {
+        // steps down cost 5, the others 1
+        let weighted = |pos: &(usize, usize)| {
+            let (row, _) = *pos;
+            neighbors(pos)
+                .into_iter()
+                .map(move |next| (next, if next.0 > row { 5 } else { 1 }))
+        };
+        let paths = dijkstra((0, 0), weighted);
+        assert_eq!(paths.distance(&(0, 1)), Some(1));
+        assert_eq!(paths.distance(&(2, 0)), Some(10));
+
+        let goal = (0, 6);
+        let manhattan = |&(row, col): &(usize, usize)| row.abs_diff(goal.0) + col.abs_diff(goal.1);
+        let (cost, path) = astar((0, 0), weighted, manhattan, |&pos| pos == goal).unwrap();
+        assert_eq!(Some(cost), paths.distance(&goal));
+        assert_eq!(path.first(), Some(&(0, 0)));
+        assert_eq!(path.last(), Some(&goal));
+        assert!(astar((0, 0), weighted, manhattan, |&pos| pos == (0, 2)).is_none());
+    }
+}
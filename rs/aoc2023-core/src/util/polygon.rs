@@ -0,0 +1,112 @@
+use num_integer::Integer;
+
+// Point of the integer lattice, as (x, y)
+pub type Point = (i64, i64);
+
+// Twice the signed area of the polygon of `vertices` in order, by the
+// shoelace formula: positive if they go counter-clockwise with y up. Twice
+// the area is always an integer, the area of lattice polygons may be a half.
+pub fn double_area(vertices: &[Point]) -> i64 {
+    let edges = vertices.iter().zip(vertices.iter().cycle().skip(1));
+    edges.map(|(&(x1, y1), &(x2, y2))| x1 * y2 - x2 * y1).sum()
+}
+
+// Number of lattice points on the sides of the polygon, vertices included
+pub fn boundary_points(vertices: &[Point]) -> u64 {
+    let edges = vertices.iter().zip(vertices.iter().cycle().skip(1));
+    edges
+        .map(|(&(x1, y1), &(x2, y2))| (x2 - x1).gcd(&(y2 - y1)).unsigned_abs())
+        .sum()
+}
+
+// Number of lattice points strictly inside the polygon, by Pick's theorem:
+// its area is the points inside plus half the points on its sides, less 1
+pub fn interior_points(vertices: &[Point]) -> u64 {
+    if vertices.len() < 3 {
+        return 0;
+    }
+    let double_area = double_area(vertices).unsigned_abs();
+    (double_area + 2 - boundary_points(vertices)) / 2
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{Rng as _, SeedableRng};
+
+    use super::*;
+    use crate::generate::Rng;
+
+    // Whether `p` is strictly inside the polygon, by the parity of the sides
+    // crossed going right from it; None if it's on a side
+    fn inside(vertices: &[Point], (px, py): Point) -> Option<bool> {
+        let mut crossings = 0;
+        for (&(x1, y1), &(x2, y2)) in vertices.iter().zip(vertices.iter().cycle().skip(1)) {
+            let cross = (x2 - x1) * (py - y1) - (y2 - y1) * (px - x1);
+            let between = |a: i64, b: i64, v: i64| a.min(b) <= v && v <= a.max(b);
+            if cross == 0 && between(x1, x2, px) && between(y1, y2, py) {
+                return None;
+            }
+            // half-open in y, so vertices are crossed once
+            if (y1 > py) != (y2 > py) {
+                let x = x1 as f64 + (py - y1) as f64 * (x2 - x1) as f64 / (y2 - y1) as f64;
+                if x > px as f64 {
+                    crossings += 1;
+                }
+            }
+        }
+        Some(crossings % 2 == 1)
+    }
+
+    #[test]
+    fn test_area_and_points() {
+        // 4 by 3 rectangle, clockwise
+        let rectangle = [(0, 0), (0, 3), (4, 3), (4, 0)];
+        assert_eq!(double_area(&rectangle), -24);
+        assert_eq!(boundary_points(&rectangle), 14);
+        assert_eq!(interior_points(&rectangle), 6);
+
+        let triangle = [(0, 0), (3, 0), (0, 3)];
+        assert_eq!(double_area(&triangle), 9);
+        assert_eq!(boundary_points(&triangle), 9);
+        assert_eq!(interior_points(&triangle), 1);
+    }
+
+    // Pick's theorem agrees with counting the points inside random star-shaped
+    // polygons one by one
+    #[test]
+    fn test_interior_points_of_random_polygons() {
+        let mut rng = Rng::seed_from_u64(2023);
+        for _ in 0..200 {
+            // vertices around (10, 10) in order of their angle
+            let mut vertices = (0..rng.gen_range(3..8))
+                .map(|_| (rng.gen_range(0..=20), rng.gen_range(0..=20)))
+                .filter(|&p| p != (10, 10))
+                .collect::<Vec<Point>>();
+            vertices.sort_by(|&(x1, y1), &(x2, y2)| {
+                let angle = |x: i64, y: i64| ((y - 10) as f64).atan2((x - 10) as f64);
+                angle(x1, y1).total_cmp(&angle(x2, y2))
+            });
+            // the polygon is simple only if each vertex is less than half a
+            // turn around the middle from the one before it
+            let turns = |&(x1, y1): &Point, &(x2, y2): &Point| {
+                (x1 - 10) * (y2 - 10) - (x2 - 10) * (y1 - 10) > 0
+            };
+            let n = vertices.len();
+            if n < 3 || !(0..n).all(|i| turns(&vertices[i], &vertices[(i + 1) % n])) {
+                continue;
+            }
+            let (mut interior, mut boundary) = (0, 0);
+            for x in 0..=20 {
+                for y in 0..=20 {
+                    match inside(&vertices, (x, y)) {
+                        Some(true) => interior += 1,
+                        Some(false) => {}
+                        None => boundary += 1,
+                    }
+                }
+            }
+            assert_eq!(boundary_points(&vertices), boundary, "{:?}", vertices);
+            assert_eq!(interior_points(&vertices), interior, "{:?}", vertices);
+        }
+    }
+}
@@ -0,0 +1,94 @@
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+// Min-heap of states by cost, for Dijkstra-like searches. States needn't be
+// ordered: the heap orders their costs, ties in the order they were pushed.
+// Pushing a state again at a lower cost leaves the old entry in the queue,
+// so searches skip those stale entries with `pop_current`.
+#[derive(Debug, Clone)]
+pub struct PriorityQueue<C, S> {
+    heap: BinaryHeap<Reverse<(C, usize)>>,
+    states: Vec<Option<S>>,
+}
+
+impl<C: Ord, S> Default for PriorityQueue<C, S> {
+    fn default() -> Self {
+        PriorityQueue {
+            heap: BinaryHeap::new(),
+            states: vec![],
+        }
+    }
+}
+
+impl<C: Ord, S> PriorityQueue<C, S> {
+    pub fn new() -> Self {
+        PriorityQueue::default()
+    }
+
+    pub fn push(&mut self, cost: C, state: S) {
+        self.heap.push(Reverse((cost, self.states.len())));
+        self.states.push(Some(state));
+    }
+
+    // Cheapest state with its cost
+    pub fn pop(&mut self) -> Option<(C, S)> {
+        let Reverse((cost, i)) = self.heap.pop()?;
+        let state = self.states[i].take()?;
+        if self.heap.is_empty() {
+            self.states.clear();
+        }
+        Some((cost, state))
+    }
+
+    // Cheapest state with its cost that `is_current`, dropping the stale
+    // entries cheaper than it, e.g. of states reached more cheaply since
+    pub fn pop_current(&mut self, mut is_current: impl FnMut(&C, &S) -> bool) -> Option<(C, S)> {
+        loop {
+            let (cost, state) = self.pop()?;
+            if is_current(&cost, &state) {
+                return Some((cost, state));
+            }
+        }
+    }
+
+    // Number of entries, stale ones included
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    #[test]
+    fn test_priority_queue() {
+        let mut queue = PriorityQueue::new();
+        for (cost, state) in [(3, "c"), (1, "a"), (2, "b1"), (2, "b2")] {
+            queue.push(cost, state);
+        }
+        assert_eq!(queue.len(), 4);
+        let popped = std::iter::from_fn(|| queue.pop()).collect::<Vec<_>>();
+        assert_eq!(popped, [(1, "a"), (2, "b1"), (2, "b2"), (3, "c")]);
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_stale_entries() {
+        // x pushed at 5, then reached more cheaply at 2
+        let best = HashMap::from([("x", 2), ("y", 4)]);
+        let mut queue = PriorityQueue::new();
+        queue.push(5, "x");
+        queue.push(4, "y");
+        queue.push(2, "x");
+        let mut current = || queue.pop_current(|cost, state| best[state] == *cost);
+        assert_eq!(current(), Some((2, "x")));
+        assert_eq!(current(), Some((4, "y")));
+        assert_eq!(current(), None);
+    }
+}
@@ -0,0 +1,175 @@
+use std::{
+    collections::{hash_map, HashMap},
+    fmt,
+};
+
+// Position in a sparse grid, as (row, col), unbounded either way
+pub type Pos = (i64, i64);
+
+// Grid of few cells spread over a large or unbounded area, e.g. galaxies in
+// an image of mostly empty space, keeping count of the cells of each row and
+// column
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Pos, T>,
+    rows: HashMap<i64, usize>,
+    cols: HashMap<i64, usize>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        SparseGrid {
+            cells: HashMap::new(),
+            rows: HashMap::new(),
+            cols: HashMap::new(),
+        }
+    }
+}
+
+impl<T> FromIterator<(Pos, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Pos, T)>>(iter: I) -> Self {
+        let mut grid = SparseGrid::default();
+        for (pos, cell) in iter {
+            grid.insert(pos, cell);
+        }
+        grid
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        SparseGrid::default()
+    }
+
+    // Put `cell` at `pos`, returning the cell it replaces
+    pub fn insert(&mut self, pos: Pos, cell: T) -> Option<T> {
+        let old = self.cells.insert(pos, cell);
+        if old.is_none() {
+            *self.rows.entry(pos.0).or_default() += 1;
+            *self.cols.entry(pos.1).or_default() += 1;
+        }
+        old
+    }
+
+    pub fn remove(&mut self, pos: Pos) -> Option<T> {
+        let old = self.cells.remove(&pos)?;
+        for (counts, key) in [(&mut self.rows, pos.0), (&mut self.cols, pos.1)] {
+            if let hash_map::Entry::Occupied(mut count) = counts.entry(key) {
+                *count.get_mut() -= 1;
+                if *count.get() == 0 {
+                    count.remove();
+                }
+            }
+        }
+        Some(old)
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.cells.get(&pos)
+    }
+
+    pub fn contains(&self, pos: Pos) -> bool {
+        self.cells.contains_key(&pos)
+    }
+
+    // Number of cells
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    // Cells with their positions, in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.cells.iter().map(|(&pos, cell)| (pos, cell))
+    }
+
+    // Top-left and bottom-right corners of the smallest rectangle holding
+    // all cells, both included
+    pub fn bounds(&self) -> Option<(Pos, Pos)> {
+        let rows = (self.rows.keys().min()?, self.rows.keys().max()?);
+        let cols = (self.cols.keys().min()?, self.cols.keys().max()?);
+        Some(((*rows.0, *cols.0), (*rows.1, *cols.1)))
+    }
+
+    // Number of cells in row `row`
+    pub fn row_count(&self, row: i64) -> usize {
+        self.rows.get(&row).copied().unwrap_or(0)
+    }
+
+    // Number of cells in column `col`
+    pub fn col_count(&self, col: i64) -> usize {
+        self.cols.get(&col).copied().unwrap_or(0)
+    }
+
+    // Rows within the bounds without any cell, top to bottom
+    pub fn empty_rows(&self) -> impl Iterator<Item = i64> + '_ {
+        let rows = self
+            .bounds()
+            .into_iter()
+            .flat_map(|((top, _), (bottom, _))| top..=bottom);
+        rows.filter(|row| !self.rows.contains_key(row))
+    }
+
+    // Columns within the bounds without any cell, left to right
+    pub fn empty_cols(&self) -> impl Iterator<Item = i64> + '_ {
+        let cols = self
+            .bounds()
+            .into_iter()
+            .flat_map(|((_, left), (_, right))| left..=right);
+        cols.filter(|col| !self.cols.contains_key(col))
+    }
+
+    // Positions of the rectangle of the bounds, row by row
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let ((top, left), (bottom, right)) = self.bounds().unwrap_or(((0, 0), (-1, -1)));
+        (top..=bottom).flat_map(move |row| (left..=right).map(move |col| (row, col)))
+    }
+}
+
+// Cells within the bounds, row by row, with `.` for positions without one
+impl<T: fmt::Display> fmt::Display for SparseGrid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some((_, (_, right))) = self.bounds() else {
+            return Ok(());
+        };
+        for pos in self.positions() {
+            match self.get(pos) {
+                Some(cell) => write!(f, "{}", cell)?,
+                None => write!(f, ".")?,
+            }
+            if pos.1 == right {
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sparse_grid() {
+        let mut grid = [((-1, 2), '#'), ((3, 0), '#'), ((3, 4), '#')]
+            .into_iter()
+            .collect::<SparseGrid<_>>();
+        assert_eq!(grid.len(), 3);
+        assert_eq!(grid.bounds(), Some(((-1, 0), (3, 4))));
+        assert_eq!(grid.row_count(3), 2);
+        assert_eq!(grid.col_count(1), 0);
+        assert_eq!(grid.empty_rows().collect::<Vec<_>>(), [0, 1, 2]);
+        assert_eq!(grid.empty_cols().collect::<Vec<_>>(), [1, 3]);
+        assert_eq!(grid.to_string(), "..#..\n.....\n.....\n.....\n#...#\n");
+
+        assert_eq!(grid.insert((3, 0), '@'), Some('#'));
+        assert_eq!(grid.row_count(3), 2);
+        assert_eq!(grid.remove((-1, 2)), Some('#'));
+        assert_eq!(grid.remove((-1, 2)), None);
+        assert_eq!(grid.bounds(), Some(((3, 0), (3, 4))));
+        assert_eq!(grid.empty_cols().count(), 3);
+    }
+}
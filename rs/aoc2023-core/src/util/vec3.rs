@@ -0,0 +1,212 @@
+use std::{
+    fmt,
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+};
+
+// Point or vector of the integer 3D lattice
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Vec3 {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+impl Vec3 {
+    pub const ZERO: Vec3 = Vec3::new(0, 0, 0);
+
+    pub const fn new(x: i64, y: i64, z: i64) -> Self {
+        Vec3 { x, y, z }
+    }
+
+    pub fn dot(self, other: Vec3) -> i64 {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    pub fn cross(self, other: Vec3) -> Vec3 {
+        Vec3::new(
+            self.y * other.z - self.z * other.y,
+            self.z * other.x - self.x * other.z,
+            self.x * other.y - self.y * other.x,
+        )
+    }
+
+    // Sum of the absolute values of the components
+    pub fn manhattan(self) -> u64 {
+        self.x.unsigned_abs() + self.y.unsigned_abs() + self.z.unsigned_abs()
+    }
+
+    // Smaller of each component
+    pub fn min(self, other: Vec3) -> Vec3 {
+        Vec3::new(
+            self.x.min(other.x),
+            self.y.min(other.y),
+            self.z.min(other.z),
+        )
+    }
+
+    // Larger of each component
+    pub fn max(self, other: Vec3) -> Vec3 {
+        Vec3::new(
+            self.x.max(other.x),
+            self.y.max(other.y),
+            self.z.max(other.z),
+        )
+    }
+
+    // Sign of each component, e.g. the unit step along an axis
+    pub fn signum(self) -> Vec3 {
+        Vec3::new(self.x.signum(), self.y.signum(), self.z.signum())
+    }
+}
+
+impl From<(i64, i64, i64)> for Vec3 {
+    fn from((x, y, z): (i64, i64, i64)) -> Self {
+        Vec3::new(x, y, z)
+    }
+}
+
+impl From<[i64; 3]> for Vec3 {
+    fn from([x, y, z]: [i64; 3]) -> Self {
+        Vec3::new(x, y, z)
+    }
+}
+
+impl fmt::Display for Vec3 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{},{}", self.x, self.y, self.z)
+    }
+}
+
+impl Add for Vec3 {
+    type Output = Vec3;
+
+    fn add(self, other: Vec3) -> Vec3 {
+        Vec3::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+impl AddAssign for Vec3 {
+    fn add_assign(&mut self, other: Vec3) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Vec3 {
+    type Output = Vec3;
+
+    fn sub(self, other: Vec3) -> Vec3 {
+        Vec3::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+impl SubAssign for Vec3 {
+    fn sub_assign(&mut self, other: Vec3) {
+        *self = *self - other;
+    }
+}
+
+impl Mul<i64> for Vec3 {
+    type Output = Vec3;
+
+    fn mul(self, n: i64) -> Vec3 {
+        Vec3::new(self.x * n, self.y * n, self.z * n)
+    }
+}
+
+impl Neg for Vec3 {
+    type Output = Vec3;
+
+    fn neg(self) -> Vec3 {
+        Vec3::new(-self.x, -self.y, -self.z)
+    }
+}
+
+// Axis-aligned box of the lattice points between two corners, both included,
+// e.g. a brick
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Aabb {
+    pub min: Vec3,
+    pub max: Vec3,
+}
+
+impl Aabb {
+    // Box with the corners `a` and `b`, in any order
+    pub fn new(a: Vec3, b: Vec3) -> Self {
+        Aabb {
+            min: a.min(b),
+            max: a.max(b),
+        }
+    }
+
+    // Size along each axis, in lattice points
+    pub fn size(&self) -> Vec3 {
+        self.max - self.min + Vec3::new(1, 1, 1)
+    }
+
+    // Number of lattice points
+    pub fn volume(&self) -> u64 {
+        let size = self.size();
+        size.x as u64 * size.y as u64 * size.z as u64
+    }
+
+    pub fn contains(&self, p: Vec3) -> bool {
+        self.min.min(p) == self.min && self.max.max(p) == self.max
+    }
+
+    // Whether the boxes share a lattice point
+    pub fn overlaps(&self, other: &Aabb) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    pub fn intersection(&self, other: &Aabb) -> Option<Aabb> {
+        let (min, max) = (self.min.max(other.min), self.max.min(other.max));
+        (min.x <= max.x && min.y <= max.y && min.z <= max.z).then_some(Aabb { min, max })
+    }
+
+    // Box moved by `offset`
+    pub fn shifted(&self, offset: Vec3) -> Aabb {
+        Aabb {
+            min: self.min + offset,
+            max: self.max + offset,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vec3() {
+        let (a, b) = (Vec3::new(1, 2, 3), Vec3::from([-4, 5, 0]));
+        assert_eq!(a + b, Vec3::new(-3, 7, 3));
+        assert_eq!(a - b, Vec3::new(5, -3, 3));
+        assert_eq!(-a * 2, Vec3::new(-2, -4, -6));
+        assert_eq!(a.dot(b), 6);
+        let cross = a.cross(b);
+        assert_eq!(cross, Vec3::new(-15, -12, 13));
+        assert_eq!((cross.dot(a), cross.dot(b)), (0, 0));
+        assert_eq!(b.manhattan(), 9);
+        assert_eq!((b - a).signum(), Vec3::new(-1, 1, -1));
+        assert_eq!(a.to_string(), "1,2,3");
+    }
+
+    #[test]
+    fn test_aabb() {
+        // bricks 1,0,1~1,2,1 and 0,0,2~2,0,2, one above the other
+        let a = Aabb::new(Vec3::new(1, 2, 1), Vec3::new(1, 0, 1));
+        let b = Aabb::new(Vec3::new(0, 0, 2), Vec3::new(2, 0, 2));
+        assert_eq!(a.min, Vec3::new(1, 0, 1));
+        assert_eq!((a.volume(), b.volume()), (3, 3));
+        assert!(!a.overlaps(&b));
+        assert!(a.contains(Vec3::new(1, 1, 1)));
+        assert!(!a.contains(Vec3::new(1, 3, 1)));
+
+        // dropping b by one makes it cross a at 1,0,1
+        let dropped = b.shifted(Vec3::new(0, 0, -1));
+        assert_eq!(
+            dropped.intersection(&a),
+            Some(Aabb::new(Vec3::new(1, 0, 1), Vec3::new(1, 0, 1)))
+        );
+    }
+}
@@ -0,0 +1,68 @@
+use rand::{Rng as _, SeedableRng};
+
+use crate::generate::Rng;
+
+// Zobrist hashing of states of a fixed number of cells, each in one of a few
+// states: a random key for every cell and state, and the hash of a whole
+// state the xor of the keys of its cells. A cell changing state changes the
+// hash by two xors, so simulations fingerprint each step without hashing
+// every cell again.
+#[derive(Debug, Clone)]
+pub struct Zobrist {
+    keys: Vec<u64>,
+    states: usize,
+}
+
+impl Zobrist {
+    // Keys for `cells` cells of `states` states each, the same on every run
+    pub fn new(cells: usize, states: usize) -> Self {
+        let mut rng = Rng::seed_from_u64(0x2023);
+        let keys = (0..cells * states).map(|_| rng.gen()).collect();
+        Zobrist { keys, states }
+    }
+
+    pub fn key(&self, cell: usize, state: usize) -> u64 {
+        self.keys[cell * self.states + state]
+    }
+
+    // Hash of the cells in the given states, from scratch
+    pub fn hash(&self, cells: impl IntoIterator<Item = (usize, usize)>) -> u64 {
+        cells
+            .into_iter()
+            .fold(0, |hash, (cell, state)| hash ^ self.key(cell, state))
+    }
+
+    // Hash with `cell` put in or taken out of `state`
+    pub fn toggle(&self, hash: u64, cell: usize, state: usize) -> u64 {
+        hash ^ self.key(cell, state)
+    }
+
+    // Hash with `cell` changed from state `from` to state `to`
+    pub fn replace(&self, hash: u64, cell: usize, from: usize, to: usize) -> u64 {
+        hash ^ self.key(cell, from) ^ self.key(cell, to)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_incremental_hash() {
+        let zobrist = Zobrist::new(9, 3);
+        let mut cells = [0, 1, 2, 0, 1, 2, 0, 1, 2];
+        let full = |cells: &[usize]| zobrist.hash(cells.iter().copied().enumerate());
+        let mut hash = full(&cells);
+
+        hash = zobrist.replace(hash, 4, cells[4], 0);
+        cells[4] = 0;
+        assert_eq!(hash, full(&cells));
+
+        // changing it back restores the hash
+        hash = zobrist.replace(hash, 4, 0, 1);
+        cells[4] = 1;
+        assert_eq!(hash, full(&cells));
+        assert_eq!(zobrist.toggle(zobrist.toggle(hash, 7, 2), 7, 2), hash);
+        assert_ne!(hash, full(&[0; 9]));
+    }
+}
@@ -0,0 +1,115 @@
+use std::fmt;
+
+// Broken invariant of the input of a day, at a line counted from 1 if it's
+// about a single line, e.g.
+//
+//   line 3: row is 9 tiles wide, not 10 like the first row
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl Violation {
+    pub fn new(message: impl Into<String>) -> Self {
+        Violation {
+            line: None,
+            message: message.into(),
+        }
+    }
+
+    pub fn at(line: usize, message: impl Into<String>) -> Self {
+        Violation {
+            line: Some(line),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {}: {}", line, self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+// Lines of `input` with their numbers, from 1
+pub fn lines(input: &str) -> impl Iterator<Item = (usize, &str)> {
+    input.lines().enumerate().map(|(idx, line)| (idx + 1, line))
+}
+
+// Numbered lines of `input` split into blocks at blank lines
+pub fn blocks(input: &str) -> Vec<Vec<(usize, &str)>> {
+    let mut blocks = vec![vec![]];
+    for (number, line) in lines(input) {
+        if line.trim().is_empty() {
+            blocks.push(vec![]);
+        } else if let Some(block) = blocks.last_mut() {
+            block.push((number, line));
+        }
+    }
+    blocks.retain(|block| !block.is_empty());
+    blocks
+}
+
+// Violations of a grid: a non-empty rectangle of tiles that are all `tile`
+pub fn grid<'a>(
+    rows: impl IntoIterator<Item = (usize, &'a str)>,
+    tile: impl Fn(char) -> bool,
+) -> Vec<Violation> {
+    let mut violations = vec![];
+    let mut width = None;
+    for (number, row) in rows {
+        let len = row.chars().count();
+        match width {
+            None => width = Some(len),
+            Some(width) if width != len => violations.push(Violation::at(
+                number,
+                format!(
+                    "row is {} tiles wide, not {} like the first row",
+                    len, width
+                ),
+            )),
+            Some(_) => {}
+        }
+        if let Some((column, c)) = row.chars().enumerate().find(|&(_, c)| !tile(c)) {
+            violations.push(Violation::at(
+                number,
+                format!("unexpected tile {:?} at column {}", c, column + 1),
+            ));
+        }
+    }
+    if width.is_none_or(|width| width == 0) {
+        violations.push(Violation::new("grid is empty"));
+    }
+    violations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grid() {
+        assert!(grid(lines("#.\n.#\n"), |c| ".#".contains(c)).is_empty());
+
+        let violations = grid(lines("#.\n.#.\n#x"), |c| ".#".contains(c));
+        let violations = violations
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            violations,
+            [
+                "line 2: row is 3 tiles wide, not 2 like the first row",
+                "line 3: unexpected tile 'x' at column 2"
+            ]
+        );
+        assert_eq!(grid(lines(""), |_| true), [Violation::new("grid is empty")]);
+
+        let blocks = blocks("a\nb\n\n\nc\n");
+        assert_eq!(blocks, [vec![(1, "a"), (2, "b")], vec![(5, "c")]]);
+    }
+}
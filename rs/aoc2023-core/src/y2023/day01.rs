@@ -0,0 +1,517 @@
+use core::fmt;
+use std::{
+    cmp::Reverse,
+    sync::{Arc, LazyLock},
+};
+
+use aho_corasick::{AhoCorasick, Match};
+use anyhow::Result;
+use rand::{seq::SliceRandom, Rng as _};
+
+use crate::{
+    error::{AocError, Location},
+    generate::Rng,
+    log,
+    solver::{self, Puzzle},
+    util::bytes,
+    Answer,
+};
+
+/// How digits are spelled in a calibration line
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DigitMode {
+    /// As digits only, e.g. `1`
+    Digits,
+    /// As digits or English words, e.g. `1` or `one`
+    Words,
+}
+
+/// English words of the digits
+pub static ENGLISH: [(u32, &str); 10] = [
+    (0, "zero"),
+    (1, "one"),
+    (2, "two"),
+    (3, "three"),
+    (4, "four"),
+    (5, "five"),
+    (6, "six"),
+    (7, "seven"),
+    (8, "eight"),
+    (9, "nine"),
+];
+
+/// What to make of calibration lines without a digit
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Strictness {
+    /// Fail on the first, pointing at it
+    #[default]
+    Strict,
+    /// Leave them out of the sum, reporting them
+    Lenient,
+}
+
+/// Line of a calibration document without a digit, left out of its sum
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reject {
+    /// Number of the line, from 1
+    pub line: usize,
+    pub content: String,
+}
+
+/// Sum of the values of a calibration document, and the lines left out of
+/// it
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Report {
+    pub sum: u32,
+    pub rejects: Vec<Reject>,
+}
+
+/// Reader of calibration lines, with digits spelled as digits and as the
+/// words of a table, none by default, e.g.
+///
+/// ```text
+/// let calibrator = Calibrator::new()
+///     .words(&[(1, "un"), (2, "deux")])?
+///     .strictness(Strictness::Lenient);
+/// let report = calibrator.report(input)?;
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Calibrator<'w> {
+    // (value, word) of the digits spelled as words
+    words: &'w [(u32, &'w str)],
+    // matcher of the digits and words, if there are words
+    matcher: Option<Matcher>,
+    strictness: Strictness,
+}
+
+impl From<DigitMode> for Calibrator<'static> {
+    fn from(mode: DigitMode) -> Self {
+        static ENGLISH_CALIBRATOR: LazyLock<Calibrator<'static>> =
+            LazyLock::new(|| Calibrator::new().words(&ENGLISH).unwrap());
+        match mode {
+            DigitMode::Digits => Calibrator::new(),
+            DigitMode::Words => ENGLISH_CALIBRATOR.clone(),
+        }
+    }
+}
+
+impl<'w> Calibrator<'w> {
+    /// Calibrator of digits spelled as digits only
+    pub fn new() -> Self {
+        Calibrator::default()
+    }
+
+    /// Digits spelled as `words` too, pairs of a digit and a word of it, e.g.
+    /// `(1, "one")`. Words must not be empty or have digits in them.
+    pub fn words(self, words: &'w [(u32, &'w str)]) -> Result<Self> {
+        for &(value, word) in words {
+            anyhow::ensure!(value <= 9, "{} of '{}' isn't a digit", value, word);
+            anyhow::ensure!(
+                !word.is_empty() && !word.bytes().any(|b| b.is_ascii_digit()),
+                "'{}' isn't a word of a digit",
+                word
+            );
+        }
+        let matcher = (!words.is_empty())
+            .then(|| Matcher::new(words))
+            .transpose()?;
+        Ok(Calibrator {
+            words,
+            matcher,
+            ..self
+        })
+    }
+
+    /// Lines without a digit failing or left out as `strictness` says, by
+    /// `sum` and `report`
+    pub fn strictness(self, strictness: Strictness) -> Self {
+        Calibrator { strictness, ..self }
+    }
+
+    // Digit spelled at the start of `bytes`
+    fn digit_at(&self, bytes: &[u8]) -> Option<u32> {
+        match bytes.first()? {
+            b @ b'0'..=b'9' => Some((b - b'0') as u32),
+            _ => self
+                .words
+                .iter()
+                .find_map(|&(v, word)| bytes.starts_with(word.as_bytes()).then_some(v)),
+        }
+    }
+
+    // First and last digit of a line, if it has any
+    fn ends(&self, line: &[u8]) -> Option<(u32, u32)> {
+        match &self.matcher {
+            Some(matcher) => matcher.ends(line),
+            None => bytes::find_digit(line)
+                .zip(bytes::rfind_digit(line))
+                .map(|(first, last)| ((line[first] - b'0') as u32, (line[last] - b'0') as u32)),
+        }
+    }
+
+    /// Value of a calibration line, from its first and last digits alone
+    pub fn value(&self, line: &[u8]) -> Result<u32> {
+        let Some((first, last)) = self.ends(line) else {
+            anyhow::bail!(
+                "invalid calibration line: '{}'",
+                String::from_utf8_lossy(line)
+            );
+        };
+        let value = first * 10 + last;
+        log::debug!("{} => value = {}", String::from_utf8_lossy(line), value);
+        Ok(value)
+    }
+
+    /// Sum of the values of the lines of the calibration document `input`
+    pub fn sum(&self, input: &str) -> Result<u32> {
+        self.report(input).map(|report| report.sum)
+    }
+
+    /// Sum of the values of the lines of the calibration document `input`,
+    /// with the lines without a digit if they're left out of it. Strictly,
+    /// the first of those fails it with its line and content.
+    pub fn report(&self, input: &str) -> Result<Report> {
+        let mut report = Report::default();
+        for (i, line) in bytes::lines(input.as_bytes()).enumerate() {
+            match (self.ends(line), self.strictness) {
+                (Some((first, last)), _) => report.sum += first * 10 + last,
+                (None, Strictness::Lenient) => {
+                    log::debug!("line {} has no digit", i + 1);
+                    report.rejects.push(Reject {
+                        line: i + 1,
+                        content: String::from_utf8_lossy(line).into_owned(),
+                    });
+                }
+                (None, Strictness::Strict) => Err(AocError::Parse {
+                    day: Solution.day(),
+                    location: Location::new(input.as_bytes(), line),
+                    reason: "no digit in the line".to_string(),
+                })?,
+            }
+        }
+        Ok(report)
+    }
+
+    /// Calibrations of the lines of `s`, with all their digits
+    pub fn calibrations<'a>(&self, s: &'a str) -> Result<Calibrations<'a>> {
+        s.lines()
+            .map(|line| Calibration::new(line, self))
+            .collect::<Result<Vec<_>>>()
+            .map(Calibrations)
+    }
+
+    /// Values of the calibration lines, read one line at a time from its
+    /// ends, without allocating
+    pub fn stream<I, S>(self, lines: I) -> impl Iterator<Item = Result<u32>> + use<'w, I, S>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        lines
+            .into_iter()
+            .map(move |line| self.value(line.as_ref().as_bytes()))
+    }
+}
+
+// Aho-Corasick automaton of the digits and the words of a table, finding
+// the first and last digit of a line in one pass over it, whatever the
+// number of words
+#[derive(Debug, Clone)]
+struct Matcher {
+    automaton: AhoCorasick,
+    // value of each pattern of the automaton, the digits and then the words
+    values: Arc<[u32]>,
+}
+
+impl Matcher {
+    fn new(words: &[(u32, &str)]) -> Result<Self> {
+        let digits = (0..10).map(|d| (d, char::from_digit(d, 10).unwrap().to_string()));
+        let (values, patterns): (Vec<_>, Vec<_>) = digits
+            .chain(words.iter().map(|&(v, word)| (v, word.to_string())))
+            .unzip();
+        Ok(Matcher {
+            automaton: AhoCorasick::new(patterns)?,
+            values: values.into(),
+        })
+    }
+
+    // Values of the first and last digit of `line`, those starting first and
+    // last. All matches are found, overlapping ones included, as a word can
+    // start in another, e.g. "eightwo". Of those starting at the same place,
+    // the first of the table wins, like `Calibrator::digit_at`.
+    fn ends(&self, line: &[u8]) -> Option<(u32, u32)> {
+        let first_key = |m: &Match| (m.start(), m.pattern());
+        let last_key = |m: &Match| (m.start(), Reverse(m.pattern()));
+        let (mut first, mut last) = (None::<Match>, None::<Match>);
+        for m in self.automaton.find_overlapping_iter(line) {
+            if first.is_none_or(|f| first_key(&m) < first_key(&f)) {
+                first = Some(m);
+            }
+            if last.is_none_or(|l| last_key(&m) > last_key(&l)) {
+                last = Some(m);
+            }
+        }
+        let value = |m: Match| self.values[m.pattern().as_usize()];
+        Some((value(first?), value(last?)))
+    }
+}
+
+// Calibration line with all its digits, to show how its value comes about.
+// Values alone are read from the ends of the lines by `Calibrator::value`.
+#[derive(Debug)]
+struct Calibration<'a> {
+    // calibration line
+    line: &'a str,
+    // digits parsed from the calibration line
+    digits: Vec<u32>,
+    // value of the calibration line
+    value: u32,
+}
+
+impl<'a> Calibration<'a> {
+    fn new(line: &'a str, calibrator: &Calibrator<'_>) -> Result<Self> {
+        let bytes = line.as_bytes();
+        let digits = (0..bytes.len())
+            .filter_map(|i| calibrator.digit_at(&bytes[i..]))
+            .collect::<Vec<_>>();
+        let value = match digits.as_slice() {
+            [d] => d * 10 + d,
+            [d1, .., d2] => d1 * 10 + d2,
+            _ => anyhow::bail!("invalid calibration line: '{}'", line),
+        };
+
+        Ok(Calibration {
+            line,
+            digits,
+            value,
+        })
+    }
+}
+
+impl fmt::Display for Calibration<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:80}", self.line)?;
+        write!(f, " => value = {:2}: digits = ", self.value)?;
+        for d in &self.digits {
+            write!(f, "{}, ", d)?;
+        }
+        Ok(())
+    }
+}
+
+/// Calibration lines with all the digits spelled in them, to show how their
+/// values come about
+#[derive(Debug)]
+pub struct Calibrations<'a>(Vec<Calibration<'a>>);
+
+impl<'a> Calibrations<'a> {
+    /// Calibrations of the lines of `s`, with digits spelled as `mode` says
+    pub fn parse(s: &'a str, mode: DigitMode) -> Result<Self> {
+        Calibrator::from(mode).calibrations(s)
+    }
+
+    pub fn sum(&self) -> u32 {
+        self.0.iter().map(|c| c.value).sum()
+    }
+
+    /// Values of the calibration lines, parsed one line at a time
+    pub fn stream(
+        lines: impl IntoIterator<Item = impl AsRef<str>>,
+        mode: DigitMode,
+    ) -> impl Iterator<Item = Result<u32>> {
+        Calibrator::from(mode).stream(lines)
+    }
+}
+
+impl fmt::Display for Calibrations<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for c in &self.0 {
+            writeln!(f, "{}", c)?;
+        }
+        Ok(())
+    }
+}
+
+/// Value of a calibration line, with digits spelled as `mode` says
+pub fn calibration_value(line: &[u8], mode: DigitMode) -> Result<u32> {
+    Calibrator::from(mode).value(line)
+}
+
+/// Sum of the values of the lines of the calibration document `input`, with
+/// digits spelled as `mode` says
+pub fn calibration_sum(input: &str, mode: DigitMode) -> Result<u32> {
+    Calibrator::from(mode).sum(input)
+}
+
+pub struct Solution;
+
+solver::register!(Solution);
+
+impl Puzzle for Solution {
+    // each part reads the digits of the calibration document differently
+    type Input<'a> = &'a str;
+
+    fn year(&self) -> u16 {
+        2023
+    }
+
+    fn day(&self) -> u8 {
+        1
+    }
+
+    fn part1(&self, input: &&str) -> Result<Option<Answer>> {
+        let ans = calibration_sum(input, DigitMode::Digits)?;
+        Ok(Some(ans.into()))
+    }
+
+    fn part2(&self, input: &&str) -> Result<Option<Answer>> {
+        let ans = calibration_sum(input, DigitMode::Words)?;
+        Ok(Some(ans.into()))
+    }
+
+    // Lines of letters, digits and spelled digits, with a digit in every line
+    // so both parts have a value for it
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        const WORDS: [&str; 9] = [
+            "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+        ];
+        let digit = |rng: &mut Rng| char::from(rng.gen_range(b'1'..=b'9'));
+        let lines = (0..1000 * size).map(|_| {
+            let len = rng.gen_range(4..40);
+            let mut line = String::new();
+            while line.len() < len {
+                match rng.gen_range(0..6) {
+                    0 => line.push(digit(rng)),
+                    1 => line.push_str(WORDS.choose(rng).unwrap()),
+                    _ => line.push(char::from(rng.gen_range(b'a'..=b'z'))),
+                }
+            }
+            if !line.bytes().any(|b| b.is_ascii_digit()) {
+                line.push(digit(rng));
+            }
+            line
+        });
+        Some(lines.collect::<Vec<_>>().join("\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::{memory, InputStore, Part};
+
+    #[test]
+    fn test_with_sample() -> Result<()> {
+        let input = &InputStore::default().sample_part(2023, 1, Part::One)?;
+        let calibrations = Calibrations::parse(input, DigitMode::Digits)?;
+        assert_eq!(calibrations.sum(), 142);
+        let values =
+            Calibrations::stream(input.lines(), DigitMode::Digits).collect::<Result<Vec<_>>>()?;
+        assert_eq!(values, [12, 38, 15, 77]);
+        assert_eq!(calibration_sum(input, DigitMode::Digits)?, 142);
+
+        let input = &InputStore::default().sample_part(2023, 1, Part::Two)?;
+        let calibrations = Calibrations::parse(input, DigitMode::Words)?;
+        assert_eq!(calibrations.sum(), 281);
+        assert_eq!(part2(&parse(input)?)?, Some(Answer::from(281)));
+        let values = Calibrations::stream(input.lines().map(String::from), DigitMode::Words);
+        assert_eq!(values.sum::<Result<u32>>()?, 281);
+        assert_eq!(calibration_sum(input, DigitMode::Words)?, 281);
+
+        Ok(())
+    }
+
+    // Values from the ends of the lines are those of all their digits
+    #[test]
+    fn test_value_against_calibrations() -> Result<()> {
+        let input = Solution.generate(&mut Rng::seed_from_u64(1), 1).unwrap();
+        for line in
+            input
+                .lines()
+                .chain(["eightwo", "1", "twone3oneight", "nineight7sevenine", "one"])
+        {
+            for mode in [DigitMode::Digits, DigitMode::Words] {
+                let value = Calibrations::stream([line], mode).next().unwrap();
+                let fast = calibration_value(line.as_bytes(), mode);
+                assert_eq!(fast.ok(), value.ok(), "{} {:?}", line, mode);
+            }
+        }
+        assert!(calibration_value(b"one", DigitMode::Digits).is_err());
+        assert!(calibration_value(b"abc", DigitMode::Words).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_stream_without_allocating() -> Result<()> {
+        let input = Solution.generate(&mut Rng::seed_from_u64(2), 1).unwrap();
+        let expected = Calibrations::parse(&input, DigitMode::Words)?.sum();
+        let calibrator = Calibrator::from(DigitMode::Words);
+        let (sum, allocs) =
+            memory::measure(|| calibrator.stream(input.lines()).sum::<Result<u32>>());
+        assert_eq!(sum?, expected);
+        assert_eq!(allocs.map(|allocs| allocs.allocations), Some(0));
+        Ok(())
+    }
+
+    #[test]
+    fn test_strictness() -> Result<()> {
+        let input = "1abc2\nabc\ntwo3\n\nxyz7";
+        let err = calibration_sum(input, DigitMode::Digits).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "day 01: failed to parse input (no digit in the line) at line 2, column 1: `abc`"
+        );
+
+        let calibrator = Calibrator::from(DigitMode::Digits).strictness(Strictness::Lenient);
+        let report = calibrator.report(input)?;
+        assert_eq!(report.sum, 12 + 33 + 77);
+        let rejects = report
+            .rejects
+            .iter()
+            .map(|r| (r.line, r.content.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(rejects, [(2, "abc"), (4, "")]);
+
+        let calibrator = Calibrator::from(DigitMode::Words).strictness(Strictness::Lenient);
+        assert_eq!(calibrator.report(input)?.rejects.len(), 2);
+        assert_eq!(calibrator.sum("one\nabc")?, 11);
+        Ok(())
+    }
+
+    #[test]
+    fn test_word_tables() -> Result<()> {
+        static FRENCH: [(u32, &str); 3] = [(1, "un"), (2, "deux"), (3, "trois")];
+        let calibrator = Calibrator::new().words(&FRENCH)?;
+        assert_eq!(calibrator.value(b"xdeuxa7bunz")?, 21);
+        assert_eq!(calibrator.sum("trois\nun2one")?, 33 + 12);
+        let values = calibrator
+            .stream(["deuxtrois"])
+            .collect::<Result<Vec<_>>>()?;
+        assert_eq!(values, [23]);
+
+        // extended with a synonym of zero
+        let words = ENGLISH
+            .iter()
+            .copied()
+            .chain([(0, "nought")])
+            .collect::<Vec<_>>();
+        let calibrator = Calibrator::new().words(&words)?;
+        assert_eq!(calibrator.value(b"noughtxsix")?, 6);
+        assert_eq!(calibrator.calibrations("nought9")?.sum(), 9);
+
+        // words in words, and words starting alike, the first in the table
+        // winning
+        static NESTED: [(u32, &str); 4] = [(0, "none"), (1, "one"), (2, "on"), (3, "ne")];
+        let calibrator = Calibrator::new().words(&NESTED)?;
+        for line in ["none", "xonx", "nonex", "neon", "7nonne", "onone"] {
+            let expected = calibrator.calibrations(line)?.sum();
+            assert_eq!(calibrator.value(line.as_bytes())?, expected, "{}", line);
+        }
+
+        assert!(Calibrator::new().words(&[(10, "ten")]).is_err());
+        assert!(Calibrator::new().words(&[(1, "1st")]).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,540 @@
+use anyhow::Result;
+use core::fmt;
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{digit1, space1},
+    combinator::{all_consuming, map_res},
+    multi::separated_list1,
+    sequence::tuple,
+    IResult,
+};
+
+use rand::{seq::SliceRandom, Rng as _};
+
+use crate::{
+    error,
+    explain::Artifact,
+    generate::Rng,
+    log,
+    solver::{self, FromInput, Puzzle},
+    Answer, FromParams, Params,
+};
+
+// Bag of part 1, unless configured otherwise
+const BAG: Cubes = Cubes {
+    red: 12,
+    green: 13,
+    blue: 14,
+};
+
+/// Color of cubes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    Red,
+    Green,
+    Blue,
+}
+
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Color::Red => write!(f, "red"),
+            Color::Green => write!(f, "green"),
+            Color::Blue => write!(f, "blue"),
+        }
+    }
+}
+
+/// Cubes of each color, e.g. drawn from the bag in a round of a game
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Cubes {
+    pub red: usize,
+    pub green: usize,
+    pub blue: usize,
+}
+
+impl fmt::Display for Cubes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Cubes { red, green, blue } = self;
+        write!(f, "{} red, {} green, {} blue", red, green, blue)
+    }
+}
+
+impl Cubes {
+    pub fn new(red: usize, green: usize, blue: usize) -> Self {
+        Cubes { red, green, blue }
+    }
+
+    /// Number of cubes of `color`
+    pub fn get(&self, color: Color) -> usize {
+        match color {
+            Color::Red => self.red,
+            Color::Green => self.green,
+            Color::Blue => self.blue,
+        }
+    }
+
+    // The same cubes, but `num` of `color`
+    fn with(mut self, color: Color, num: usize) -> Self {
+        match color {
+            Color::Red => self.red = num,
+            Color::Green => self.green = num,
+            Color::Blue => self.blue = num,
+        }
+        self
+    }
+
+    /// Product of the numbers of cubes of each color
+    pub fn power(&self) -> usize {
+        self.red * self.green * self.blue
+    }
+
+    /// Whether there are no more cubes of any color than in `bag`
+    pub fn fits_in(&self, bag: &Cubes) -> bool {
+        self.red <= bag.red && self.green <= bag.green && self.blue <= bag.blue
+    }
+
+    /// Most cubes of each color of the two
+    pub fn max(&self, other: &Cubes) -> Cubes {
+        Cubes::new(
+            self.red.max(other.red),
+            self.green.max(other.green),
+            self.blue.max(other.blue),
+        )
+    }
+}
+
+/// Draw of a game with more cubes of a color than the bag holds
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Overdraw {
+    /// Index of the draw in the game, from 0
+    pub draw: usize,
+    pub color: Color,
+    /// Cubes of the color drawn
+    pub drawn: usize,
+    /// Cubes of the color in the bag
+    pub allowed: usize,
+}
+
+impl fmt::Display for Overdraw {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "draw {} has {} {}, the bag holds {}",
+            self.draw + 1,
+            self.drawn,
+            self.color,
+            self.allowed
+        )
+    }
+}
+
+/// Game of rounds of cubes drawn from the bag
+#[derive(Debug)]
+pub struct Game {
+    id: usize,
+    rounds: Vec<Cubes>,
+}
+
+impl fmt::Display for Game {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Game { id, rounds } = self;
+        write!(f, "Game {}: ", id)?;
+        for round in rounds {
+            write!(f, "{}; ", round)?;
+        }
+        Ok(())
+    }
+}
+
+impl Game {
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    /// Cubes drawn in each round, in order
+    pub fn draws(&self) -> impl ExactSizeIterator<Item = &Cubes> {
+        self.rounds.iter()
+    }
+
+    /// Whether `bag` holds enough cubes of every color for each round
+    pub fn is_possible(&self, bag: &Cubes) -> bool {
+        self.first_overdraw(bag).is_none()
+    }
+
+    /// First draw with more cubes of a color than `bag` holds, and of which
+    /// color, the first of red, green and blue if several, if there's one
+    pub fn first_overdraw(&self, bag: &Cubes) -> Option<Overdraw> {
+        self.draws().enumerate().find_map(|(i, draw)| {
+            [Color::Red, Color::Green, Color::Blue]
+                .into_iter()
+                .find(|&color| draw.get(color) > bag.get(color))
+                .map(|color| Overdraw {
+                    draw: i,
+                    color,
+                    drawn: draw.get(color),
+                    allowed: bag.get(color),
+                })
+        })
+    }
+
+    /// Fewest cubes of each color the game can be played with
+    pub fn min_bag(&self) -> Cubes {
+        self.draws()
+            .fold(Cubes::default(), |bag, draw| bag.max(draw))
+    }
+
+    /// Power of the fewest cubes of each color the game can be played with
+    pub fn power(&self) -> usize {
+        self.min_bag().power()
+    }
+}
+
+/// Games of cubes drawn from a bag, and the bag of part 1
+#[derive(Debug)]
+pub struct Games {
+    games: Vec<Game>,
+    bag: Cubes,
+}
+
+impl fmt::Display for Games {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for game in &self.games {
+            writeln!(f, "{}", game)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromInput<'_> for Games {
+    fn from_input(s: &str) -> Result<Self> {
+        let games = error::parse_lines(Solution.day(), s, parse_game)?;
+        Ok(Games { games, bag: BAG })
+    }
+}
+
+impl FromIterator<Game> for Games {
+    fn from_iter<I: IntoIterator<Item = Game>>(iter: I) -> Self {
+        Games {
+            games: iter.into_iter().collect(),
+            bag: BAG,
+        }
+    }
+}
+
+impl Games {
+    /// Games parsed one line at a time
+    pub fn stream(
+        lines: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> impl Iterator<Item = Result<Game>> {
+        lines.into_iter().enumerate().map(|(idx, line)| {
+            Ok(error::parse_line(
+                Solution.day(),
+                idx + 1,
+                line.as_ref(),
+                parse_game,
+            )?)
+        })
+    }
+
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = &Game> {
+        self.games.iter()
+    }
+
+    /// Bag the games of part 1 are played with
+    pub fn bag(&self) -> Cubes {
+        self.bag
+    }
+
+    /// The same games, played with `bag` in part 1
+    pub fn with_bag(self, bag: Cubes) -> Self {
+        Games { bag, ..self }
+    }
+
+    /// Games that `pred` holds for
+    pub fn filter<'a>(
+        &'a self,
+        pred: impl Fn(&Game) -> bool + 'a,
+    ) -> impl Iterator<Item = &'a Game> + 'a {
+        self.iter().filter(move |game| pred(game))
+    }
+
+    /// Games that `pred` holds for every draw of, e.g. the games possible
+    /// with a bag of cubes
+    pub fn with_all_draws<'a>(
+        &'a self,
+        pred: impl Fn(&Cubes) -> bool + 'a,
+    ) -> impl Iterator<Item = &'a Game> + 'a {
+        self.filter(move |game| game.draws().all(&pred))
+    }
+
+    /// Fewest cubes of each color each game can be played with, by game id
+    pub fn min_bags(&self) -> impl Iterator<Item = (usize, Cubes)> + '_ {
+        self.iter().map(|game| (game.id(), game.min_bag()))
+    }
+
+    fn sum_of_possible_game_ids(&self) -> usize {
+        self.iter()
+            .filter(|game| game.is_possible(&self.bag))
+            .map(Game::id)
+            .sum()
+    }
+
+    fn sum_of_power(&self) -> usize {
+        self.iter().map(Game::power).sum()
+    }
+}
+
+fn parse_game(input: &str) -> IResult<&str, Game> {
+    let (input, (_, id, _, rounds)) = tuple((
+        tag("Game "),
+        parse_usize,
+        tag(": "),
+        separated_list1(tag("; "), parse_rounds),
+    ))(input)?;
+    Ok((input, Game { id, rounds }))
+}
+
+fn parse_usize(input: &str) -> IResult<&str, usize> {
+    map_res(digit1, str::parse::<usize>)(input)
+}
+
+fn parse_rounds(input: &str) -> IResult<&str, Cubes> {
+    parse_cubes(Cubes::default())(input)
+}
+
+// Cubes of the colors listed, e.g. "3 blue, 4 red", and of `rest` for the
+// others
+fn parse_cubes(rest: Cubes) -> impl FnMut(&str) -> IResult<&str, Cubes> {
+    move |input| {
+        let (input, colors) = separated_list1(tag(", "), parse_color)(input)?;
+        let cubes = colors
+            .into_iter()
+            .fold(rest, |cubes, (num, color)| cubes.with(color, num));
+        Ok((input, cubes))
+    }
+}
+
+fn parse_color(input: &str) -> IResult<&str, (usize, Color)> {
+    let (input, (num, _, color)) = tuple((
+        parse_usize,
+        space1,
+        alt((tag("red"), tag("green"), tag("blue"))),
+    ))(input)?;
+    let color = match color {
+        "red" => Color::Red,
+        "green" => Color::Green,
+        _ => Color::Blue,
+    };
+    Ok((input, (num, color)))
+}
+
+/// Options of the puzzle, set from parameters
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+    /// Bag of part 1, from `bag`, e.g. "20 red, 13 green", with the usual
+    /// number of cubes of the colors left out
+    pub bag: Cubes,
+}
+
+impl FromParams for Options {
+    const KEYS: &'static [&'static str] = &["bag"];
+
+    fn defaults() -> Self {
+        Options { bag: BAG }
+    }
+
+    fn set(&mut self, key: &str, value: &str) -> Result<()> {
+        match key {
+            "bag" => {
+                let (_, bag) = all_consuming(parse_cubes(BAG))(value)
+                    .map_err(|_| anyhow::anyhow!("expected cubes like \"20 red, 13 green\""))?;
+                self.bag = bag;
+            }
+            _ => anyhow::bail!("unknown option {}", key),
+        }
+        Ok(())
+    }
+}
+
+pub struct Solution;
+
+solver::register!(Solution);
+
+impl Puzzle for Solution {
+    type Input<'a> = Games;
+
+    fn year(&self) -> u16 {
+        2023
+    }
+
+    fn day(&self) -> u8 {
+        2
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Self::Input<'a>> {
+        let games = Games::from_input(input)?;
+        log::debug!("games: \n{}", games);
+        Ok(games)
+    }
+
+    fn configure(&self, games: &mut Games, params: &Params) -> Result<()> {
+        let options = params.options::<Options>(self.day())?;
+        games.bag = options.bag;
+        log::debug!("bag: {}", games.bag);
+        Ok(())
+    }
+
+    fn part1(&self, games: &Games) -> Result<Option<Answer>> {
+        let part1 = games.sum_of_possible_game_ids();
+        Ok(Some(part1.into()))
+    }
+
+    fn part2(&self, games: &Games) -> Result<Option<Answer>> {
+        let part2 = games.sum_of_power();
+        Ok(Some(part2.into()))
+    }
+
+    fn explain(&self, games: &Games) -> Result<Vec<Artifact>> {
+        let impossible = games
+            .iter()
+            .filter_map(|game| Some((game.id(), game.first_overdraw(&games.bag)?)))
+            .map(|(id, overdraw)| format!("Game {}: {}", id, overdraw))
+            .collect::<Vec<_>>();
+        Ok(vec![Artifact::new(
+            format!("impossible games ({})", impossible.len()),
+            impossible,
+        )])
+    }
+
+    // Games of a few rounds, each drawing up to 20 cubes of some colors, so
+    // about half the games fit the bag
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        let games = (1..=100 * size).map(|id| {
+            let rounds = (0..rng.gen_range(1..=6))
+                .map(|_| {
+                    let mut colors = ["red", "green", "blue"];
+                    colors.shuffle(rng);
+                    colors[..rng.gen_range(1..=3)]
+                        .iter()
+                        .map(|color| format!("{} {}", rng.gen_range(1..=20), color))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                })
+                .collect::<Vec<_>>();
+            format!("Game {}: {}", id, rounds.join("; "))
+        });
+        Some(games.collect::<Vec<_>>().join("\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{InputStore, Solver};
+
+    #[test]
+    fn test_with_sample() -> Result<()> {
+        let input = &InputStore::default().sample(2023, 2)?;
+        let games = input
+            .lines()
+            .map(parse_game)
+            .map(|res| res.map(|(_, game)| game))
+            .collect::<Result<Games, _>>()
+            .map_err(|e| e.to_owned())?;
+        assert_eq!(games.sum_of_possible_game_ids(), 8);
+        assert_eq!(games.sum_of_power(), 2286);
+
+        let power = Games::stream(input.lines()).map(|game| game.map(|game| game.power()));
+        assert_eq!(power.sum::<Result<usize>>()?, 2286);
+        let err = Games::stream(["Game 1: 3 blue", "Game 2: 3 blu"])
+            .nth(1)
+            .unwrap();
+        assert!(err.unwrap_err().to_string().contains("line 2"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_overdraws() -> Result<()> {
+        let input = &InputStore::default().sample(2023, 2)?;
+        let games = Games::from_input(input)?;
+        let overdraws = games
+            .iter()
+            .filter_map(|game| game.first_overdraw(&BAG))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            overdraws,
+            [
+                Overdraw {
+                    draw: 0,
+                    color: Color::Red,
+                    drawn: 20,
+                    allowed: 12
+                },
+                Overdraw {
+                    draw: 2,
+                    color: Color::Red,
+                    drawn: 14,
+                    allowed: 12
+                },
+            ]
+        );
+        let explained = Solution.explain(&games)?;
+        assert_eq!(
+            explained[0].lines,
+            [
+                "Game 3: draw 1 has 20 red, the bag holds 12",
+                "Game 4: draw 3 has 14 red, the bag holds 12"
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_queries() -> Result<()> {
+        let input = &InputStore::default().sample(2023, 2)?;
+        let games = Games::from_input(input)?;
+        assert_eq!(games.iter().len(), 5);
+
+        let game = games.iter().next().unwrap();
+        assert_eq!(game.draws().len(), 3);
+        assert_eq!(game.draws().next(), Some(&Cubes::new(4, 0, 3)));
+        assert_eq!(game.min_bag(), Cubes::new(4, 2, 6));
+        assert_eq!(
+            games.min_bags().map(|(_, bag)| bag.power()).sum::<usize>(),
+            games.sum_of_power()
+        );
+
+        // possible games are those with all draws fitting the bag
+        let bag = Cubes::new(12, 13, 14);
+        let possible = games.with_all_draws(|draw| draw.fits_in(&bag));
+        assert_eq!(possible.map(Game::id).collect::<Vec<_>>(), [1, 2, 5]);
+        let blue = games.filter(|game| game.draws().any(|draw| draw.blue > 5));
+        assert_eq!(blue.map(Game::id).collect::<Vec<_>>(), [1, 3, 4]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_configured_bag() -> Result<()> {
+        let input = &InputStore::default().sample(2023, 2)?;
+        let games = Games::from_input(input)?;
+        assert_eq!(games.bag(), BAG);
+        let games = games.with_bag(Cubes::new(20, 13, 14));
+        assert_eq!(games.sum_of_possible_game_ids(), 11);
+
+        // colors left out of the parameter keep their usual number
+        let run = |params: &Params| -> Result<Option<Answer>> {
+            Solver::parse_with(&Solution, input, params)?.part1()
+        };
+        assert_eq!(run(&Params::new())?, Some(8.into()));
+        assert_eq!(run(&Params::new().with("bag", "20 red"))?, Some(11.into()));
+        assert_eq!(
+            run(&Params::new().with("bag", "4 red, 6 blue"))?,
+            Some(3.into())
+        );
+        assert!(run(&Params::new().with("bag", "20 purple")).is_err());
+        assert!(run(&Params::new().with("bags", "20 red")).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,717 @@
+use std::{
+    borrow::Borrow,
+    fmt,
+    hash::Hash,
+    ops::{Add, Range},
+    str::FromStr,
+};
+
+use anyhow::Result;
+use nom::{
+    branch::alt,
+    character::complete::{anychar, char, digit1},
+    combinator::{map, map_res},
+    multi::many1,
+    IResult,
+};
+use rand::{seq::SliceRandom, Rng as _};
+
+use crate::{
+    error::{self, AocError, Location},
+    generate::{self, Rng},
+    log,
+    solver::{self, FromInput, Puzzle},
+    util::grid::Grid,
+    validate::{self, Violation},
+    Answer, FromParams, Params,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Pos(isize, isize);
+
+impl fmt::Display for Pos {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Pos(x, y) = self;
+        write!(f, "({}, {})", x, y)
+    }
+}
+
+impl<T> Add<T> for Pos
+where
+    T: Borrow<Pos>,
+{
+    type Output = Pos;
+
+    fn add(self, other: T) -> Self::Output {
+        let Pos(x1, y1) = self;
+        let Pos(x2, y2) = other.borrow();
+        Pos(x1 + x2, y1 + y2)
+    }
+}
+
+impl<T> Add<T> for &Pos
+where
+    T: Borrow<Pos>,
+{
+    type Output = Pos;
+
+    fn add(self, other: T) -> Self::Output {
+        let Pos(x1, y1) = self;
+        let Pos(x2, y2) = other.borrow();
+        Pos(x1 + x2, y1 + y2)
+    }
+}
+
+impl Pos {
+    const NEIGHBORS: [Pos; 8] = [
+        Pos(0, 1),
+        Pos(1, 1),
+        Pos(1, 0),
+        Pos(1, -1),
+        Pos(0, -1),
+        Pos(-1, -1),
+        Pos(-1, 0),
+        Pos(-1, 1),
+    ];
+
+    fn new(x: usize, y: usize) -> Self {
+        Pos(x as isize, y as isize)
+    }
+
+    // Neighbors of self along x-axis and y-axis, and diagonals if 8-way;
+    // every other one of NEIGHBORS is a diagonal
+    fn neighbors(self, adjacency: Adjacency) -> impl Iterator<Item = Pos> {
+        let step = match adjacency {
+            Adjacency::Eight => 1,
+            Adjacency::Four => 2,
+        };
+        Pos::NEIGHBORS.iter().step_by(step).map(move |p| self + p)
+    }
+}
+
+/// Cells next to a cell: 8-way including diagonals, as in the puzzle, or
+/// 4-way without
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Adjacency {
+    #[default]
+    Eight,
+    Four,
+}
+
+// Adjacency as the number of neighbors, "8" or "4"
+impl FromStr for Adjacency {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "8" => Ok(Adjacency::Eight),
+            "4" => Ok(Adjacency::Four),
+            _ => anyhow::bail!("expected 8 or 4"),
+        }
+    }
+}
+
+/// Cell of the engine schematic; a number spans `len` columns
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Cell {
+    Number { num: usize, len: usize },
+    Dot,
+    Symbol(char),
+}
+
+impl Cell {
+    // Columns the cell covers
+    fn width(&self) -> usize {
+        match self {
+            Cell::Number { len, .. } => *len,
+            Cell::Dot | Cell::Symbol(_) => 1,
+        }
+    }
+}
+
+impl fmt::Display for Cell {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Cell::Number { num, .. } => write!(f, "{}", num),
+            Cell::Dot => write!(f, "."),
+            Cell::Symbol(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+/// Columns of a row a cell covers, which tell apart numbers of the same
+/// value
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Span {
+    pub row: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    pub fn cols(&self) -> Range<usize> {
+        self.start..self.end
+    }
+}
+
+/// Number of the schematic, in the columns `cols` of `row`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NumberSpan {
+    pub value: usize,
+    pub row: usize,
+    pub cols: Range<usize>,
+}
+
+// What covers a cell of the schematic: a number, by its index into the
+// engine's numbers, or a dot or symbol; or nothing, past the end of a
+// shorter line
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Slot {
+    Off,
+    Dot,
+    Number(u32),
+    Symbol(char),
+}
+
+/// Engine schematic of numbers and symbols, and what makes part numbers and
+/// gears of them
+#[derive(Debug)]
+pub struct Engine {
+    grid: Vec<Vec<Cell>>,
+    // each number once, with its span
+    numbers: Vec<(Span, usize)>,
+    // what covers each cell, row by row
+    index: Grid<Slot>,
+    adjacency: Adjacency,
+    // characters that are symbols, or any but digits and dots if none
+    symbols: Option<Vec<char>>,
+    // symbol of gears, and how many numbers they're next to
+    gear: (char, usize),
+}
+
+// A schematic is a rectangle, of columns of characters rather than bytes,
+// so a symbol may be any character. Lines of another width than the first
+// are an error, rather than the cells after them being misplaced.
+impl FromInput<'_> for Engine {
+    fn from_input(s: &str) -> Result<Self> {
+        let grid = error::parse_lines(Solution.day(), s, parse_cells)?;
+        let width = |cells: &Vec<Cell>| cells.iter().map(Cell::width).sum::<usize>();
+        let first = grid.first().map_or(0, width);
+        if let Some((line, cols)) = s
+            .lines()
+            .zip(grid.iter().map(width))
+            .find(|&(_, cols)| cols != first)
+        {
+            // at the column past the first line's width, or past the end
+            let at = line
+                .char_indices()
+                .nth(first)
+                .map_or(line.len(), |(i, _)| i);
+            Err(AocError::Parse {
+                day: Solution.day(),
+                location: Location::new(s.as_bytes(), &line.as_bytes()[at..]),
+                reason: format!("line of {} columns, the first has {}", cols, first),
+            })?;
+        }
+        Ok(Engine::new(grid))
+    }
+}
+
+impl fmt::Display for Engine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for cells in self.grid.iter() {
+            for cell in cells.iter() {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl Engine {
+    /// Engine of the rows of cells of a schematic; shorter rows are padded
+    /// with cells of nothing, neither dots nor symbols
+    pub fn new(grid: Vec<Vec<Cell>>) -> Self {
+        let width = |cells: &Vec<Cell>| cells.iter().map(Cell::width).sum::<usize>();
+        let cols = grid.iter().map(width).max().unwrap_or(0);
+        let mut index = Grid::filled(grid.len(), cols, Slot::Off);
+        let mut numbers = vec![];
+
+        for (row, cells) in grid.iter().enumerate() {
+            let mut col = 0;
+            for &cell in cells.iter() {
+                let span = Span {
+                    row,
+                    start: col,
+                    end: col + cell.width(),
+                };
+                let slot = match cell {
+                    Cell::Number { num, .. } => {
+                        numbers.push((span, num));
+                        Slot::Number(numbers.len() as u32 - 1)
+                    }
+                    Cell::Dot => Slot::Dot,
+                    Cell::Symbol(symbol) => Slot::Symbol(symbol),
+                };
+                for col in span.cols() {
+                    index[(row, col)] = slot;
+                }
+                col = span.end;
+            }
+        }
+        Engine {
+            grid,
+            numbers,
+            index,
+            adjacency: Adjacency::default(),
+            symbols: None,
+            gear: ('*', 2),
+        }
+    }
+
+    /// The same schematic with numbers next to cells `adjacency` away
+    pub fn adjacency(self, adjacency: Adjacency) -> Self {
+        Engine { adjacency, ..self }
+    }
+
+    /// The same schematic with only the characters of `symbols` as symbols,
+    /// the others like dots
+    pub fn symbol_set(self, symbols: &str) -> Self {
+        let symbols = Some(symbols.chars().collect());
+        Engine { symbols, ..self }
+    }
+
+    /// The same schematic with gears of `symbol` next to exactly `arity`
+    /// numbers
+    pub fn gear(self, symbol: char, arity: usize) -> Self {
+        let gear = (symbol, arity);
+        Engine { gear, ..self }
+    }
+
+    /// Cells of the schematic with the (row, column) each starts at
+    pub fn cells(&self) -> impl Iterator<Item = ((usize, usize), Cell)> + '_ {
+        self.grid.iter().enumerate().flat_map(|(row, cells)| {
+            cells.iter().scan(0, move |col, &cell| {
+                let start = *col;
+                *col += cell.width();
+                Some(((row, start), cell))
+            })
+        })
+    }
+
+    /// Cell at (`row`, `col`), which a number spans all the columns of
+    pub fn cell(&self, row: usize, col: usize) -> Option<Cell> {
+        match self.slot(Pos::new(row, col)) {
+            Slot::Off => None,
+            Slot::Dot => Some(Cell::Dot),
+            Slot::Number(id) => {
+                let (span, num) = self.numbers[id as usize];
+                let len = span.end - span.start;
+                Some(Cell::Number { num, len })
+            }
+            Slot::Symbol(symbol) => Some(Cell::Symbol(symbol)),
+        }
+    }
+
+    /// Span of the cell at (`row`, `col`)
+    pub fn span(&self, row: usize, col: usize) -> Option<Span> {
+        match self.slot(Pos::new(row, col)) {
+            Slot::Off => None,
+            Slot::Number(id) => Some(self.numbers[id as usize].0),
+            Slot::Dot | Slot::Symbol(_) => Some(Span {
+                row,
+                start: col,
+                end: col + 1,
+            }),
+        }
+    }
+
+    /// Numbers with their spans, row by row
+    pub fn numbers(&self) -> impl ExactSizeIterator<Item = NumberSpan> + '_ {
+        self.numbers.iter().map(|&(span, value)| NumberSpan {
+            value,
+            row: span.row,
+            cols: span.cols(),
+        })
+    }
+
+    /// Symbols with their (row, column), row by row
+    pub fn symbols(&self) -> impl Iterator<Item = (char, (usize, usize))> + '_ {
+        self.cells().filter_map(|(pos, cell)| match cell {
+            Cell::Symbol(symbol) if self.is_symbol(symbol) => Some((symbol, pos)),
+            _ => None,
+        })
+    }
+
+    /// Part numbers, i.e. numbers next to a symbol
+    pub fn part_numbers(&self) -> Vec<usize> {
+        let is_symbol = |p| matches!(self.slot(p), Slot::Symbol(c) if self.is_symbol(c));
+        self.numbers
+            .iter()
+            .filter(|(span, _)| match self.adjacency {
+                Adjacency::Eight => {
+                    // the number's cells and the ring of cells around it
+                    let Pos(x, y) = Pos::new(span.row, span.start);
+                    let len = span.end - span.start;
+                    (x - 1..=x + 1)
+                        .flat_map(|x| (y - 1..=y + len as isize).map(move |y| Pos(x, y)))
+                        .any(is_symbol)
+                }
+                Adjacency::Four => span
+                    .cols()
+                    .flat_map(|col| Pos::new(span.row, col).neighbors(Adjacency::Four))
+                    .any(is_symbol),
+            })
+            .map(|&(_, num)| num)
+            .collect()
+    }
+
+    /// Numbers next to the cell at (`row`, `col`), e.g. a symbol, each once
+    /// however many of its digits are; numbers of the same value at
+    /// different spans are different numbers
+    pub fn numbers_adjacent_to(&self, (row, col): (usize, usize)) -> Vec<usize> {
+        let mut ids = vec![];
+        for p in Pos::new(row, col).neighbors(self.adjacency) {
+            if let Slot::Number(id) = self.slot(p) {
+                if !ids.contains(&id) {
+                    ids.push(id);
+                }
+            }
+        }
+        ids.into_iter()
+            .map(|id| self.numbers[id as usize].1)
+            .collect()
+    }
+
+    /// Numbers of each gear, i.e. `*` next to exactly two numbers unless
+    /// configured otherwise
+    pub fn gears(&self) -> Vec<Vec<usize>> {
+        let (gear, arity) = self.gear;
+        self.symbols()
+            .filter(|&(symbol, _)| symbol == gear)
+            .map(|(_, pos)| self.numbers_adjacent_to(pos))
+            .filter(|nums| nums.len() == arity)
+            .collect()
+    }
+
+    /// Sum of the part numbers
+    pub fn sum_of_parts(&self) -> usize {
+        self.part_numbers().iter().sum()
+    }
+
+    /// Sum of the products of the numbers of each gear
+    pub fn sum_of_gear_ratios(&self) -> usize {
+        self.gears()
+            .into_iter()
+            .map(|nums| nums.into_iter().product::<usize>())
+            .sum()
+    }
+
+    fn is_symbol(&self, c: char) -> bool {
+        self.symbols
+            .as_ref()
+            .is_none_or(|symbols| symbols.contains(&c))
+    }
+
+    fn slot(&self, Pos(x, y): Pos) -> Slot {
+        if x < 0 || y < 0 {
+            return Slot::Off;
+        }
+        let slot = self.index.get((x as usize, y as usize));
+        slot.copied().unwrap_or(Slot::Off)
+    }
+}
+
+fn parse_number(input: &str) -> IResult<&str, (usize, usize)> {
+    let len = input.len();
+    let (input, num) = map_res(digit1, |d: &str| d.parse::<usize>())(input)?;
+    Ok((input, (num, len - input.len())))
+}
+
+fn parse_cell(input: &str) -> IResult<&str, Cell> {
+    alt((
+        map(parse_number, |(num, len)| Cell::Number { num, len }),
+        map(char('.'), |_| Cell::Dot),
+        map(anychar, Cell::Symbol),
+    ))(input)
+}
+
+fn parse_cells(input: &str) -> IResult<&str, Vec<Cell>> {
+    many1(parse_cell)(input)
+}
+
+/// Options of the puzzle, set from parameters
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Options {
+    /// Cells next to a cell, from `adjacency`
+    pub adjacency: Adjacency,
+    /// Characters that are symbols, from `symbols`, or any but digits and
+    /// dots
+    pub symbols: Option<String>,
+    /// Symbol of gears, from `gear`
+    pub gear: char,
+    /// Number of part numbers next to a gear, from `arity`
+    pub arity: usize,
+}
+
+impl FromParams for Options {
+    const KEYS: &'static [&'static str] = &["adjacency", "symbols", "gear", "arity"];
+
+    fn defaults() -> Self {
+        Options {
+            adjacency: Adjacency::Eight,
+            symbols: None,
+            gear: '*',
+            arity: 2,
+        }
+    }
+
+    fn set(&mut self, key: &str, value: &str) -> Result<()> {
+        match key {
+            "adjacency" => self.adjacency = value.parse()?,
+            "symbols" => self.symbols = Some(value.to_string()),
+            "gear" => self.gear = value.parse()?,
+            "arity" => self.arity = value.parse()?,
+            _ => anyhow::bail!("unknown option {}", key),
+        }
+        Ok(())
+    }
+}
+
+pub struct Solution;
+
+solver::register!(Solution);
+
+impl Puzzle for Solution {
+    type Input<'a> = Engine;
+
+    fn year(&self) -> u16 {
+        2023
+    }
+
+    fn day(&self) -> u8 {
+        3
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Self::Input<'a>> {
+        let engine = Engine::from_input(input)?;
+        log::debug!("engine:\n{}", engine);
+        Ok(engine)
+    }
+
+    fn configure(&self, engine: &mut Engine, params: &Params) -> Result<()> {
+        let options = params.options::<Options>(self.day())?;
+        engine.adjacency = options.adjacency;
+        engine.symbols = options.symbols.map(|symbols| symbols.chars().collect());
+        engine.gear = (options.gear, options.arity);
+        Ok(())
+    }
+
+    fn part1(&self, engine: &Engine) -> Result<Option<Answer>> {
+        let parts = engine.part_numbers();
+        log::debug!("parts: {:?}", parts);
+        let part1 = parts.iter().sum::<usize>();
+        Ok(Some(part1.into()))
+    }
+
+    fn part2(&self, engine: &Engine) -> Result<Option<Answer>> {
+        let gears = engine.gears();
+        log::debug!("gears: {:?}", gears);
+        let part2 = gears
+            .into_iter()
+            .map(|nums| nums.into_iter().product::<usize>())
+            .sum::<usize>();
+        Ok(Some(part2.into()))
+    }
+
+    // The schematic is a rectangle of digits, symbols and dots, where symbols
+    // are any other characters but whitespace, e.g. `€`
+    fn validate(&self, input: &str) -> Vec<Violation> {
+        validate::grid(validate::lines(input), |c| !c.is_whitespace())
+    }
+
+    // Numbers of up to 3 digits and symbols scattered over dots, with a dot
+    // or symbol between any two numbers on a row. Larger schematics have
+    // more rows of the same width.
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        const SIZE: usize = 140;
+        let rows = (0..SIZE * size)
+            .map(|_| {
+                let mut row = vec![];
+                while row.len() < SIZE {
+                    let number = rng.gen_range(1..1000).to_string();
+                    if rng.gen_bool(0.15) && row.len() + number.len() < SIZE {
+                        row.extend(number.bytes());
+                        row.push(b'.');
+                    } else if rng.gen_bool(0.05) {
+                        row.push(*b"*#+$/@%=&-".choose(rng).unwrap());
+                    } else {
+                        row.push(b'.');
+                    }
+                }
+                row
+            })
+            .collect::<Vec<_>>();
+        Some(generate::grid(&rows))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::InputStore;
+
+    #[test]
+    fn test_with_sample() -> Result<()> {
+        let input = &InputStore::default().sample(2023, 3)?;
+        assert!(Solution.validate(input).is_empty());
+        let engine = Engine::from_input(input)?;
+        let part1 = engine.sum_of_parts();
+        assert_eq!(part1, 4361);
+
+        let part2 = engine.sum_of_gear_ratios();
+        assert_eq!(part2, 467835);
+        Ok(())
+    }
+
+    #[test]
+    fn test_queries() -> Result<()> {
+        let input = &InputStore::default().sample(2023, 3)?;
+        let engine = Engine::from_input(input)?;
+        assert_eq!(
+            engine.part_numbers(),
+            [467, 35, 633, 617, 592, 755, 664, 598]
+        );
+        assert_eq!(
+            engine.symbols().collect::<Vec<_>>(),
+            [
+                ('*', (1, 3)),
+                ('#', (3, 6)),
+                ('*', (4, 3)),
+                ('+', (5, 5)),
+                ('$', (8, 3)),
+                ('*', (8, 5))
+            ]
+        );
+        assert_eq!(engine.numbers_adjacent_to((1, 3)), [35, 467]);
+        assert_eq!(engine.numbers_adjacent_to((4, 3)), [617]);
+        assert_eq!(engine.gears(), [vec![35, 467], vec![598, 755]]);
+
+        // a number spans all its columns
+        let number = Cell::Number { num: 467, len: 3 };
+        assert_eq!(engine.cell(0, 0), Some(number));
+        assert_eq!(engine.cell(0, 2), Some(number));
+        assert_eq!(engine.cell(0, 3), Some(Cell::Dot));
+        assert_eq!(engine.cell(10, 0), None);
+        Ok(())
+    }
+
+    // A gear between two numbers of the same value, which are two numbers
+    // all the same
+    #[test]
+    fn test_gear_of_equal_numbers() -> Result<()> {
+        let engine = Engine::from_input("..117..\n...*...\n..117..\n.......\n117*117")?;
+        assert_eq!(engine.numbers_adjacent_to((1, 3)), [117, 117]);
+        assert_eq!(engine.gears(), [vec![117, 117], vec![117, 117]]);
+        assert_eq!(engine.sum_of_gear_ratios(), 2 * 117 * 117);
+        assert_eq!(
+            engine.span(0, 3),
+            Some(Span {
+                row: 0,
+                start: 2,
+                end: 5
+            })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_numbers() -> Result<()> {
+        let input = &InputStore::default().sample(2023, 3)?;
+        let engine = Engine::from_input(input)?;
+        assert_eq!(engine.numbers().len(), 10);
+        assert_eq!(
+            engine.numbers().nth(1),
+            Some(NumberSpan {
+                value: 114,
+                row: 0,
+                cols: 5..8
+            })
+        );
+
+        // numbers right below a symbol, beyond what the puzzle asks
+        let below = engine
+            .numbers()
+            .filter(|number| {
+                engine
+                    .symbols()
+                    .any(|(_, (row, col))| row + 1 == number.row && number.cols.contains(&col))
+            })
+            .map(|number| number.value)
+            .collect::<Vec<_>>();
+        assert_eq!(below, [35, 664, 598]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_configured() -> Result<()> {
+        let input = &InputStore::default().sample(2023, 3)?;
+        let engine = Engine::from_input(input)?.adjacency(Adjacency::Four);
+        // 467, 592 and 755 only touch their symbols diagonally
+        assert_eq!(engine.part_numbers(), [35, 633, 617, 664, 598]);
+        assert_eq!(engine.numbers_adjacent_to((1, 3)), [35]);
+
+        let engine = Engine::from_input(input)?.symbol_set("*");
+        assert_eq!(engine.part_numbers(), [467, 35, 617, 755, 598]);
+        assert_eq!(engine.symbols().count(), 3);
+
+        let engine = Engine::from_input(input)?.gear('*', 1);
+        assert_eq!(engine.gears(), [vec![617]]);
+        let engine = Engine::from_input(input)?.gear('#', 1);
+        assert_eq!(engine.gears(), [vec![633]]);
+
+        let run = |params: &Params| -> Result<Option<Answer>> {
+            crate::Solver::parse_with(&Solution, input, params)?.part2()
+        };
+        let params = Params::new().with("gear", "+").with("arity", "1");
+        let options = params.options::<Options>(3)?;
+        assert_eq!(
+            (options.adjacency, options.gear, options.arity),
+            (Adjacency::Eight, '+', 1)
+        );
+        assert_eq!(run(&params)?, Some(592.into()));
+        assert!(run(&Params::new().with("adjacency", "6")).is_err());
+        assert!(run(&Params::new().with("gear", "**")).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_ragged_and_unicode() -> Result<()> {
+        // symbols are characters, a column each, whatever their bytes
+        assert!(Solution.validate("1€2.\n.→.3").is_empty());
+        assert!(!Solution.validate("1 2.\n.→.3").is_empty());
+        let engine = Engine::from_input("1€2.\n.→.3")?;
+        assert_eq!(
+            engine.symbols().collect::<Vec<_>>(),
+            [('€', (0, 1)), ('→', (1, 1))]
+        );
+        assert_eq!(engine.part_numbers(), [1, 2]);
+        assert_eq!(engine.cell(1, 3), Some(Cell::Number { num: 3, len: 1 }));
+
+        let err = Engine::from_input("467..\n.*\n..35.").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "day 03: failed to parse input (line of 2 columns, the first has 5) \
+             at line 2, column 3: end of line"
+        );
+        let err = Engine::from_input("4€7\n.→*.").unwrap_err();
+        assert!(err.to_string().contains("at line 2, column 4: `.`"));
+
+        // rows given directly are padded
+        let engine = Engine::new(vec![vec![Cell::Number { num: 7, len: 1 }], vec![]]);
+        assert_eq!(engine.cell(1, 0), None);
+        assert!(engine.part_numbers().is_empty());
+        Ok(())
+    }
+}
@@ -0,0 +1,539 @@
+use std::{collections::VecDeque, fmt, str::FromStr};
+
+use anyhow::Result;
+use itertools::Itertools;
+use nom::{
+    bytes::complete::tag,
+    character::complete::{char, digit1, space0, space1},
+    combinator::{map_res, verify},
+    multi::fold_many1,
+    sequence::{preceded, separated_pair, terminated, tuple},
+    IResult,
+};
+use rand::{seq::SliceRandom, Rng as _};
+
+use crate::{
+    error,
+    explain::Artifact,
+    generate::Rng,
+    log,
+    solver::{self, FromInput, Puzzle},
+    Answer, FromParams, Params,
+};
+
+/// Rule of the points of a card by its matching numbers, none scoring none
+#[derive(Debug, Clone, Copy, Default)]
+pub enum Scoring {
+    /// 1 point for the first match, doubled for each further one
+    #[default]
+    Doubling,
+    /// 1 point per match
+    Linear,
+    /// 1 point for the first match, times the base for each further one
+    Base(usize),
+    /// Points of the number of matches, at least one
+    Custom(fn(usize) -> usize),
+}
+
+impl Scoring {
+    /// Points of a card with `matching` matching numbers
+    pub fn points(&self, matching: usize) -> usize {
+        match (self, matching) {
+            (_, 0) => 0,
+            (Scoring::Doubling, n) => Scoring::Base(2).points(n),
+            (Scoring::Linear, n) => n,
+            (&Scoring::Base(base), n) => base.pow(n as u32 - 1),
+            (Scoring::Custom(points), n) => points(n),
+        }
+    }
+}
+
+// A scoring rule as "doubling", "linear" or "base N"
+impl FromStr for Scoring {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.split_whitespace().collect::<Vec<_>>()[..] {
+            ["doubling"] => Ok(Scoring::Doubling),
+            ["linear"] => Ok(Scoring::Linear),
+            ["base", base] => Ok(Scoring::Base(base.parse()?)),
+            _ => anyhow::bail!("expected doubling, linear or base N"),
+        }
+    }
+}
+
+// Numbers on cards are below this, so a set of them fits in a u128
+const MAX_NUMBER: usize = 128;
+
+// Set of numbers below MAX_NUMBER, a bit each
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct Numbers(u128);
+
+impl Numbers {
+    fn with(self, n: usize) -> Numbers {
+        Numbers(self.0 | 1 << n)
+    }
+
+    fn intersection(self, other: Numbers) -> Numbers {
+        Numbers(self.0 & other.0)
+    }
+
+    fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    // The numbers in ascending order
+    fn iter(self) -> impl Iterator<Item = usize> {
+        (0..MAX_NUMBER).filter(move |&n| self.0 & 1 << n != 0)
+    }
+}
+
+/// Every scratchcard, has a
+/// - unique id
+/// - set of winning numbers
+/// - set of numbers I have
+#[derive(Debug, Clone)]
+pub struct Card {
+    id: usize,
+    winning_numbers: Numbers,
+    my_numbers: Numbers,
+}
+
+impl fmt::Display for Card {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let winning_numbers = self
+            .winning_numbers
+            .iter()
+            .map(|n| format!("{:>3}", n))
+            .join(" ");
+        let my_numbers = self
+            .my_numbers
+            .iter()
+            .map(|n| format!("{:>3}", n))
+            .join(" ");
+        write!(
+            f,
+            "Card {:3}: {} | {}",
+            self.id, winning_numbers, my_numbers
+        )
+    }
+}
+
+impl Card {
+    // Numbers both winning and mine, in ascending order
+    fn matching(&self) -> Vec<usize> {
+        self.winning_numbers
+            .intersection(self.my_numbers)
+            .iter()
+            .collect()
+    }
+
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    /// Number of my numbers that are winning numbers
+    pub fn num_matching(&self) -> usize {
+        self.winning_numbers.intersection(self.my_numbers).len()
+    }
+
+    /// Points of the card by `scoring`
+    pub fn points(&self, scoring: Scoring) -> usize {
+        scoring.points(self.num_matching())
+    }
+}
+
+/// Answers of both parts of a game
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Totals {
+    pub points: usize,
+    pub scratchcards: usize,
+}
+
+/// Game is a collection of scratchcards, and the scoring of part 1
+#[derive(Debug, Clone)]
+pub struct Game {
+    cards: Vec<Card>,
+    scoring: Scoring,
+}
+
+impl fmt::Display for Game {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for card in self.cards.iter() {
+            writeln!(f, "{}", card)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromInput<'_> for Game {
+    fn from_input(s: &str) -> Result<Self> {
+        let cards = error::parse_lines(Solution.day(), s, parse_card)?;
+        Ok(Game {
+            cards,
+            scoring: Scoring::default(),
+        })
+    }
+}
+
+impl Game {
+    /// Cards parsed one line at a time
+    pub fn stream(
+        lines: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> impl Iterator<Item = Result<Card>> {
+        lines.into_iter().enumerate().map(|(idx, line)| {
+            Ok(error::parse_line(
+                Solution.day(),
+                idx + 1,
+                line.as_ref(),
+                parse_card,
+            )?)
+        })
+    }
+
+    /// Copies of each card, including the original, when playing the cards
+    /// parsed one line at a time
+    pub fn stream_copies(
+        lines: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> impl Iterator<Item = Result<usize>> {
+        Game::stream_played(lines).map(|played| played.map(|(_, copies)| copies))
+    }
+
+    /// Points of the cards by `scoring` and scratchcards at the end of the
+    /// game, of the cards parsed one line at a time, for card lists of any
+    /// length
+    pub fn stream_totals(
+        lines: impl IntoIterator<Item = impl AsRef<str>>,
+        scoring: Scoring,
+    ) -> Result<Totals> {
+        Game::stream_played(lines).try_fold(Totals::default(), |totals, played| {
+            let (card, copies) = played?;
+            Ok(Totals {
+                points: totals.points + card.points(scoring),
+                scratchcards: totals.scratchcards + copies,
+            })
+        })
+    }
+
+    // Each card parsed one line at a time, with its copies. A card only wins
+    // copies of the cards right after it, so only the copies won of the
+    // next few cards are kept, as many as the most matching numbers of a
+    // card: memory is bounded by that rather than by the number of cards.
+    fn stream_played(
+        lines: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> impl Iterator<Item = Result<(Card, usize)>> {
+        let mut won = VecDeque::new();
+        Game::stream(lines).map(move |card| {
+            let card = card?;
+            let num_matching = card.num_matching();
+            let copies = 1 + won.pop_front().unwrap_or(0);
+            if won.len() < num_matching {
+                won.resize(num_matching, 0);
+            }
+            for next in won.iter_mut().take(num_matching) {
+                *next += copies;
+            }
+            Ok((card, copies))
+        })
+    }
+
+    fn matching(&self) -> Vec<Vec<usize>> {
+        self.cards.iter().map(Card::matching).collect::<Vec<_>>()
+    }
+
+    /// Points of all cards by `scoring`
+    pub fn points(&self, scoring: Scoring) -> usize {
+        self.cards.iter().map(|card| card.points(scoring)).sum()
+    }
+
+    /// Copies of each card, including the original, when playing the game.
+    /// They're counted apart from the cards, which are left as parsed, so
+    /// the game can be played again or shared.
+    pub fn copies(&self) -> Vec<usize> {
+        let mut copies = vec![1; self.cards.len()];
+        for (card_idx, card) in self.cards.iter().enumerate() {
+            let num_matching = card.num_matching();
+            for next_idx in (card_idx + 1..copies.len()).take(num_matching) {
+                copies[next_idx] += copies[card_idx];
+            }
+        }
+        copies
+    }
+
+    /// Ids of the cards each card wins copies of, by the card's id: the
+    /// cards right after it, one per matching number, as far as there are
+    /// cards. Copies only ever go to later cards, so these are the edges of
+    /// a DAG in card order, which the copies cascade down.
+    pub fn wins(&self) -> Vec<(usize, Vec<usize>)> {
+        self.cards
+            .iter()
+            .enumerate()
+            .map(|(idx, card)| {
+                let won = self.cards[idx + 1..]
+                    .iter()
+                    .take(card.num_matching())
+                    .map(Card::id)
+                    .collect();
+                (card.id(), won)
+            })
+            .collect()
+    }
+
+    /// Scratchcards, originals and copies, at the end of the game
+    pub fn total_scratchcards(&self) -> usize {
+        let copies = self.copies();
+        log::debug!("copies of cards playing the game: {:?}", copies);
+        copies.iter().sum()
+    }
+}
+fn parse_number(input: &str) -> IResult<&str, usize> {
+    map_res(digit1, |s: &str| s.parse::<usize>())(input)
+}
+
+fn parse_numbers(input: &str) -> IResult<&str, Numbers> {
+    let small = verify(parse_number, |&n| n < MAX_NUMBER);
+    fold_many1(preceded(space0, small), Numbers::default, |numbers, n| {
+        numbers.with(n)
+    })(input)
+}
+
+fn parse_card(input: &str) -> IResult<&str, Card> {
+    let (input, (_, _, id, _, (winning_numbers, my_numbers))) = tuple((
+        tag("Card"),
+        space1,
+        parse_number,
+        char(':'),
+        separated_pair(
+            terminated(parse_numbers, space0),
+            char('|'),
+            terminated(parse_numbers, space0),
+        ),
+    ))(input)?;
+    let card = Card {
+        id,
+        winning_numbers,
+        my_numbers,
+    };
+    Ok((input, card))
+}
+
+/// Options of the puzzle, set from parameters
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Options {
+    /// Points of the cards, from `scoring`, e.g. "linear" or "base 3"
+    pub scoring: Scoring,
+}
+
+impl FromParams for Options {
+    const KEYS: &'static [&'static str] = &["scoring"];
+
+    fn defaults() -> Self {
+        Options::default()
+    }
+
+    fn set(&mut self, key: &str, value: &str) -> Result<()> {
+        match key {
+            "scoring" => self.scoring = value.parse()?,
+            _ => anyhow::bail!("unknown option {}", key),
+        }
+        Ok(())
+    }
+}
+
+pub struct Solution;
+
+solver::register!(Solution);
+
+impl Puzzle for Solution {
+    type Input<'a> = Game;
+
+    fn year(&self) -> u16 {
+        2023
+    }
+
+    fn day(&self) -> u8 {
+        4
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Self::Input<'a>> {
+        let game = Game::from_input(input)?;
+        log::debug!("games:\n{}", game);
+        for (i, numbers) in game.matching().iter().enumerate() {
+            log::debug!("Matching numbers in card {}: {:?}", i + 1, numbers);
+        }
+        Ok(game)
+    }
+
+    fn configure(&self, game: &mut Game, params: &Params) -> Result<()> {
+        game.scoring = params.options::<Options>(self.day())?.scoring;
+        Ok(())
+    }
+
+    fn part1(&self, game: &Game) -> Result<Option<Answer>> {
+        let part1 = game.points(game.scoring);
+        Ok(Some(part1.into()))
+    }
+
+    fn part2(&self, game: &Game) -> Result<Option<Answer>> {
+        let part2 = game.total_scratchcards();
+        Ok(Some(part2.into()))
+    }
+
+    fn explain(&self, game: &Game) -> Result<Vec<Artifact>> {
+        let lines = game
+            .wins()
+            .into_iter()
+            .zip(game.copies())
+            .map(|((id, won), copies)| {
+                let won = if won.is_empty() {
+                    "none".to_string()
+                } else {
+                    won.iter().join(", ")
+                };
+                format!("Card {:>3}: {:>8} copies, wins {}", id, copies, won)
+            })
+            .collect::<Vec<_>>();
+        Ok(vec![Artifact::new(
+            format!("cards won ({})", lines.len()),
+            lines,
+        )])
+    }
+
+    // Cards of 10 winning numbers and 25 numbers I have, below 100. Most
+    // cards match none, so the copies won don't grow out of bounds, and no
+    // card wins copies of cards past the last.
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        let cards = 200 * size;
+        let numbers = |numbers: &[usize]| numbers.iter().map(|n| format!(" {:>2}", n)).join("");
+        let cards = (1..=cards).map(|id| {
+            let mut all = (1..100).collect::<Vec<_>>();
+            all.shuffle(rng);
+            let (winning, rest) = all.split_at(10);
+            let matching = if rng.gen_bool(0.7) {
+                0
+            } else {
+                rng.gen_range(1..=5).min(cards - id)
+            };
+            let mut mine = [&winning[..matching], &rest[..25 - matching]].concat();
+            mine.shuffle(rng);
+            format!("Card {:>3}:{} |{}", id, numbers(winning), numbers(&mine))
+        });
+        Some(cards.collect::<Vec<_>>().join("\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::{memory, InputStore};
+
+    #[test]
+    fn test_with_sample() -> Result<()> {
+        let input = &InputStore::default().sample(2023, 4)?;
+        let game = Game::from_input(input)?;
+
+        let part1 = game.points(Scoring::Doubling);
+        assert_eq!(part1, 13);
+
+        let part2 = game.total_scratchcards();
+        assert_eq!(part2, 30);
+        assert_eq!(game.total_scratchcards(), part2);
+        assert_eq!(game.copies(), [1, 2, 4, 8, 14, 1]);
+
+        let points =
+            Game::stream(input.lines()).map(|card| card.map(|card| card.points(Scoring::Doubling)));
+        assert_eq!(points.sum::<Result<usize>>()?, part1);
+        let copies = Game::stream_copies(input.lines()).collect::<Result<Vec<_>>>()?;
+        assert_eq!(copies, [1, 2, 4, 8, 14, 1]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_scoring() -> Result<()> {
+        let input = &InputStore::default().sample(2023, 4)?;
+        let game = Game::from_input(input)?;
+        // cards match 4, 2, 2, 1, 0 and 0 numbers
+        assert_eq!(game.points(Scoring::Linear), 9);
+        assert_eq!(game.points(Scoring::Base(3)), 27 + 3 + 3 + 1);
+        assert_eq!(game.points(Scoring::Base(2)), 13);
+        assert_eq!(game.points(Scoring::Custom(|n| n * n)), 16 + 4 + 4 + 1);
+
+        assert_eq!("base 3".parse::<Scoring>()?.points(2), 3);
+        assert!("base".parse::<Scoring>().is_err());
+        assert!("quadratic".parse::<Scoring>().is_err());
+
+        let params = Params::new().with("scoring", "linear");
+        let parsed = crate::Solver::parse_with(&Solution, input, &params)?;
+        assert_eq!(parsed.part1()?, Some(9.into()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_numbers() -> Result<()> {
+        let card = Game::stream(["Card 1: 0 127 5 | 5 127 3 5"])
+            .next()
+            .unwrap()?;
+        assert_eq!(card.matching(), [5, 127]);
+        assert_eq!(card.num_matching(), 2);
+        assert_eq!(card.to_string(), "Card   1:   0   5 127 |   3   5 127");
+
+        let err = Game::stream(["Card 1: 1 128 | 1"]).next().unwrap();
+        assert!(err.unwrap_err().to_string().contains("column 11"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_wins() -> Result<()> {
+        let input = &InputStore::default().sample(2023, 4)?;
+        let game = Game::from_input(input)?;
+        assert_eq!(
+            game.wins(),
+            [
+                (1, vec![2, 3, 4, 5]),
+                (2, vec![3, 4]),
+                (3, vec![4, 5]),
+                (4, vec![5]),
+                (5, vec![]),
+                (6, vec![])
+            ]
+        );
+        // the last cards win as many as there are after them
+        let game = Game::from_input("Card 1: 1 2 | 1 2\nCard 2: 1 2 | 1 2")?;
+        assert_eq!(game.wins(), [(1, vec![2]), (2, vec![])]);
+
+        let explained = Solution.explain(&game)?;
+        assert_eq!(
+            explained[0].lines,
+            [
+                "Card   1:        1 copies, wins 2",
+                "Card   2:        2 copies, wins none"
+            ]
+        );
+        Ok(())
+    }
+
+    // Memory of streaming is bounded by the copies kept of the next few
+    // cards, however many cards there are
+    #[test]
+    fn test_stream_totals() -> Result<()> {
+        let mut rng = Rng::seed_from_u64(4);
+        for size in [1, 20] {
+            let input = Solution.generate(&mut rng, size).unwrap();
+            let game = Game::from_input(&input)?;
+            let (totals, allocs) =
+                memory::measure(|| Game::stream_totals(input.lines(), Scoring::Doubling));
+            assert_eq!(
+                totals?,
+                Totals {
+                    points: game.points(Scoring::Doubling),
+                    scratchcards: game.total_scratchcards()
+                }
+            );
+            assert!(allocs.is_some_and(|allocs| allocs.peak_bytes <= 256));
+        }
+        Ok(())
+    }
+}
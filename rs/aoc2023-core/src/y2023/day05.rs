@@ -0,0 +1,1008 @@
+use core::{fmt, iter, ops, str::FromStr};
+
+use anyhow::Result;
+
+use nom::{
+    bytes::complete::tag,
+    character::complete::{alpha1, digit1, newline, space1},
+    combinator::map_res,
+    error::ErrorKind,
+    multi::separated_list1,
+    sequence::{pair, tuple},
+    IResult,
+};
+use rand::{seq::SliceRandom, Rng as _};
+
+use crate::{
+    error::{self, AocError, Location},
+    generate::Rng,
+    log, par,
+    solver::{self, FromInput, Puzzle},
+    util::interval::{Interval, IntervalSet, Mapping},
+    validate::{self, Violation},
+    Answer, Artifact, FromParams, Params,
+};
+
+#[derive(Debug)]
+struct Seeds(Vec<usize>);
+
+impl fmt::Display for Seeds {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} seeds with {} 2-sized chunks",
+            self.0.len(),
+            self.0.len() / 2
+        )?;
+
+        for (i, chunk) in self.0.chunks_exact(2).enumerate() {
+            let start = chunk[0];
+            let len = chunk[1];
+            writeln!(
+                f,
+                "{} seed range: [{}] ({:10}, {:10}) ",
+                i,
+                len,
+                start,
+                start + len
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Mapping of the `len` numbers from `src` on to the ones from `dst` on
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Range {
+    pub src: usize,
+    pub dst: usize,
+    pub len: usize,
+}
+
+impl fmt::Display for Range {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            " [{:10}] ({:10} {:10}) -> {:10}",
+            self.len,
+            self.src,
+            self.src + self.len,
+            self.dst
+        )
+    }
+}
+
+impl Range {
+    pub fn new(dst: usize, src: usize, len: usize) -> Self {
+        Self { src, dst, len }
+    }
+
+    /// Whether `key` is one of the source numbers
+    pub fn contains(&self, key: &usize) -> bool {
+        let lb = self.src;
+        let ub = self.src + self.len;
+        (lb..ub).contains(key)
+    }
+
+    /// Destination number of `key`, which must be one of the source numbers
+    pub fn map(&self, key: &usize) -> usize {
+        assert!(self.contains(key), "key must be in range");
+        self.dst + (key - self.src)
+    }
+}
+
+/// Mapping of one category to the next, e.g. seed to soil. Numbers outside
+/// all ranges map to themselves.
+#[derive(Debug, Clone)]
+pub struct Map {
+    ranges: Vec<Range>,
+    mapping: Mapping,
+    // numbers where the destinations of the numbers from there on change
+    // how they map back, sorted: where ranges of sources or destinations
+    // start or end
+    boundaries: Vec<usize>,
+}
+
+impl fmt::Display for Map {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for range in &self.ranges {
+            writeln!(f, "{}", range)?;
+        }
+        Ok(())
+    }
+}
+
+impl Map {
+    pub fn new(mut ranges: Vec<Range>) -> Self {
+        ranges.sort();
+        let mapping = Mapping::new(
+            ranges
+                .iter()
+                .map(|range| (Interval::with_len(range.src, range.len), range.dst)),
+        );
+        let mut boundaries = ranges
+            .iter()
+            .flat_map(|range| {
+                [
+                    range.src,
+                    range.src + range.len,
+                    range.dst,
+                    range.dst + range.len,
+                ]
+            })
+            .collect::<Vec<_>>();
+        boundaries.sort();
+        boundaries.dedup();
+        Self {
+            ranges,
+            mapping,
+            boundaries,
+        }
+    }
+
+    /// Ranges of the map, sorted by source
+    pub fn ranges(&self) -> &[Range] {
+        &self.ranges
+    }
+
+    pub fn map(&self, key: usize) -> usize {
+        self.mapping.map(key)
+    }
+
+    /// Mapping of the ranges as intervals, shared with other puzzles
+    pub fn mapping(&self) -> &Mapping {
+        &self.mapping
+    }
+
+    /// Ranges the numbers of `range` map to, sorted and merged
+    pub fn map_interval(&self, range: ops::Range<usize>) -> Vec<ops::Range<usize>> {
+        self.map_ranges(&[range])
+    }
+
+    /// Map of the numbers through this map and then `next`
+    pub fn then(&self, next: &Map) -> Map {
+        let ranges = self
+            .mapping
+            .then(&next.mapping)
+            .shifts()
+            .iter()
+            .map(|&(src, dst)| Range::new(dst, src.start, src.len()))
+            .collect();
+        Map::new(ranges)
+    }
+
+    /// Numbers that map to `value`: those of the ranges with `value` as a
+    /// destination, and `value` itself unless a range maps it elsewhere
+    pub fn preimages(&self, value: usize) -> impl Iterator<Item = usize> + '_ {
+        let unmapped = !self.ranges.iter().any(|range| range.contains(&value));
+        self.ranges
+            .iter()
+            .filter(move |range| (range.dst..range.dst + range.len).contains(&value))
+            .map(move |range| range.src + (value - range.dst))
+            .chain(unmapped.then_some(value))
+    }
+
+    // Numbers from `value` on that map back alike, each preimage of one
+    // shifted by as much as the number, up to the next boundary
+    fn run(&self, value: usize) -> usize {
+        let next = self.boundaries.partition_point(|&b| b <= value);
+        self.boundaries
+            .get(next)
+            .map_or(usize::MAX - value, |&b| b - value)
+    }
+
+    /// Ranges the numbers of `keys` map to, sorted and merged
+    pub fn map_ranges(&self, keys: &[ops::Range<usize>]) -> Vec<ops::Range<usize>> {
+        let keys = keys.iter().cloned().collect::<IntervalSet>();
+        self.mapping
+            .map_set(&keys)
+            .intervals()
+            .iter()
+            .map(|interval| interval.start..interval.end)
+            .collect()
+    }
+}
+
+/// Maps from seeds to locations, applied one after the other, and the
+/// categories they lead through, e.g. seed, soil, ..., location
+#[derive(Debug, Clone)]
+pub struct Maps {
+    maps: Vec<Map>,
+    // names of the categories, one more than the maps, or none if unnamed
+    categories: Vec<String>,
+}
+
+impl Maps {
+    /// Maps of unnamed categories
+    pub fn new(maps: Vec<Map>) -> Self {
+        Maps {
+            maps,
+            categories: vec![],
+        }
+    }
+
+    /// Maps leading through `categories`, one more than the maps
+    pub fn named(categories: Vec<String>, maps: Vec<Map>) -> Result<Self> {
+        anyhow::ensure!(
+            categories.len() == maps.len() + 1,
+            "{} maps lead through {} categories, not {}",
+            maps.len(),
+            maps.len() + 1,
+            categories.len()
+        );
+        Ok(Maps { maps, categories })
+    }
+
+    /// Names of the categories, from the first map's source to the last
+    /// map's destination, if they're named
+    pub fn categories(&self) -> &[String] {
+        &self.categories
+    }
+
+    /// Map from the category `from` to the next
+    pub fn get(&self, from: &str) -> Option<&Map> {
+        let idx = self.categories.iter().position(|c| c == from)?;
+        self.maps.get(idx)
+    }
+
+    pub fn maps(&self) -> &[Map] {
+        &self.maps
+    }
+
+    /// Location of seed `key`
+    pub fn map(&self, key: usize) -> usize {
+        // map through all maps in order
+        self.maps.iter().fold(key, |acc, map| map.map(acc))
+    }
+
+    /// Ranges of locations the seeds of `range` map to, sorted and merged
+    pub fn map_interval(&self, range: ops::Range<usize>) -> Vec<ops::Range<usize>> {
+        self.map_ranges(&[range])
+    }
+
+    /// All maps as one, from seeds straight to locations
+    pub fn compose(&self) -> Map {
+        self.maps
+            .iter()
+            .fold(Map::new(vec![]), |composed, map| composed.then(map))
+    }
+
+    /// Seeds that map to `location`, sorted
+    pub fn preimages(&self, location: usize) -> Vec<usize> {
+        let mut seeds = self.maps.iter().rev().fold(vec![location], |values, map| {
+            values
+                .into_iter()
+                .flat_map(|value| map.preimages(value))
+                .collect()
+        });
+        seeds.sort();
+        seeds
+    }
+
+    /// Ranges of locations the seeds of `keys` map to, sorted and merged
+    pub fn map_ranges(&self, keys: &[ops::Range<usize>]) -> Vec<ops::Range<usize>> {
+        self.map_set(keys.iter().cloned().collect())
+            .intervals()
+            .iter()
+            .map(|interval| interval.start..interval.end)
+            .collect()
+    }
+
+    // Seeds of `keys` pushed through the maps as intervals, each split
+    // wherever a map's ranges start or end, so every piece is shifted as
+    // a whole
+    fn map_set(&self, keys: IntervalSet) -> IntervalSet {
+        self.maps
+            .iter()
+            .fold(keys, |keys, map| map.mapping.map_set(&keys))
+    }
+
+    // Lowest location of the seeds of `seeds`, the start of the lowest
+    // interval they map to, unless there are none
+    fn min(&self, seeds: ops::Range<usize>) -> Option<usize> {
+        self.map_set([seeds].into_iter().collect()).min()
+    }
+}
+
+/// How part 2 finds the lowest location of the seed ranges, either way to
+/// the same answer
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Strategy {
+    /// Push the seed ranges forward through the maps
+    #[default]
+    Intervals,
+    /// Scan locations upward for one with a seed in the seed ranges
+    Scan,
+}
+
+// Strategy by name, "intervals" or "scan"
+impl FromStr for Strategy {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "intervals" => Ok(Strategy::Intervals),
+            "scan" => Ok(Strategy::Scan),
+            _ => anyhow::bail!("expected intervals or scan"),
+        }
+    }
+}
+
+/// Seeds and the maps of their categories
+#[derive(Debug)]
+pub struct Almanac {
+    seeds: Seeds,
+    maps: Maps,
+    strategy: Strategy,
+}
+
+impl FromInput<'_> for Almanac {
+    fn from_input(s: &str) -> Result<Self> {
+        let (seeds, maps) = error::parse(Solution.day(), s, parse_input)?;
+        // seeds pair up as the start and length of a range in part 2
+        if seeds.0.len() % 2 != 0 {
+            let line = s.trim_start();
+            Err(AocError::Parse {
+                day: Solution.day(),
+                location: Location::new(s.as_bytes(), line.as_bytes()),
+                reason: format!("odd number of seeds, {}", seeds.0.len()),
+            })?;
+        }
+        Ok(Almanac::new(seeds.0, maps))
+    }
+}
+
+impl Almanac {
+    pub fn new(seeds: Vec<usize>, maps: Maps) -> Self {
+        Almanac {
+            seeds: Seeds(seeds),
+            maps,
+            strategy: Strategy::default(),
+        }
+    }
+
+    /// The same almanac, with part 2 solved by `strategy`
+    pub fn strategy(self, strategy: Strategy) -> Self {
+        Almanac { strategy, ..self }
+    }
+
+    pub fn seeds(&self) -> &[usize] {
+        &self.seeds.0
+    }
+
+    pub fn maps(&self) -> &Maps {
+        &self.maps
+    }
+
+    /// Seeds read as pairs of the start and length of a range of seeds
+    pub fn seed_ranges(&self) -> impl Iterator<Item = ops::Range<usize>> + '_ {
+        self.seeds
+            .0
+            .chunks_exact(2)
+            .map(|chunk| chunk[0]..chunk[0] + chunk[1])
+    }
+
+    /// Lowest location of any seed
+    pub fn lowest_location(&self) -> usize {
+        self.seeds
+            .0
+            .iter()
+            .map(|&seed| self.maps.map(seed))
+            .fold(usize::MAX, usize::min)
+    }
+
+    /// Lowest location of any seed in the seed ranges, by the almanac's
+    /// strategy
+    pub fn lowest_location_of_seed_ranges(&self) -> usize {
+        match self.strategy {
+            Strategy::Intervals => self.lowest_location_by_intervals(),
+            Strategy::Scan => self.lowest_location_by_scan().unwrap_or(usize::MAX),
+        }
+    }
+
+    // Lowest location of the seed ranges pushed forward through the maps
+    fn lowest_location_by_intervals(&self) -> usize {
+        // ranges searched in parallel, each split in as many parts as there
+        // are threads, so that a few long ranges don't leave threads idle
+        let parts = par::threads();
+        let seed_ranges = self
+            .seed_ranges()
+            .flat_map(|seeds| split(seeds, parts))
+            .enumerate()
+            .collect::<Vec<_>>();
+        par::map(&seed_ranges, |(i, seeds)| {
+            log::debug!(
+                "{:2}: searching over  [{}] ({}, {})",
+                i,
+                seeds.len(),
+                seeds.start,
+                seeds.end,
+            );
+            self.maps.min(seeds.clone())
+        })
+        .into_iter()
+        .flatten()
+        .fold(usize::MAX, usize::min)
+    }
+
+    /// Lowest location of any seed in the seed ranges, scanning locations
+    /// upward for the first with a seed in the ranges, if there's one.
+    /// Locations aren't tried one at a time: from a location on, every
+    /// seed mapping to it shifts along with it up to the nearest boundary
+    /// of a map, so the scan checks that run of locations at once and
+    /// skips to the next.
+    pub fn lowest_location_by_scan(&self) -> Option<usize> {
+        let seed_ranges = self.seed_ranges().collect::<Vec<_>>();
+        let mut location = 0;
+        loop {
+            // seeds mapping to `location`, and how far on they shift alike
+            let mut run = usize::MAX - location;
+            let mut keys = vec![location];
+            for map in self.maps.maps.iter().rev() {
+                keys = keys
+                    .into_iter()
+                    .flat_map(|key| {
+                        run = run.min(map.run(key));
+                        map.preimages(key)
+                    })
+                    .collect();
+            }
+            // the first seed of the ranges among keys..keys + run
+            let found = keys
+                .iter()
+                .flat_map(|&key| {
+                    seed_ranges.iter().filter_map(move |seeds| {
+                        let seed = seeds.start.max(key);
+                        (seed < seeds.end && seed - key < run).then_some(seed - key)
+                    })
+                })
+                .min();
+            if let Some(offset) = found {
+                return Some(location + offset);
+            }
+            if run == usize::MAX - location {
+                return None;
+            }
+            location += run;
+        }
+    }
+}
+
+// `range` split in up to `parts` consecutive ranges of about the same length
+fn split(range: ops::Range<usize>, parts: usize) -> impl Iterator<Item = ops::Range<usize>> {
+    let step = range.len().div_ceil(parts.max(1)).max(1);
+    (range.start..range.end)
+        .step_by(step)
+        .map(move |start| start..(start + step).min(range.end))
+}
+
+fn parse_number(input: &str) -> IResult<&str, usize> {
+    map_res(digit1, |s: &str| s.parse::<usize>())(input)
+}
+
+fn parse_numbers(input: &str) -> IResult<&str, Vec<usize>> {
+    separated_list1(space1, parse_number)(input)
+}
+
+fn parse_map(input: &str) -> IResult<&str, Range> {
+    let (input, (dst, _, src, _, len)) =
+        tuple((parse_number, space1, parse_number, space1, parse_number))(input)?;
+    Ok((input, Range { src, dst, len }))
+}
+
+// A map between two categories, e.g.
+//
+//   seed-to-soil map:
+//   50 98 2
+//   52 50 48
+fn parse_section(input: &str) -> IResult<&str, (&str, &str, Map)> {
+    let (input, (from, _, to, _, _, ranges)) = tuple((
+        alpha1,
+        tag("-to-"),
+        alpha1,
+        tag(" map:"),
+        newline,
+        separated_list1(newline, parse_map),
+    ))(input)?;
+    Ok((input, (from, to, Map::new(ranges))))
+}
+
+// Seeds, and any number of maps, each from the category the one before
+// leads to
+fn parse_input(input: &str) -> IResult<&str, (Seeds, Maps)> {
+    let (input, _) = tag("seeds: ")(input)?;
+    let (input, seeds) = parse_numbers(input)?;
+    log::debug!("seeds: {:?}", seeds);
+
+    let mut categories = vec![];
+    let mut maps = vec![];
+    let (mut input, _) = pair(newline, newline)(input)?;
+    loop {
+        let (rest, (from, to, map)) = parse_section(input)?;
+        if categories.last().is_some_and(|last| last != from) {
+            // a map from another category than the one before leads to
+            return Err(nom::Err::Failure(nom::error::Error::new(
+                input,
+                ErrorKind::Verify,
+            )));
+        }
+        log::debug!("{}-to-{} map:\n{}", from, to, map);
+        if categories.is_empty() {
+            categories.push(from.to_string());
+        }
+        categories.push(to.to_string());
+        maps.push(map);
+        match pair(newline, newline)(rest) {
+            Ok((next, _)) => input = next,
+            Err(nom::Err::Error(_)) => {
+                input = rest;
+                break;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+
+    let maps = Maps { maps, categories };
+    Ok((input, (Seeds(seeds), maps)))
+}
+
+/// Options of the puzzle, set from parameters
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Options {
+    /// How part 2 finds the lowest location, from `strategy`, to check one
+    /// way against the other
+    pub strategy: Strategy,
+}
+
+impl FromParams for Options {
+    const KEYS: &'static [&'static str] = &["strategy"];
+
+    fn defaults() -> Self {
+        Options::default()
+    }
+
+    fn set(&mut self, key: &str, value: &str) -> Result<()> {
+        match key {
+            "strategy" => self.strategy = value.parse()?,
+            _ => anyhow::bail!("unknown option {}", key),
+        }
+        Ok(())
+    }
+}
+
+pub struct Solution;
+
+solver::register!(Solution);
+
+impl Puzzle for Solution {
+    type Input<'a> = Almanac;
+
+    fn year(&self) -> u16 {
+        2023
+    }
+
+    fn day(&self) -> u8 {
+        5
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Self::Input<'a>> {
+        let input = Almanac::from_input(input)?;
+        log::debug!("{}", input.seeds);
+        for (map_idx, map) in input.maps.maps.iter().enumerate() {
+            for range in &map.ranges {
+                log::debug!(
+                    "map {}: ({}, {})",
+                    map_idx,
+                    range.src,
+                    range.src + range.len
+                );
+            }
+            log::debug!("");
+        }
+        Ok(input)
+    }
+
+    fn configure(&self, input: &mut Almanac, params: &Params) -> Result<()> {
+        input.strategy = params.options::<Options>(self.day())?.strategy;
+        Ok(())
+    }
+
+    fn part1(&self, input: &Almanac) -> Result<Option<Answer>> {
+        let part1 = input.lowest_location();
+        Ok(Some(part1.into()))
+    }
+
+    fn part2(&self, input: &Almanac) -> Result<Option<Answer>> {
+        let part2 = input.lowest_location_of_seed_ranges();
+        Ok(Some(part2.into()))
+    }
+
+    // Ranges of every category the seed ranges map to, stage by stage; the
+    // start of the first range of locations is the answer of part 2
+    fn explain(&self, input: &Almanac) -> Result<Vec<Artifact>> {
+        let mut ranges = input.seed_ranges().collect::<Vec<_>>();
+        ranges.sort_by_key(|range| range.start);
+        let mut artifacts = vec![];
+        for (i, map) in iter::once(None)
+            .chain(input.maps().maps().iter().map(Some))
+            .enumerate()
+        {
+            if let Some(map) = map {
+                ranges = map.map_ranges(&ranges);
+            }
+            let category = input
+                .maps()
+                .categories()
+                .get(i)
+                .map_or("unknown", String::as_str);
+            artifacts.push(Artifact::new(
+                format!("{} ranges ({})", category, ranges.len()),
+                ranges
+                    .iter()
+                    .map(|range| format!("{:>10} .. {:>10}", range.start, range.end)),
+            ));
+        }
+        Ok(artifacts)
+    }
+
+    // Seeds come in pairs of start and length, and 7 maps lead from seeds to
+    // locations, each a list of destination, source and length
+    fn validate(&self, input: &str) -> Vec<Violation> {
+        let mut violations = vec![];
+        let blocks = validate::blocks(input);
+        let Some((seeds, maps)) = blocks.split_first() else {
+            return vec![Violation::new("input is empty")];
+        };
+        let (number, line) = seeds[0];
+        match line.strip_prefix("seeds:") {
+            Some(seeds) => {
+                let len = seeds.split_whitespace().count();
+                if len == 0 || len % 2 != 0 {
+                    violations.push(Violation::at(
+                        number,
+                        format!("{} seeds, not pairs of start and length", len),
+                    ));
+                }
+            }
+            None => violations.push(Violation::at(number, "expected `seeds:`")),
+        }
+        if maps.len() != 7 {
+            violations.push(Violation::new(format!(
+                "{} maps, not 7 from seed to location",
+                maps.len()
+            )));
+        }
+        for map in maps {
+            let (number, header) = map[0];
+            if !header.ends_with(" map:") {
+                violations.push(Violation::at(number, "expected the name of a map"));
+            }
+            for &(number, line) in &map[1..] {
+                let numbers = line.split_whitespace().collect::<Vec<_>>();
+                if numbers.len() != 3 || numbers.iter().any(|n| n.parse::<usize>().is_err()) {
+                    violations.push(Violation::at(
+                        number,
+                        "expected destination, source and length",
+                    ));
+                }
+            }
+        }
+        violations
+    }
+
+    // Seeds and maps over the 32-bit numbers, where every map cuts the
+    // numbers into ranges and lays them out again in another order, so
+    // every number has one destination
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        const SPAN: usize = 1 << 32;
+        const NAMES: [&str; 7] = [
+            "seed-to-soil",
+            "soil-to-fertilizer",
+            "fertilizer-to-water",
+            "water-to-light",
+            "light-to-temperature",
+            "temperature-to-humidity",
+            "humidity-to-location",
+        ];
+        let seeds = (0..10 * size)
+            .map(|_| {
+                let start = rng.gen_range(0..SPAN);
+                let len = rng.gen_range(1..=(SPAN - start).min(500_000_000));
+                format!("{} {}", start, len)
+            })
+            .collect::<Vec<_>>();
+        let mut blocks = vec![format!("seeds: {}", seeds.join(" "))];
+        for name in NAMES {
+            let mut cuts = (0..rng.gen_range(10..40) * size)
+                .map(|_| rng.gen_range(1..SPAN))
+                .chain([0, SPAN])
+                .collect::<Vec<_>>();
+            cuts.sort();
+            cuts.dedup();
+            let mut ranges = cuts
+                .windows(2)
+                .map(|w| (w[0], w[1] - w[0]))
+                .collect::<Vec<_>>();
+            ranges.shuffle(rng);
+            let mut dst = 0;
+            let mut lines = vec![format!("{} map:", name)];
+            for (src, len) in ranges {
+                lines.push(format!("{} {} {}", dst, src, len));
+                dst += len;
+            }
+            blocks.push(lines.join("\n"));
+        }
+        Some(blocks.join("\n\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::InputStore;
+
+    #[test]
+    fn test_with_sample() -> Result<()> {
+        let input = &InputStore::default().sample(2023, 5)?;
+        assert!(Solution.validate(input).is_empty());
+        assert_eq!(
+            Solution.validate("seeds: 79 14 55\n\nseed-to-soil map:\n50 98\n"),
+            [
+                Violation::at(1, "3 seeds, not pairs of start and length"),
+                Violation::new("1 maps, not 7 from seed to location"),
+                Violation::at(4, "expected destination, source and length")
+            ]
+        );
+        let Almanac { seeds, maps, .. } = Almanac::from_input(input)?;
+
+        assert_eq!(seeds.0, vec![79, 14, 55, 13]);
+        assert_eq!(maps.maps().len(), 7);
+
+        // seed to soil map for sample input
+        assert_eq!(maps.maps()[0].map(79), 81);
+        assert_eq!(maps.maps()[0].map(14), 14);
+        assert_eq!(maps.maps()[0].map(55), 57);
+        assert_eq!(maps.maps()[0].map(13), 13);
+
+        // seed to location map for sample input
+        assert_eq!(maps.map(79), 82);
+        assert_eq!(maps.map(14), 43);
+        assert_eq!(maps.map(55), 86);
+        assert_eq!(maps.map(13), 35);
+
+        assert_eq!(maps.map(82), 46);
+        assert_eq!(
+            maps.maps()[0].map_ranges(&[55..68, 79..93]),
+            [57..70, 81..95]
+        );
+
+        let input = Almanac::new(seeds.0, maps);
+        assert_eq!(input.seed_ranges().collect::<Vec<_>>(), [79..93, 55..68]);
+
+        let part1 = input.lowest_location();
+        assert_eq!(part1, 35);
+
+        let part2 = input.lowest_location_of_seed_ranges();
+        assert_eq!(part2, 46);
+
+        // the lowest of the ranges of locations starts at the answer
+        let artifacts = Solution.explain(&input)?;
+        assert_eq!(artifacts.len(), 8);
+        assert_eq!(artifacts[0].title, "seed ranges (2)");
+        let locations = &artifacts[7];
+        assert!(locations.title.starts_with("location ranges"));
+        assert!(locations.lines[0].trim_start().starts_with("46 .."));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_that_breaks_day5_part2_algo() -> Result<()> {
+        let seeds = vec![0, 100];
+        let maps1 = vec![Range {
+            src: 0,
+            dst: 100,
+            len: 100,
+        }];
+        let maps2 = vec![
+            Range {
+                src: 100,
+                dst: 100,
+                len: 50,
+            },
+            Range {
+                src: 150,
+                dst: 0,
+                len: 20,
+            },
+            Range {
+                src: 170,
+                dst: 170,
+                len: 30,
+            },
+        ];
+        let map1 = Map::new(maps1);
+        let map2 = Map::new(maps2);
+        let maps = Maps::new(vec![map1, map2]);
+        assert_eq!(maps.map(0), 100);
+        assert_eq!(maps.map(99), 199);
+        assert_eq!(maps.map(100), 100);
+        assert_eq!(maps.map(50), 0);
+        assert_eq!(maps.map(69), 19);
+        assert_eq!(maps.map(70), 170);
+        let input = Almanac::new(seeds, maps);
+        assert_eq!(input.lowest_location(), 100);
+
+        // the seeds 50..70 map to 150..170 on to 0..20, which halving the
+        // seeds until a half maps linearly didn't find
+        assert_eq!(input.lowest_location_of_seed_ranges(), 0);
+        assert_eq!(input.lowest_location_by_scan(), Some(0));
+        assert_eq!(input.maps().preimages(0), [50, 150]);
+        assert_eq!(input.maps().preimages(100), [0, 100]);
+        let seeds = input.seed_ranges().collect::<Vec<_>>();
+        assert_eq!(input.maps().map_ranges(&seeds), [0..20, 100..150, 170..200]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_map() -> Result<()> {
+        // 50 98 2
+        let input = "50 98 2";
+        let (input, map) = parse_map(input)?;
+        assert_eq!(input, "");
+        assert_eq!(
+            map,
+            Range {
+                src: 98,
+                dst: 50,
+                len: 2
+            }
+        );
+        Ok(())
+    }
+
+    // Scanning locations back to the seeds agrees with pushing the seeds
+    // forward to the locations
+    #[test]
+    fn test_scan() -> Result<()> {
+        let input = &InputStore::default().sample(2023, 5)?;
+        let almanac = Almanac::from_input(input)?;
+        assert_eq!(almanac.maps().preimages(46), [82]);
+        assert!(almanac.maps().preimages(35).contains(&13));
+        assert_eq!(almanac.lowest_location_by_scan(), Some(46));
+
+        let mut rng = Rng::seed_from_u64(5);
+        for _ in 0..5 {
+            let input = Solution.generate(&mut rng, 1).unwrap();
+            let almanac = Almanac::from_input(&input)?;
+            let intervals = almanac.lowest_location_of_seed_ranges();
+            let almanac = almanac.strategy(Strategy::Scan);
+            assert_eq!(almanac.lowest_location_of_seed_ranges(), intervals);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_any_chain_of_maps() -> Result<()> {
+        let input = &InputStore::default().sample(2023, 5)?;
+        let almanac = Almanac::from_input(input)?;
+        let categories = almanac.maps().categories();
+        assert_eq!(categories.len(), 8);
+        assert_eq!(categories.first().map(String::as_str), Some("seed"));
+        assert_eq!(categories.last().map(String::as_str), Some("location"));
+        assert_eq!(almanac.maps().get("soil").map(|map| map.map(53)), Some(38));
+        assert!(almanac.maps().get("location").is_none());
+
+        let input = "seeds: 1 10\n\nseed-to-fruit map:\n100 1 5\n\nfruit-to-location map:\n0 100 3";
+        let almanac = Almanac::from_input(input)?;
+        assert_eq!(almanac.maps().categories(), ["seed", "fruit", "location"]);
+        assert_eq!(almanac.lowest_location(), 0);
+        assert_eq!(almanac.lowest_location_of_seed_ranges(), 0);
+
+        let input = "seeds: 1 10\n\nseed-to-fruit map:\n100 1 5\n\nsoil-to-location map:\n0 100 3";
+        assert!(Almanac::from_input(input).is_err());
+        assert!(Maps::named(vec!["seed".to_string()], vec![Map::new(vec![])]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_split() {
+        let parts = split(79..93, 4).collect::<Vec<_>>();
+        assert_eq!(parts, [79..83, 83..87, 87..91, 91..93]);
+        assert_eq!(split(0..3, 8).collect::<Vec<_>>(), [0..1, 1..2, 2..3]);
+        assert_eq!(split(5..5, 2).count(), 0);
+        assert!(split(0..10, 1).eq(Some(0..10)));
+    }
+
+    #[test]
+    fn test_interval_mapping() -> Result<()> {
+        let input = &InputStore::default().sample(2023, 5)?;
+        let almanac = Almanac::from_input(input)?;
+        let maps = almanac.maps();
+
+        let seed_to_soil = maps.get("seed").unwrap();
+        assert_eq!(seed_to_soil.map_interval(45..55), [45..50, 52..57]);
+        assert_eq!(maps.map_interval(79..93), [46..56, 60..61, 82..85]);
+
+        let composed = maps.compose();
+        for seed in 0..120 {
+            assert_eq!(composed.map(seed), maps.map(seed));
+        }
+        assert_eq!(composed.map_interval(79..93), maps.map_interval(79..93));
+        let seed_to_fertilizer = seed_to_soil.then(maps.get("soil").unwrap());
+        assert_eq!(seed_to_fertilizer.map(79), 81);
+        assert_eq!(seed_to_fertilizer.map(14), 53);
+        Ok(())
+    }
+
+    // Almanac of a few maps over small numbers, the sources of each map's
+    // ranges apart from each other as in the puzzle, their destinations
+    // anywhere, and a few seed ranges, some maybe empty
+    fn random_almanac(rng: &mut Rng) -> Almanac {
+        let maps = (0..rng.gen_range(1..=4))
+            .map(|_| {
+                let mut cuts = (0..rng.gen_range(0..=8))
+                    .map(|_| rng.gen_range(0..80))
+                    .collect::<Vec<_>>();
+                cuts.sort();
+                cuts.dedup();
+                let ranges = cuts
+                    .chunks_exact(2)
+                    .map(|pair| Range::new(rng.gen_range(0..80), pair[0], pair[1] - pair[0]))
+                    .collect();
+                Map::new(ranges)
+            })
+            .collect();
+        let seeds = (0..rng.gen_range(1..=3))
+            .flat_map(|_| [rng.gen_range(0..80), rng.gen_range(0..=20)])
+            .collect();
+        Almanac::new(seeds, Maps::new(maps))
+    }
+
+    #[test]
+    fn test_against_brute_force() {
+        let mut rng = Rng::seed_from_u64(5);
+        for _ in 0..500 {
+            let almanac = random_almanac(&mut rng);
+            let maps = almanac.maps();
+            // every seed of the seed ranges mapped one by one
+            let lowest = almanac
+                .seed_ranges()
+                .flatten()
+                .map(|seed| maps.map(seed))
+                .min();
+            let ranges = almanac.seed_ranges().collect::<Vec<_>>();
+            let context = format!("seeds {:?} through\n{:#?}", ranges, maps.maps());
+
+            assert_eq!(
+                almanac.lowest_location_of_seed_ranges(),
+                lowest.unwrap_or(usize::MAX),
+                "{}",
+                context
+            );
+            assert_eq!(almanac.lowest_location_by_scan(), lowest, "{}", context);
+            let mapped = maps.map_ranges(&ranges);
+            assert_eq!(mapped.first().map(|r| r.start), lowest, "{}", context);
+            let composed = maps.compose();
+            for seed in 0..100 {
+                assert_eq!(composed.map(seed), maps.map(seed), "{}", context);
+            }
+        }
+    }
+
+    #[test]
+    fn test_seed_count() {
+        let maps = "\n\nseed-to-location map:\n0 1 2";
+        let err = Almanac::from_input(&format!("seeds: 79 14 55{}", maps)).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "day 05: failed to parse input (odd number of seeds, 3) \
+             at line 1, column 1: `seeds: 79 14 55`"
+        );
+        let err = Almanac::from_input(&format!("seeds: {}", maps)).unwrap_err();
+        assert!(err.to_string().contains("at line 1, column 8"));
+        assert!(Almanac::from_input(&format!("seeds: 79 14{}", maps)).is_ok());
+    }
+}
@@ -0,0 +1,277 @@
+use core::fmt;
+
+use anyhow::{Context, Result};
+use itertools::Itertools;
+use nom::{
+    bytes::complete::tag,
+    character::complete::{digit1, newline, space1},
+    combinator::map_res,
+    multi::separated_list1,
+    sequence::tuple,
+    IResult,
+};
+use rand::Rng as _;
+
+use crate::{
+    error,
+    generate::Rng,
+    int::{self, Int},
+    log, par,
+    solver::{self, FromInput, Puzzle},
+    validate::{self, Violation},
+    Answer,
+};
+
+#[derive(Debug)]
+struct Race<T> {
+    time: T,
+    distance: T,
+}
+
+impl<T: Int> fmt::Display for Race<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "time: {}, distance: {}", self.time, self.distance)
+    }
+}
+
+impl<T: Int> Race<T> {
+    // Distance of holding the button for `hold_time`, if it fits in `T`
+    fn distance(&self, hold_time: &T) -> Option<T> {
+        assert!(*hold_time <= self.time);
+        let remaining_time = self.time.clone() - hold_time.clone();
+        let speed = hold_time;
+        remaining_time.checked_mul(speed)
+    }
+
+    fn wins(&self, hold_time: &T) -> bool {
+        // distances that don't fit in `T` beat any record that does
+        self.distance(hold_time)
+            .is_none_or(|distance| distance > self.distance)
+    }
+
+    fn winning_bets(&self) -> Vec<(T, Option<T>)> {
+        let mut bets = vec![];
+        let mut hold_time = T::zero();
+        while hold_time <= self.time {
+            if self.wins(&hold_time) {
+                bets.push((hold_time.clone(), self.distance(&hold_time)));
+            }
+            hold_time = hold_time + T::one();
+        }
+        bets
+    }
+
+    // Distances grow up to holding the button for half the time and shrink
+    // symmetrically after, so the winning hold times are a range around the
+    // half, found by searching for its start
+    fn num_winning_bets(&self) -> T {
+        let two = T::one() + T::one();
+        let half = self.time.clone() / two.clone();
+        if !self.wins(&half) {
+            return T::zero();
+        }
+        let (mut lo, mut hi) = (T::zero(), half);
+        while lo < hi {
+            let mid = (lo.clone() + hi.clone()) / two.clone();
+            if self.wins(&mid) {
+                hi = mid;
+            } else {
+                lo = mid + T::one();
+            }
+        }
+        self.time.clone() + T::one() - lo * two
+    }
+}
+
+/// Boat races of the sheet, with times and distances of the integer type
+/// `T`
+#[derive(Debug)]
+pub struct Races<T = u64>(Vec<Race<T>>);
+
+impl<T: Int> fmt::Display for Races<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, race) in self.0.iter().enumerate() {
+            write!(f, "{} {}", i, race)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: Int> FromInput<'_> for Races<T> {
+    fn from_input(s: &str) -> Result<Self> {
+        Ok(error::parse(Solution.day(), s, parse_races)?)
+    }
+}
+
+impl<T: Int> Races<T> {
+    /// Product of the number of ways to win each race, failing if it
+    /// doesn't fit in `T`
+    pub fn num_winning_bets(&self) -> Result<T> {
+        par::map(&self.0, Race::num_winning_bets)
+            .into_iter()
+            .filter(|len| !len.is_zero())
+            .try_fold(T::one(), |product, len| {
+                product
+                    .checked_mul(&len)
+                    .with_context(|| format!("product of ways to win overflows at {}", len))
+            })
+    }
+
+    /// Number of ways to win the race read without the spaces between the
+    /// numbers, failing if its time or distance doesn't fit in `T`
+    pub fn num_unkerned_winning_bets(&self) -> Result<T> {
+        Ok(self.unkerned()?.num_winning_bets())
+    }
+
+    fn unkerned(&self) -> Result<Race<T>> {
+        let time = int::parse(&self.0.iter().map(|race| &race.time).join(""))?;
+        let distance = int::parse(&self.0.iter().map(|race| &race.distance).join(""))?;
+        Ok(Race { time, distance })
+    }
+}
+
+pub struct Solution;
+
+solver::register!(Solution);
+
+impl Puzzle for Solution {
+    type Input<'a> = Races<u64>;
+
+    fn year(&self) -> u16 {
+        2023
+    }
+
+    fn day(&self) -> u8 {
+        6
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Self::Input<'a>> {
+        let races = Races::from_input(input)?;
+        for (i, race) in races.0.iter().enumerate() {
+            log::debug!("winning bet of race: {}", i);
+            for (hold_time, distance) in race.winning_bets() {
+                log::debug!("[{}] hold time: {}, distance: {:?}", i, hold_time, distance);
+            }
+        }
+        Ok(races)
+    }
+
+    fn part1(&self, races: &Races) -> Result<Option<Answer>> {
+        let part1 = races.num_winning_bets()?;
+        Ok(Some(part1.into()))
+    }
+
+    fn part2(&self, races: &Races) -> Result<Option<Answer>> {
+        let part2 = races.num_unkerned_winning_bets()?;
+        Ok(Some(part2.into()))
+    }
+
+    // Every race has a time and a distance
+    fn validate(&self, input: &str) -> Vec<Violation> {
+        let lines = validate::lines(input).collect::<Vec<_>>();
+        let [(_, times), (number, distances)] = lines[..] else {
+            return vec![Violation::new(format!(
+                "{} lines, not the times and the distances",
+                lines.len()
+            ))];
+        };
+        // the numbers follow the names of the lines
+        let times = times.split_whitespace().skip(1).count();
+        let distances = distances.split_whitespace().skip(1).count();
+        if times != distances {
+            return vec![Violation::at(
+                number,
+                format!("{} distances for {} times", distances, times),
+            )];
+        }
+        vec![]
+    }
+
+    // Races of up to 100ms whose records are beaten by holding the button
+    // for about half the time. There are always 4 races, as the race of
+    // their digits overflows with more, and larger races take no longer.
+    fn generate(&self, rng: &mut Rng, _size: usize) -> Option<String> {
+        let races = (0..4)
+            .map(|_| {
+                let time = rng.gen_range(40..100);
+                let hold = rng.gen_range(time / 8..time / 2);
+                (time, hold * (time - hold))
+            })
+            .collect::<Vec<_>>();
+        let times = races
+            .iter()
+            .map(|(time, _)| format!(" {:>6}", time))
+            .join("");
+        let records = races
+            .iter()
+            .map(|(_, record)| format!(" {:>6}", record))
+            .join("");
+        Some(format!(
+            "{:<9}{}\n{:<9}{}",
+            "Time:", times, "Distance:", records
+        ))
+    }
+}
+
+fn parse_number<T: Int>(input: &str) -> IResult<&str, T> {
+    map_res(digit1, int::parse)(input)
+}
+
+fn parse_numbers<T: Int>(input: &str) -> IResult<&str, Vec<T>> {
+    separated_list1(space1, parse_number)(input)
+}
+
+fn parse_races<T: Int>(input: &str) -> IResult<&str, Races<T>> {
+    let (input, (_, _, times, _, _, _, distances)) = tuple((
+        tag("Time:"),
+        space1,
+        parse_numbers,
+        newline,
+        tag("Distance:"),
+        space1,
+        parse_numbers,
+    ))(input)?;
+    assert_eq!(times.len(), distances.len());
+    let races = times
+        .into_iter()
+        .zip(distances)
+        .map(|(time, distance)| Race { time, distance })
+        .collect::<Vec<_>>();
+    Ok((input, Races(races)))
+}
+
+#[cfg(test)]
+mod tests {
+    use num_bigint::BigUint;
+
+    use super::*;
+    use crate::InputStore;
+
+    #[test]
+    fn test_with_sample() -> Result<()> {
+        let input = &InputStore::default().sample(2023, 6)?;
+        assert!(Solution.validate(input).is_empty());
+        let races = Races::<u64>::from_input(input)?;
+        let part1 = races.num_winning_bets()?;
+        assert_eq!(part1, 288);
+
+        let part2 = races.num_unkerned_winning_bets()?;
+        assert_eq!(part2, 71503);
+
+        let races = Races::<u16>::from_input(input)?;
+        assert_eq!(races.num_winning_bets()?, 288);
+        assert!(races.num_unkerned_winning_bets().is_err());
+        let races = Races::<u8>::from_input(input)?;
+        assert!(races.num_winning_bets().is_err());
+
+        // the race read without spaces only fits in 128 bits
+        let input = "Time: 31415 926535\nDistance: 24674011001 3129276555";
+        let races = Races::<u64>::from_input(input)?;
+        assert!(races.num_unkerned_winning_bets().is_err());
+        let races = Races::<BigUint>::from_input(input)?;
+        assert_eq!(races.num_unkerned_winning_bets()?, BigUint::from(2u8));
+        let races = Races::<u128>::from_input(input)?;
+        assert_eq!(races.num_unkerned_winning_bets()?, 2);
+        Ok(())
+    }
+}
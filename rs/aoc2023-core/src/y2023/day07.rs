@@ -0,0 +1,435 @@
+use std::{cmp::Ordering, collections::HashSet, fmt, str::FromStr};
+
+use anyhow::Result;
+use nom::{
+    character::complete::{alphanumeric1, digit1, space1},
+    combinator::map_res,
+    sequence::tuple,
+    IResult,
+};
+use rand::{seq::SliceRandom, Rng as _};
+
+use crate::{
+    error,
+    generate::Rng,
+    solver::{self, FromInput, Puzzle},
+    util::counter::Counter,
+    validate::{self, Violation},
+    Answer, Artifact,
+};
+
+/// Camel card, ordered by strength
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Copy, Clone, Hash)]
+pub enum Card {
+    Two,
+    Three,
+    Four,
+    Five,
+    Six,
+    Seven,
+    Eight,
+    Nine,
+    Ten,
+    J, // Jack / Joker
+    Q, // Queen
+    K, // King
+    A, // Ace
+}
+
+impl TryFrom<u8> for Card {
+    type Error = anyhow::Error;
+
+    fn try_from(value: u8) -> Result<Self> {
+        match value {
+            b'2' => Ok(Card::Two),
+            b'3' => Ok(Card::Three),
+            b'4' => Ok(Card::Four),
+            b'5' => Ok(Card::Five),
+            b'6' => Ok(Card::Six),
+            b'7' => Ok(Card::Seven),
+            b'8' => Ok(Card::Eight),
+            b'9' => Ok(Card::Nine),
+            b'T' => Ok(Card::Ten),
+            b'J' => Ok(Card::J),
+            b'Q' => Ok(Card::Q),
+            b'K' => Ok(Card::K),
+            b'A' => Ok(Card::A),
+            _ => Err(anyhow::anyhow!("Invalid card: {}", value)),
+        }
+    }
+}
+
+impl Card {
+    fn label(self) -> char {
+        b"23456789TJQKA"[self as usize] as char
+    }
+
+    fn joker_cmp((this, that): (&Self, &Self)) -> Ordering {
+        match (this, that) {
+            (Card::J, Card::J) => Ordering::Equal,
+            (Card::J, _) => Ordering::Less,
+            (_, Card::J) => Ordering::Greater,
+            _ => this.cmp(that),
+        }
+    }
+}
+
+/// Type of a hand, ordered by strength
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Copy, Clone)]
+pub enum HandType {
+    HighCard,
+    OnePair,
+    TwoPairs,
+    ThreeOfAKind,
+    FullHouse,
+    FourOfAKind,
+    FiveOfAKind,
+}
+
+impl From<Counter<Card>> for HandType {
+    fn from(counts: Counter<Card>) -> Self {
+        let counts = counts
+            .most_common()
+            .into_iter()
+            .map(|(_, count)| count)
+            .collect::<Vec<_>>();
+        match counts[..] {
+            [5, ..] => HandType::FiveOfAKind,
+            [4, 1, ..] => HandType::FourOfAKind,
+            [3, 2, ..] => HandType::FullHouse,
+            [3, 1, 1, ..] => HandType::ThreeOfAKind,
+            [2, 2, 1, ..] => HandType::TwoPairs,
+            [2, 1, 1, 1, ..] => HandType::OnePair,
+            _ => HandType::HighCard,
+        }
+    }
+}
+
+/// Hand of five camel cards
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Copy, Clone)]
+pub struct Hand([Card; 5]);
+
+impl FromStr for Hand {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        anyhow::ensure!(s.len() == 5, "invalid hand: {}", s);
+        let mut cards = [Card::Two; 5];
+        for (i, byte) in s.bytes().enumerate() {
+            cards[i] = Card::try_from(byte)?;
+        }
+        Ok(Hand(cards))
+    }
+}
+
+impl fmt::Display for Hand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for card in self.0 {
+            write!(f, "{}", card.label())?;
+        }
+        Ok(())
+    }
+}
+
+impl Hand {
+    pub fn cards(&self) -> &[Card; 5] {
+        &self.0
+    }
+
+    /// Type of the hand with jacks
+    pub fn hand_type(&self) -> HandType {
+        self.0.iter().copied().collect::<Counter<_>>().into()
+    }
+
+    /// Type of the hand with jokers, which count as the most frequent card
+    pub fn joker_hand_type(&self) -> HandType {
+        let mut counts = self.0.iter().copied().collect::<Counter<_>>();
+        let jokers = counts.remove(&Card::J);
+
+        // give jokers to the most frequent card, or keep them if they're all
+        // the hand has
+        let most_common = counts
+            .most_common()
+            .first()
+            .map_or(Card::J, |&(&card, _)| card);
+        counts.add(most_common, jokers);
+
+        counts.into()
+    }
+}
+
+// Hand with jacks and its type, classified once rather than on every
+// comparison, ordered by the type and then the cards
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct DefaultHand(HandType, Hand);
+
+impl From<Hand> for DefaultHand {
+    fn from(hand: Hand) -> Self {
+        DefaultHand(hand.hand_type(), hand)
+    }
+}
+
+// Hand with jokers and its type, ordered by the type and then the cards,
+// jokers the weakest
+#[derive(Debug, PartialEq, Eq)]
+struct JokerHand(HandType, Hand);
+
+impl From<Hand> for JokerHand {
+    fn from(hand: Hand) -> Self {
+        JokerHand(hand.joker_hand_type(), hand)
+    }
+}
+
+impl Ord for JokerHand {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp(&other.0).then_with(|| {
+            self.1
+                .cards()
+                .iter()
+                .zip(other.1.cards())
+                .map(Card::joker_cmp)
+                .find(|&order| order != Ordering::Equal)
+                .unwrap_or(Ordering::Equal)
+        })
+    }
+}
+
+impl PartialOrd for JokerHand {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Hand with its bid
+#[derive(Debug)]
+pub struct Game {
+    hand: Hand,
+    bid: usize,
+}
+
+impl Game {
+    pub fn hand(&self) -> &Hand {
+        &self.hand
+    }
+
+    pub fn bid(&self) -> usize {
+        self.bid
+    }
+}
+
+/// Hands of camel cards with their bids
+#[derive(Debug)]
+pub struct Games(Vec<Game>);
+
+impl FromInput<'_> for Games {
+    fn from_input(s: &str) -> Result<Self> {
+        let games = error::parse_lines(Solution.day(), s, parse_game)?
+            .into_iter()
+            .map(|(hand, bid)| {
+                Ok(Game {
+                    hand: hand.parse()?,
+                    bid,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Games(games))
+    }
+}
+
+impl Games {
+    pub fn games(&self) -> &[Game] {
+        &self.0
+    }
+
+    // Games from the weakest hand to the strongest, i.e. by rank from 1
+    fn ranked(&self) -> Vec<&Game> {
+        let mut ranked = self.0.iter().collect::<Vec<_>>();
+        ranked.sort_by_cached_key(|game| DefaultHand::from(game.hand));
+        ranked
+    }
+
+    // Games by rank with jokers
+    fn ranked_with_joker(&self) -> Vec<&Game> {
+        let mut ranked = self.0.iter().collect::<Vec<_>>();
+        ranked.sort_by_cached_key(|game| JokerHand::from(game.hand));
+        ranked
+    }
+
+    fn winnings(&self) -> usize {
+        winnings(&self.ranked())
+    }
+
+    fn winnings_with_joker(&self) -> usize {
+        winnings(&self.ranked_with_joker())
+    }
+}
+
+// Winnings of games by rank
+fn winnings(ranked: &[&Game]) -> usize {
+    ranked
+        .iter()
+        .enumerate()
+        .map(|(i, game)| game.bid * (i + 1))
+        .sum()
+}
+
+fn parse_number(input: &str) -> IResult<&str, usize> {
+    map_res(digit1, |s: &str| s.parse::<usize>())(input)
+}
+
+fn parse_game(input: &str) -> IResult<&str, (&str, usize)> {
+    let (input, (hand, _, bid)) = tuple((alphanumeric1, space1, parse_number))(input)?;
+    Ok((input, (hand, bid)))
+}
+
+pub struct Solution;
+
+solver::register!(Solution);
+
+impl Puzzle for Solution {
+    type Input<'a> = Games;
+
+    fn year(&self) -> u16 {
+        2023
+    }
+
+    fn day(&self) -> u8 {
+        7
+    }
+
+    fn part1(&self, games: &Games) -> Result<Option<Answer>> {
+        let part1 = games.winnings();
+        Ok(Some(part1.into()))
+    }
+
+    // Hands by rank with their types, with jacks and with jokers
+    fn explain(&self, games: &Games) -> Result<Vec<Artifact>> {
+        let ranks = |ranked: Vec<&Game>, hand_type: fn(&Hand) -> HandType| {
+            ranked
+                .into_iter()
+                .enumerate()
+                .map(move |(i, game)| {
+                    format!(
+                        "{:>4}  {}  {:<12}  bid {:>4}",
+                        i + 1,
+                        game.hand,
+                        format!("{:?}", hand_type(&game.hand)),
+                        game.bid
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+        Ok(vec![
+            Artifact::new("ranks", ranks(games.ranked(), Hand::hand_type)),
+            Artifact::new(
+                "ranks with jokers",
+                ranks(games.ranked_with_joker(), Hand::joker_hand_type),
+            ),
+        ])
+    }
+
+    fn part2(&self, games: &Games) -> Result<Option<Answer>> {
+        let part2 = games.winnings_with_joker();
+        Ok(Some(part2.into()))
+    }
+
+    // Every hand has 5 cards and a bid
+    fn validate(&self, input: &str) -> Vec<Violation> {
+        let mut violations = vec![];
+        for (number, line) in validate::lines(input) {
+            let Some((hand, bid)) = line.split_once(' ') else {
+                violations.push(Violation::at(number, "expected a hand and a bid"));
+                continue;
+            };
+            if hand.len() != 5 || !hand.chars().all(|c| "23456789TJQKA".contains(c)) {
+                violations.push(Violation::at(
+                    number,
+                    format!("hand {:?} isn't 5 cards", hand),
+                ));
+            }
+            if bid.parse::<usize>().is_err() {
+                violations.push(Violation::at(
+                    number,
+                    format!("bid {:?} isn't a number", bid),
+                ));
+            }
+        }
+        violations
+    }
+
+    // Distinct hands of every type alike, rather than mostly high cards and
+    // pairs as dealt at random, and their bids. There are at most 200000
+    // hands, about half of the distinct ones.
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        const TYPES: [&[usize]; 7] = [
+            &[5],
+            &[4, 1],
+            &[3, 2],
+            &[3, 1, 1],
+            &[2, 2, 1],
+            &[2, 1, 1, 1],
+            &[1, 1, 1, 1, 1],
+        ];
+        let mut hands = HashSet::new();
+        let mut lines = vec![];
+        let len = (1000 * size).min(200_000);
+        while lines.len() < len {
+            let mut labels = b"23456789TJQKA".to_vec();
+            labels.shuffle(rng);
+            let mut hand = TYPES
+                .choose(rng)
+                .unwrap()
+                .iter()
+                .zip(labels)
+                .flat_map(|(&count, label)| [label].repeat(count))
+                .collect::<Vec<_>>();
+            hand.shuffle(rng);
+            if hands.insert(hand.clone()) {
+                let hand = String::from_utf8(hand).unwrap();
+                lines.push(format!("{} {}", hand, rng.gen_range(1..=1000)));
+            }
+        }
+        Some(lines.join("\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::InputStore;
+
+    #[test]
+    fn test_with_sample() -> Result<()> {
+        let input = &InputStore::default().sample(2023, 7)?;
+        assert!(Solution.validate(input).is_empty());
+        let games = Games::from_input(input)?;
+
+        let part1 = games.winnings();
+        assert_eq!(part1, 6440);
+
+        let part2 = games.winnings_with_joker();
+        assert_eq!(part2, 5905);
+
+        let artifacts = Solution.explain(&games)?;
+        assert_eq!(artifacts[0].lines[0], "   1  32T3K  OnePair       bid  765");
+        assert_eq!(artifacts[1].lines[4], "   5  KTJJT  FourOfAKind   bid  220");
+
+        let game = &games.games()[1];
+        assert_eq!(game.bid(), 684);
+        assert_eq!(game.hand().cards()[0], Card::Ten);
+        assert_eq!(game.hand().hand_type(), HandType::ThreeOfAKind);
+        assert_eq!(game.hand().joker_hand_type(), HandType::FourOfAKind);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_game() -> Result<()> {
+        let input = "32T3K 765";
+        let (input, (hand, bid)) = parse_game(input)?;
+        assert_eq!(input, "");
+        assert_eq!(hand, "32T3K");
+        assert_eq!(bid, 765);
+
+        Ok(())
+    }
+}
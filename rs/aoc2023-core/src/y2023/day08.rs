@@ -0,0 +1,459 @@
+use std::{collections::HashSet, fmt};
+
+use anyhow::Result;
+use nom::{
+    branch::alt,
+    bytes::complete::{tag, take_while_m_n},
+    character::{complete::newline, is_alphanumeric},
+    combinator::{map, map_res},
+    multi::{many1, separated_list1},
+    IResult,
+};
+use rand::{seq::SliceRandom, Rng as _};
+
+use crate::{
+    error,
+    generate::Rng,
+    int::{self, Int},
+    solver::{self, FromInput, Puzzle},
+    util::{graph::Graph, math},
+    validate::{self, Violation},
+    Answer, Artifact,
+};
+
+pub struct Solution;
+
+solver::register!(Solution);
+
+impl Puzzle for Solution {
+    type Input<'a> = Input;
+
+    fn year(&self) -> u16 {
+        2023
+    }
+
+    fn day(&self) -> u8 {
+        8
+    }
+
+    fn part1(&self, input: &Input) -> Result<Option<Answer>> {
+        let part1 = input.steps();
+        Ok(Some(part1.into()))
+    }
+
+    fn part2(&self, input: &Input) -> Result<Option<Answer>> {
+        let part2 = input.multi_steps::<u64>()?;
+        Ok(Some(part2.into()))
+    }
+
+    // Steps of each ghost to its first node ending with 'Z', the cycles
+    // whose least common multiple is the answer of part 2
+    fn explain(&self, input: &Input) -> Result<Vec<Artifact>> {
+        let len = input.instructions().len();
+        let ghosts = input.ghost_steps().into_iter().map(|(start, end, steps)| {
+            format!(
+                "{} -> {}: {:>6} steps, {:>4} times the instructions{}",
+                start,
+                end,
+                steps,
+                steps / len,
+                if steps % len == 0 { "" } else { " and some" }
+            )
+        });
+        Ok(vec![Artifact::new("ghosts", ghosts)])
+    }
+
+    // Instructions are lefts and rights, labels are 3 characters, and every
+    // node leads to nodes of the network, starting with AAA and ending with
+    // ZZZ
+    fn validate(&self, input: &str) -> Vec<Violation> {
+        let mut violations = vec![];
+        let blocks = validate::blocks(input);
+        let [instructions, nodes] = &blocks[..] else {
+            return vec![Violation::new("expected the instructions and the nodes")];
+        };
+        for &(number, line) in instructions {
+            if !line.chars().all(|c| c == 'L' || c == 'R') {
+                violations.push(Violation::at(number, "instructions aren't all L or R"));
+            }
+        }
+
+        let mut defined = HashSet::new();
+        let mut referenced = vec![];
+        for &(number, line) in nodes {
+            let labels = line
+                .split(|c: char| !c.is_ascii_alphanumeric())
+                .filter(|label| !label.is_empty())
+                .collect::<Vec<_>>();
+            let [node, left, right] = labels[..] else {
+                violations.push(Violation::at(number, "expected a node, left and right"));
+                continue;
+            };
+            for label in [node, left, right] {
+                if label.len() != 3 {
+                    violations.push(Violation::at(
+                        number,
+                        format!("label {:?} isn't 3 characters", label),
+                    ));
+                }
+            }
+            defined.insert(node);
+            referenced.extend([(number, left), (number, right)]);
+        }
+        for (number, label) in referenced {
+            if !defined.contains(label) {
+                violations.push(Violation::at(
+                    number,
+                    format!("node {} is undefined", label),
+                ));
+            }
+        }
+        for label in ["AAA", "ZZZ"] {
+            if !defined.contains(label) {
+                violations.push(Violation::new(format!("node {} is undefined", label)));
+            }
+        }
+        violations
+    }
+
+    // Instructions and a network of ghost paths, the first from AAA to ZZZ
+    // and the rest from other nodes ending in A to nodes ending in Z. Every
+    // path is a chain of layers of two nodes leading to either node of the
+    // next layer, so it reaches its end after as many steps as it has layers
+    // whatever the instructions, then starts over at its first layer. The
+    // steps of the paths are multiples of a common length by distinct primes,
+    // so all ghosts meet their ends before the steps overflow. Labels run out
+    // at about 40000 nodes, so larger networks stop growing there.
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        const MAX_NODES: usize = 30_000;
+        let instructions = (0..rng.gen_range(100..300) * size)
+            .map(|_| if rng.gen_bool(0.5) { 'L' } else { 'R' })
+            .collect::<String>();
+
+        let mut used = HashSet::from([Label::START, Label::END]);
+        let mut label = |rng: &mut Rng, last: Option<u8>| loop {
+            let mut label = [0; 3];
+            label.fill_with(|| *b"0123456789BCDEFGHIJKLMNOPQRSTUVWXY".choose(rng).unwrap());
+            if let Some(last) = last {
+                label[2] = last;
+            }
+            let label = Label(label);
+            if used.insert(label) {
+                return label;
+            }
+        };
+        let mut primes = [2, 3, 5, 7, 11, 13];
+        primes.shuffle(rng);
+        let primes = &primes[..rng.gen_range(3..=6)];
+        let len = (rng.gen_range(3..10) * size).min(MAX_NODES / 2 / primes.iter().sum::<usize>());
+        let mut nodes = vec![];
+        for (ghost, prime) in primes.iter().enumerate() {
+            let (start, end) = if ghost == 0 {
+                (Label::START, Label::END)
+            } else {
+                (label(rng, Some(b'A')), label(rng, Some(b'Z')))
+            };
+            let layers = (1..len * prime)
+                .map(|_| [label(rng, None), label(rng, None)])
+                .collect::<Vec<_>>();
+            let next = |rng: &mut Rng, layer: Option<&[Label; 2]>| {
+                let mut next = layer.map_or([end, end], |&layer| layer);
+                next.shuffle(rng);
+                next
+            };
+            let [left, right] = next(rng, layers.first());
+            nodes.push(Node {
+                name: start,
+                left,
+                right,
+            });
+            for (i, layer) in layers.iter().enumerate() {
+                for &name in layer {
+                    let [left, right] = next(rng, layers.get(i + 1));
+                    nodes.push(Node { name, left, right });
+                }
+            }
+            let [left, right] = next(rng, layers.first());
+            nodes.push(Node {
+                name: end,
+                left,
+                right,
+            });
+        }
+        nodes.shuffle(rng);
+        let nodes = nodes
+            .iter()
+            .map(|node| format!("{} = ({}, {})", node.name, node.left, node.right))
+            .collect::<Vec<_>>();
+        Some(format!("{}\n\n{}", instructions, nodes.join("\n")))
+    }
+}
+
+/// Direction of an instruction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Left,
+    Right,
+}
+
+#[derive(Debug)]
+struct Instruction(Vec<Direction>);
+
+/// Label of a node, three alphanumeric characters
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Label([u8; 3]);
+
+impl fmt::Display for Label {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", String::from_utf8_lossy(&self.0))
+    }
+}
+
+impl TryFrom<&[u8]> for Label {
+    type Error = anyhow::Error;
+
+    fn try_from(value: &[u8]) -> Result<Self> {
+        anyhow::ensure!(
+            value.len() == 3,
+            "Label '{:?}' must be 3 characters long",
+            value
+        );
+        let mut name = [0; 3];
+        name.copy_from_slice(value);
+        Ok(Label(name))
+    }
+}
+
+impl Label {
+    const START: Label = Label([b'A', b'A', b'A']);
+    const END: Label = Label([b'Z', b'Z', b'Z']);
+
+    pub fn as_bytes(&self) -> &[u8; 3] {
+        &self.0
+    }
+}
+
+/// Node of the network with the labels of the nodes to its left and right
+#[derive(Debug, Clone)]
+pub struct Node {
+    name: Label,
+    left: Label,
+    right: Label,
+}
+
+impl Node {
+    pub fn name(&self) -> Label {
+        self.name
+    }
+
+    pub fn left(&self) -> Label {
+        self.left
+    }
+
+    pub fn right(&self) -> Label {
+        self.right
+    }
+}
+
+/// Instructions and the network of nodes
+#[derive(Debug)]
+pub struct Input {
+    instruction: Instruction,
+    nodes: Vec<Node>,
+    // nodes numbered in the order of the input, with an edge to the left
+    // and the right node of each
+    network: Graph<Label, Direction>,
+}
+
+impl FromInput<'_> for Input {
+    fn from_input(s: &str) -> Result<Self> {
+        Ok(error::parse(Solution.day(), s.as_bytes(), parse_input)?)
+    }
+}
+
+impl Input {
+    pub fn instructions(&self) -> &[Direction] {
+        &self.instruction.0
+    }
+
+    /// Nodes in the order of the input
+    pub fn nodes(&self) -> &[Node] {
+        &self.nodes
+    }
+
+    pub fn node(&self, label: &Label) -> Option<&Node> {
+        self.nodes.get(self.network.index(label)?)
+    }
+
+    /// Network of the nodes, with edges to the left and right nodes
+    pub fn network(&self) -> &Graph<Label, Direction> {
+        &self.network
+    }
+
+    // Steps from the node `start` following the instructions until
+    // `is_end`, with the node reached
+    fn walk(&self, start: Label, is_end: impl Fn(Label) -> bool) -> (Label, usize) {
+        let mut steps = 0usize;
+        let mut node = self.network.index(&start).unwrap();
+        for direction in self.instruction.0.iter().cycle() {
+            (node, _) = *self
+                .network
+                .edges(node)
+                .iter()
+                .find(|(_, edge)| edge == direction)
+                .unwrap();
+            steps += 1;
+            let label = *self.network.label(node);
+            if is_end(label) {
+                return (label, steps);
+            }
+        }
+        unreachable!()
+    }
+
+    fn steps(&self) -> usize {
+        self.walk(Label::START, |label| label == Label::END).1
+    }
+
+    /// Steps until all paths from the nodes ending with 'A' are at nodes
+    /// ending with 'Z' at once, as the integer type `T`, failing if it doesn't
+    /// fit
+    pub fn multi_steps<T: Int>(&self) -> Result<T> {
+        let steps = self
+            .ghost_steps()
+            .into_iter()
+            .map(|(_, _, steps)| int::from_usize(steps))
+            .collect::<Result<Vec<T>>>()?;
+        math::lcm_of_set(steps)
+    }
+
+    // Start, end and steps of the path from each node ending with 'A' to the
+    // first node ending with 'Z'
+    fn ghost_steps(&self) -> Vec<(Label, Label, usize)> {
+        // starting points are all labels that end with 'A'
+        self.nodes
+            .iter()
+            .filter(|node| node.name.0[2] == b'A')
+            .map(|starting_node| {
+                let (end, steps) = self.walk(starting_node.name, |label| label.0[2] == b'Z');
+                (starting_node.name, end, steps)
+            })
+            .collect()
+    }
+}
+
+fn parse_label(input: &[u8]) -> IResult<&[u8], Label> {
+    map_res(take_while_m_n(3, 3, is_alphanumeric), Label::try_from)(input)
+}
+
+fn parse_node(input: &[u8]) -> IResult<&[u8], Node> {
+    let (input, name) = parse_label(input)?;
+    let (input, _) = tag(" = ")(input)?;
+    let (input, _) = tag("(")(input)?;
+    let (input, left) = parse_label(input)?;
+    let (input, _) = tag(", ")(input)?;
+    let (input, right) = parse_label(input)?;
+    let (input, _) = tag(")")(input)?;
+    Ok((input, Node { name, left, right }))
+}
+
+fn parse_direction(input: &[u8]) -> IResult<&[u8], Direction> {
+    alt((
+        map(tag("L"), |_| Direction::Left),
+        map(tag("R"), |_| Direction::Right),
+    ))(input)
+}
+
+fn parse_instruction(input: &[u8]) -> IResult<&[u8], Instruction> {
+    let (input, directions) = many1(parse_direction)(input)?;
+    Ok((input, Instruction(directions)))
+}
+
+fn parse_input(input: &[u8]) -> IResult<&[u8], Input> {
+    let (input, instruction) = parse_instruction(input)?;
+    let (input, _) = tag("\n\n")(input)?;
+    let (input, nodes) = separated_list1(newline, parse_node)(input)?;
+    let mut network = Graph::new();
+    for node in &nodes {
+        network.add_node(node.name);
+    }
+    for node in &nodes {
+        network.add_edge(node.name, node.left, Direction::Left);
+        network.add_edge(node.name, node.right, Direction::Right);
+    }
+    Ok((
+        input,
+        Input {
+            instruction,
+            nodes,
+            network,
+        },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use num_bigint::BigUint;
+
+    use super::*;
+    use crate::InputStore;
+
+    #[test]
+    fn test_with_sample() -> Result<()> {
+        let input = &InputStore::default().sample(2023, 8)?;
+        assert!(Solution.validate(input).is_empty());
+        let input = Input::from_input(input)?;
+        let part1 = input.steps();
+        assert_eq!(part1, 2);
+
+        let input = "LLR
+
+AAA = (BBB, BBB)
+BBB = (AAA, ZZZ)
+ZZZ = (ZZZ, ZZZ)";
+        let input = Input::from_input(input)?;
+        let part1 = input.steps();
+        assert_eq!(part1, 6);
+        assert_eq!(input.instructions().len(), 3);
+        let node = input.node(&input.nodes()[1].name()).unwrap();
+        assert_eq!(
+            (node.left().as_bytes(), node.right().as_bytes()),
+            (b"AAA", b"ZZZ")
+        );
+        // ZZZ leads to itself
+        assert_eq!(input.network().sccs().len(), 2);
+        assert!(input.network().has_cycle());
+
+        let input = "LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)";
+        let violations = Solution.validate(input);
+        assert_eq!(
+            violations,
+            [
+                Violation::new("node AAA is undefined"),
+                Violation::new("node ZZZ is undefined")
+            ]
+        );
+        let input = Input::from_input(input)?;
+        let part2 = input.multi_steps::<u64>()?;
+        assert_eq!(part2, 6);
+        assert_eq!(input.multi_steps::<BigUint>()?, BigUint::from(6u8));
+        assert_eq!(
+            Solution.explain(&input)?[0].lines,
+            [
+                "11A -> 11Z:      2 steps,    1 times the instructions",
+                "22A -> 22Z:      3 steps,    1 times the instructions and some"
+            ]
+        );
+
+        Ok(())
+    }
+}
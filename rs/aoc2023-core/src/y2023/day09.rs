@@ -0,0 +1,195 @@
+use anyhow::Result;
+use nom::{
+    character::complete::{char, digit1, newline, space1},
+    combinator::{map_res, recognize},
+    multi::separated_list1,
+    sequence::preceded,
+    IResult, Parser,
+};
+use rand::Rng as _;
+
+use crate::{
+    error,
+    generate::Rng,
+    log,
+    solver::{self, FromInput, Puzzle},
+    Answer,
+};
+
+/// History of a value of the oasis
+#[derive(Debug)]
+pub struct History(Vec<isize>);
+
+impl History {
+    /// Value extrapolated after the history
+    pub fn next_value(&self) -> isize {
+        let mut placeholders = vec![];
+        let mut deltas = self.0.clone();
+
+        loop {
+            // push the last delta
+            log::debug!("deltas: {:?}", deltas);
+            placeholders.push(deltas[deltas.len() - 1]);
+
+            // we're done if all deltas are 0
+            if deltas.iter().all(|&d| d == 0) {
+                break;
+            }
+
+            // otherwise, compute the next deltas
+            deltas = deltas.windows(2).map(|w| w[1] - w[0]).collect::<Vec<_>>();
+        }
+
+        placeholders.reverse();
+        log::debug!("placeholders: {:?}", placeholders);
+
+        placeholders.into_iter().fold(0, |delta, curr| {
+            let next_value = curr + delta;
+            log::debug!("next value: {}", curr + delta);
+            next_value
+        })
+    }
+
+    /// Value extrapolated before the history
+    pub fn previous_value(&self) -> isize {
+        let mut history = self.0.clone();
+        history.reverse();
+        History(history).next_value()
+    }
+}
+
+/// Histories of the values of the oasis
+#[derive(Debug)]
+pub struct Histories(Vec<History>);
+
+impl FromInput<'_> for Histories {
+    fn from_input(s: &str) -> Result<Self> {
+        Ok(error::parse(Solution.day(), s, parse_histories)?)
+    }
+}
+
+impl Histories {
+    /// Histories parsed one line at a time
+    pub fn stream(
+        lines: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> impl Iterator<Item = Result<History>> {
+        lines.into_iter().enumerate().map(|(idx, line)| {
+            Ok(error::parse_line(
+                Solution.day(),
+                idx + 1,
+                line.as_ref(),
+                parse_history,
+            )?)
+        })
+    }
+
+    fn next_values(&self) -> Vec<isize> {
+        self.0.iter().map(|h| h.next_value()).collect::<Vec<_>>()
+    }
+
+    fn sum(&self) -> isize {
+        self.next_values().iter().sum()
+    }
+
+    fn reverse_sum(&self) -> isize {
+        self.0.iter().map(History::previous_value).sum()
+    }
+}
+
+fn parse_isize(input: &str) -> IResult<&str, isize> {
+    let parse_negative = preceded(char('-'), digit1);
+    let parse_number = recognize(parse_negative.or(digit1));
+
+    map_res(parse_number, |s: &str| s.parse::<isize>())(input)
+}
+
+fn parse_history(input: &str) -> IResult<&str, History> {
+    let (input, history) = separated_list1(space1, parse_isize)(input)?;
+    Ok((input, History(history)))
+}
+
+fn parse_histories(input: &str) -> IResult<&str, Histories> {
+    let (input, histories) = separated_list1(newline, parse_history)(input)?;
+    Ok((input, Histories(histories)))
+}
+
+pub struct Solution;
+
+solver::register!(Solution);
+
+impl Puzzle for Solution {
+    type Input<'a> = Histories;
+
+    fn year(&self) -> u16 {
+        2023
+    }
+
+    fn day(&self) -> u8 {
+        9
+    }
+
+    fn part1(&self, histories: &Histories) -> Result<Option<Answer>> {
+        let part1 = histories.sum();
+        Ok(Some(part1.into()))
+    }
+
+    fn part2(&self, histories: &Histories) -> Result<Option<Answer>> {
+        let part2 = histories.reverse_sum();
+        Ok(Some(part2.into()))
+    }
+
+    // Histories of 21 values of polynomials of up to degree 15, so their
+    // differences reach zeros before running out. A polynomial is a sum of
+    // binomials C(x, k) with small coefficients, whose differences are sums
+    // of the binomials of degree one less, down to the constant.
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        let histories = (0..200 * size).map(|_| {
+            let coefficients = (0..=rng.gen_range(1..=15))
+                .map(|_| rng.gen_range(-9..=9))
+                .collect::<Vec<isize>>();
+            (0..21)
+                .map(|x| {
+                    let mut binomial = 1;
+                    let mut value = 0;
+                    for (k, c) in coefficients.iter().enumerate() {
+                        value += c * binomial;
+                        binomial = binomial * (x - k as isize) / (k as isize + 1);
+                    }
+                    value.to_string()
+                })
+                .collect::<Vec<_>>()
+                .join(" ")
+        });
+        Some(histories.collect::<Vec<_>>().join("\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::InputStore;
+
+    #[test]
+    fn test_with_sample_day09() -> Result<()> {
+        let input = &InputStore::default().sample(2023, 9)?;
+        let histories = Histories::from_input(input)?;
+        let next_values = histories
+            .0
+            .iter()
+            .map(|h| h.next_value())
+            .collect::<Vec<_>>();
+        assert_eq!(next_values, vec![18, 28, 68]);
+
+        let part1 = histories.sum();
+        assert_eq!(part1, 114);
+
+        let part2 = histories.reverse_sum();
+        assert_eq!(part2, 2);
+
+        let previous_values = Histories::stream(input.lines())
+            .map(|h| h.map(|h| h.previous_value()))
+            .collect::<Result<Vec<_>>>()?;
+        assert_eq!(previous_values, vec![-3, 0, 5]);
+        Ok(())
+    }
+}
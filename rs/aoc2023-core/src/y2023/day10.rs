@@ -0,0 +1,391 @@
+use anyhow::Result;
+use rand::Rng as _;
+
+use crate::{
+    generate::{self, Rng},
+    log,
+    solver::{self, FromInput, Puzzle},
+    util::{pathfind, polygon},
+    validate::{self, Violation},
+    Answer,
+};
+
+// Grid that operates on a 2D array of tiles as:
+// - Move left is x - 1
+// - Move right is x + 1
+// - Move up is y - 1
+// - Move down is y + 1
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Pos(usize, usize);
+
+impl Pos {
+    // Position next to this one in direction `dir`, if it's not off the top
+    // or left of the sketch
+    fn step(&self, dir: Direction) -> Option<Self> {
+        let &Pos(x, y) = self;
+        Some(match dir {
+            Direction::North => Pos(x, y.checked_sub(1)?),
+            Direction::South => Pos(x, y + 1),
+            Direction::East => Pos(x + 1, y),
+            Direction::West => Pos(x.checked_sub(1)?, y),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    North,
+    South,
+    East,
+    West,
+}
+
+impl Direction {
+    const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::South,
+        Direction::East,
+        Direction::West,
+    ];
+
+    fn reverse(self) -> Self {
+        match self {
+            Direction::North => Direction::South,
+            Direction::South => Direction::North,
+            Direction::East => Direction::West,
+            Direction::West => Direction::East,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tile {
+    Vertical,   // |
+    Horizontal, // -
+    NorthEast,  // L
+    NorthWest,  // J
+    SouthWest,  // 7
+    SouthEast,  // F
+    Ground,     // .
+    Start,      // S
+}
+
+impl TryFrom<u8> for Tile {
+    type Error = anyhow::Error;
+
+    fn try_from(value: u8) -> Result<Self> {
+        match value {
+            b'|' => Ok(Self::Vertical),
+            b'-' => Ok(Self::Horizontal),
+            b'L' => Ok(Self::NorthEast),
+            b'J' => Ok(Self::NorthWest),
+            b'7' => Ok(Self::SouthWest),
+            b'F' => Ok(Self::SouthEast),
+            b'.' => Ok(Self::Ground),
+            b'S' => Ok(Self::Start),
+            _ => Err(anyhow::anyhow!("invalid tile: {}", value)),
+        }
+    }
+}
+
+impl Tile {
+    // Directions the tile connects to; the start connects to whatever pipes
+    // lead to it
+    fn exits(self) -> &'static [Direction] {
+        match self {
+            Tile::Vertical => &[Direction::North, Direction::South],
+            Tile::Horizontal => &[Direction::East, Direction::West],
+            Tile::NorthEast => &[Direction::North, Direction::East],
+            Tile::NorthWest => &[Direction::North, Direction::West],
+            Tile::SouthWest => &[Direction::South, Direction::West],
+            Tile::SouthEast => &[Direction::South, Direction::East],
+            Tile::Ground => &[],
+            Tile::Start => &Direction::ALL,
+        }
+    }
+}
+
+/// Sketch of the field of pipes
+#[derive(Debug)]
+pub struct Sketch {
+    tiles: Vec<Vec<Tile>>,
+    start: Pos,
+}
+
+impl FromInput<'_> for Sketch {
+    fn from_input(s: &str) -> Result<Self> {
+        let tiles = s
+            .as_bytes()
+            .split(|&b| b == b'\n')
+            .map(|line| {
+                line.iter()
+                    .map(|&b| Tile::try_from(b))
+                    .collect::<Result<Vec<_>>>()
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Sketch::new(tiles))
+    }
+}
+
+impl Sketch {
+    fn new(tiles: Vec<Vec<Tile>>) -> Self {
+        let start = tiles
+            .iter()
+            .enumerate()
+            .find_map(|(y, row)| {
+                row.iter()
+                    .enumerate()
+                    .find_map(|(x, &tile)| (tile == Tile::Start).then_some(Pos(x, y)))
+            })
+            .expect("no start tile found");
+        Self { tiles, start }
+    }
+
+    fn get(&self, pos: &Pos) -> Option<Tile> {
+        let &Pos(x, y) = pos;
+        self.tiles.get(y).and_then(|row| row.get(x)).copied()
+    }
+
+    // Positions of the pipes connected to the one at `pos`, each of both
+    // leading to the other
+    fn connected(&self, pos: &Pos) -> Vec<Pos> {
+        let Some(tile) = self.get(pos) else {
+            return vec![];
+        };
+        tile.exits()
+            .iter()
+            .filter_map(|&dir| {
+                let next = pos.step(dir)?;
+                let exits = self.get(&next)?.exits();
+                exits.contains(&dir.reverse()).then_some(next)
+            })
+            .collect()
+    }
+
+    /// Steps along the loop from the start to the position farthest from it
+    pub fn farthest_steps(&self) -> usize {
+        let paths = pathfind::bfs(self.start, |pos| self.connected(pos));
+        let (pos, steps) = paths.farthest().expect("start is always reached");
+        log::debug!("farthest from the start at {:?}, {} steps", pos, steps);
+        steps
+    }
+
+    // Positions of the loop through the start, in order from it
+    fn main_loop(&self) -> Vec<Pos> {
+        let mut path = vec![self.start];
+        let (mut prev, mut pos) = (self.start, self.connected(&self.start)[0]);
+        while pos != self.start {
+            path.push(pos);
+            let next = self
+                .connected(&pos)
+                .into_iter()
+                .find(|&next| next != prev)
+                .expect("the loop goes on from every pipe");
+            (prev, pos) = (pos, next);
+        }
+        path
+    }
+
+    /// Tiles enclosed by the loop, by Pick's theorem: the loop is a polygon
+    /// with a vertex at each of its pipes
+    pub fn enclosed(&self) -> u64 {
+        let vertices = self
+            .main_loop()
+            .into_iter()
+            .map(|Pos(x, y)| (x as i64, y as i64))
+            .collect::<Vec<_>>();
+        polygon::interior_points(&vertices)
+    }
+}
+
+pub struct Solution;
+
+solver::register!(Solution);
+
+impl Puzzle for Solution {
+    type Input<'a> = Sketch;
+
+    fn year(&self) -> u16 {
+        2023
+    }
+
+    fn day(&self) -> u8 {
+        10
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Self::Input<'a>> {
+        let sketch = Sketch::from_input(input)?;
+        log::debug!("{:?}", sketch);
+        Ok(sketch)
+    }
+
+    fn part1(&self, sketch: &Sketch) -> Result<Option<Answer>> {
+        let part1 = sketch.farthest_steps();
+        Ok(Some(part1.into()))
+    }
+
+    fn part2(&self, sketch: &Sketch) -> Result<Option<Answer>> {
+        let part2 = sketch.enclosed();
+        Ok(Some(part2.into()))
+    }
+
+    // The sketch is a rectangle of pipes and ground with a single start
+    fn validate(&self, input: &str) -> Vec<Violation> {
+        let mut violations = validate::grid(validate::lines(input), |c| "|-LJ7F.S".contains(c));
+        let starts = input.matches('S').count();
+        if starts != 1 {
+            violations.push(Violation::new(format!("{} starts, not 1", starts)));
+        }
+        violations
+    }
+
+    // A loop of pipes around a random blob of cells between the tiles, among
+    // junk pipes that don't lead to the start
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        let side = 40 * size;
+        let blob = blob(rng, side - 1);
+        let inside = |x: usize, y: usize, dx: usize, dy: usize| {
+            // cell at (x + dx - 1, y + dy - 1), one of the four around the tile at
+            // (x, y)
+            let (Some(x), Some(y)) = ((x + dx).checked_sub(1), (y + dy).checked_sub(1)) else {
+                return false;
+            };
+            blob.get(y)
+                .and_then(|row| row.get(x))
+                .copied()
+                .unwrap_or(false)
+        };
+        let mut tiles = generate::tiles(
+            rng,
+            side,
+            side,
+            &[
+                (b'.', 4),
+                (b'|', 1),
+                (b'-', 1),
+                (b'L', 1),
+                (b'J', 1),
+                (b'7', 1),
+                (b'F', 1),
+            ],
+        );
+        let mut pipes = vec![];
+        for (y, row) in tiles.iter_mut().enumerate() {
+            for (x, tile) in row.iter_mut().enumerate() {
+                // pipes follow the sides between cells in and out of the blob
+                let north = inside(x, y, 0, 0) != inside(x, y, 1, 0);
+                let south = inside(x, y, 0, 1) != inside(x, y, 1, 1);
+                let west = inside(x, y, 0, 0) != inside(x, y, 0, 1);
+                let east = inside(x, y, 1, 0) != inside(x, y, 1, 1);
+                *tile = match (north, south, west, east) {
+                    (true, true, false, false) => b'|',
+                    (false, false, true, true) => b'-',
+                    (true, false, false, true) => b'L',
+                    (true, false, true, false) => b'J',
+                    (false, true, true, false) => b'7',
+                    (false, true, false, true) => b'F',
+                    _ => continue,
+                };
+                pipes.push((x, y));
+            }
+        }
+        let (x, y) = pipes[rng.gen_range(0..pipes.len())];
+        tiles[y][x] = b'S';
+        for (x, y) in [
+            (x + 1, y),
+            (x.wrapping_sub(1), y),
+            (x, y + 1),
+            (x, y.wrapping_sub(1)),
+        ] {
+            if let Some(tile) = tiles.get_mut(y).and_then(|row| row.get_mut(x)) {
+                if !pipes.contains(&(x, y)) {
+                    *tile = b'.';
+                }
+            }
+        }
+        Some(generate::grid(&tiles))
+    }
+}
+
+// Blob of about a third of `size` by `size` cells, grown from the middle one
+// neighbor at a time. A neighbor is only added if the blob keeps without
+// holes, and without cells touching only by their corners, so its outline is
+// a single loop.
+fn blob(rng: &mut Rng, size: usize) -> Vec<Vec<bool>> {
+    let mut blob = vec![vec![false; size]; size];
+    blob[size / 2][size / 2] = true;
+    let mut cells = 1;
+    while cells < size * size / 3 {
+        let (x, y) = (rng.gen_range(0..size), rng.gen_range(0..size));
+        if !blob[y][x] && can_grow(&blob, x, y) {
+            blob[y][x] = true;
+            cells += 1;
+        }
+    }
+    blob
+}
+
+// Whether the cell at (x, y) is next to `blob` and adding it keeps the blob
+// without holes, and without cells touching only by their corners. The cells
+// around it that are out of the blob must be a single run of the ring of its
+// 8 neighbors, so they stay connected along the ring once the cell is added.
+// That turns down some cells that would do, but needs no search of the blob.
+fn can_grow(blob: &[Vec<bool>], x: usize, y: usize) -> bool {
+    // neighbors clockwise from the north
+    const RING: [(isize, isize); 8] = [
+        (0, -1),
+        (1, -1),
+        (1, 0),
+        (1, 1),
+        (0, 1),
+        (-1, 1),
+        (-1, 0),
+        (-1, -1),
+    ];
+    let ring = RING.map(|(dx, dy)| {
+        let (x, y) = (x.wrapping_add_signed(dx), y.wrapping_add_signed(dy));
+        blob.get(y)
+            .and_then(|row| row.get(x))
+            .copied()
+            .unwrap_or(false)
+    });
+    let next = (0..8).step_by(2).any(|i| ring[i]);
+    // a corner in the blob must share a side in the blob with the cell
+    let touching = (1..8)
+        .step_by(2)
+        .all(|i| !ring[i] || ring[i - 1] || ring[(i + 1) % 8]);
+    let runs = (0..8).filter(|&i| !ring[i] && ring[(i + 7) % 8]).count();
+    next && touching && runs == 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::InputStore;
+
+    #[test]
+    fn test_with_sample_day09() -> Result<()> {
+        let input = &InputStore::default().sample(2023, 10)?;
+        assert!(Solution.validate(input).is_empty());
+        assert_eq!(Sketch::from_input(input)?.farthest_steps(), 8);
+        assert_eq!(Sketch::from_input(input)?.enclosed(), 1);
+        let enclosing = "...........\n\
+                         .S-------7.\n\
+                         .|F-----7|.\n\
+                         .||.....||.\n\
+                         .||.....||.\n\
+                         .|L-7.F-J|.\n\
+                         .|..|.|..|.\n\
+                         .L--J.L--J.\n\
+                         ...........";
+        assert_eq!(Sketch::from_input(enclosing)?.enclosed(), 4);
+        assert_eq!(
+            Solution.validate("S-7\n|.|\nL-JS"),
+            [
+                Violation::at(3, "row is 4 tiles wide, not 3 like the first row"),
+                Violation::new("2 starts, not 1")
+            ]
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,268 @@
+use core::fmt;
+
+use anyhow::{Context, Result};
+use itertools::Itertools;
+use rand::Rng as _;
+
+use crate::{
+    generate::{self, Rng},
+    log,
+    solver::{self, FromInput, Puzzle},
+    util::sparse::SparseGrid,
+    validate::{self, Violation},
+    Answer, FromParams, Params,
+};
+
+// Universe is a 2D grid of galaxies `[Galaxy]`.
+// `[Galaxy]`` is a point in the grid encoding using its `(x, y)` coordinate.
+// Moving horizontally to the right, incr x coordinate by 1.
+// Moving vertically down, incr y coordinate by 1.
+// Top left of the universe is (0, 0), borrow right is (max_x, max_y).
+
+/// Parameters of the puzzle, e.g. `Options::PART2.expansion(10)?`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+    expansion: usize,
+}
+
+impl Options {
+    pub const PART1: Options = Options { expansion: 2 };
+    pub const PART2: Options = Options {
+        expansion: 1_000_000,
+    };
+
+    /// Number of rows or columns each empty row or column expands to, at
+    /// least 1
+    pub fn expansion(self, expansion: usize) -> Result<Self> {
+        anyhow::ensure!(expansion > 0, "expansion must be at least 1, got 0");
+        Ok(Options { expansion })
+    }
+}
+
+// Parameters set the options of part 2
+impl FromParams for Options {
+    const KEYS: &'static [&'static str] = &["expansion"];
+
+    fn defaults() -> Self {
+        Options::PART2
+    }
+
+    fn set(&mut self, key: &str, value: &str) -> Result<()> {
+        match key {
+            "expansion" => *self = self.expansion(value.parse()?)?,
+            _ => anyhow::bail!("unknown option {}", key),
+        }
+        Ok(())
+    }
+}
+
+/// Galaxy at `(x, y)`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Galaxy(pub usize, pub usize);
+
+impl Galaxy {
+    /// Manhattan distance to `other`
+    pub fn shortest_distance(&self, other: &Self) -> usize {
+        let Galaxy(x1, y1) = self;
+        let Galaxy(x2, y2) = other;
+        (x1.max(x2) - x1.min(x2)) + (y1.max(y2) - y1.min(y2))
+    }
+}
+
+impl fmt::Display for Galaxy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#")
+    }
+}
+
+/// Image of the galaxies in the universe
+#[derive(Debug, Clone)]
+pub struct Universe {
+    image: SparseGrid<Galaxy>,
+    // options of part 2, the puzzle's unless configured otherwise
+    part2: Options,
+}
+
+impl FromInput<'_> for Universe {
+    fn from_input(s: &str) -> Result<Self> {
+        let galaxies = s
+            .lines()
+            .enumerate()
+            .flat_map(|(y, line)| {
+                line.chars()
+                    .enumerate()
+                    .filter_map(|(x, c)| (c == '#').then_some(Galaxy(x, y)))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        Ok(Universe::new(galaxies))
+    }
+}
+
+impl fmt::Display for Universe {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.image)
+    }
+}
+
+impl Universe {
+    pub fn new(galaxies: Vec<Galaxy>) -> Self {
+        let image = galaxies
+            .into_iter()
+            .map(|galaxy| ((galaxy.1 as i64, galaxy.0 as i64), galaxy))
+            .collect();
+        Universe {
+            image,
+            part2: Options::PART2,
+        }
+    }
+
+    /// Galaxies, in no particular order
+    pub fn galaxies(&self) -> impl Iterator<Item = &Galaxy> {
+        self.image.iter().map(|(_, galaxy)| galaxy)
+    }
+
+    /// Expand every empty row and column to `expansion` of them, at least 1
+    pub fn expand(&mut self, expansion: usize) -> Result<()> {
+        let extra = expansion
+            .checked_sub(1)
+            .context("expansion must be at least 1, got 0")?;
+        self.shift(extra);
+        Ok(())
+    }
+
+    // Push the galaxies `extra` rows or columns further for every empty row
+    // or column before them
+    fn shift(&mut self, extra: usize) {
+        let empty_rows = self.image.empty_rows().collect::<Vec<_>>();
+        let empty_cols = self.image.empty_cols().collect::<Vec<_>>();
+        log::debug!("empty rows: {:?}", empty_rows);
+        log::debug!("empty cols: {:?}", empty_cols);
+
+        let offset = |empty: &[i64], n: usize| empty.partition_point(|&m| m < n as i64) * extra;
+        let galaxies = self
+            .galaxies()
+            .map(|Galaxy(x, y)| Galaxy(x + offset(&empty_cols, *x), y + offset(&empty_rows, *y)))
+            .collect();
+        *self = Universe {
+            part2: self.part2,
+            ..Universe::new(galaxies)
+        };
+    }
+
+    /// Sum of the distances between each pair of galaxies once the universe
+    /// is expanded
+    pub fn solve(&self, options: &Options) -> usize {
+        let mut universe = self.clone();
+        // options only expand by at least 1
+        universe.shift(options.expansion - 1);
+        universe.sum_of_shortest_distance()
+    }
+
+    /// Sum of the distances between each pair of galaxies
+    pub fn sum_of_shortest_distance(&self) -> usize {
+        self.galaxies()
+            .combinations(2)
+            .map(|galaxies| {
+                log::debug!("galaxies: {:?}", galaxies);
+                let (g1, g2) = (galaxies[0], galaxies[1]);
+                g1.shortest_distance(g2)
+            })
+            .sum::<usize>()
+    }
+}
+
+pub struct Solution;
+
+solver::register!(Solution);
+
+impl Puzzle for Solution {
+    type Input<'a> = Universe;
+
+    fn year(&self) -> u16 {
+        2023
+    }
+
+    fn day(&self) -> u8 {
+        11
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Self::Input<'a>> {
+        let universe = Universe::from_input(input)?;
+        log::debug!("universe:\n{}", universe);
+        Ok(universe)
+    }
+
+    fn configure(&self, universe: &mut Universe, params: &Params) -> Result<()> {
+        universe.part2 = params.options(self.day())?;
+        Ok(())
+    }
+
+    fn part1(&self, universe: &Universe) -> Result<Option<Answer>> {
+        let part1 = universe.solve(&Options::PART1);
+        Ok(Some(part1.into()))
+    }
+
+    fn part2(&self, universe: &Universe) -> Result<Option<Answer>> {
+        let part2 = universe.solve(&universe.part2);
+        Ok(Some(part2.into()))
+    }
+
+    // The image is a rectangle of galaxies and empty space
+    fn validate(&self, input: &str) -> Vec<Violation> {
+        validate::grid(validate::lines(input), |c| ".#".contains(c))
+    }
+
+    // Sparse galaxies, with about a tenth of the rows and columns empty to
+    // expand
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        let side = 140 * size;
+        let mut image = generate::tiles(rng, side, side, &[(b'.', 30), (b'#', 1)]);
+        for _ in 0..side / 10 {
+            let (row, col) = (rng.gen_range(0..side), rng.gen_range(0..side));
+            image[row].fill(b'.');
+            image.iter_mut().for_each(|tiles| tiles[col] = b'.');
+        }
+        Some(generate::grid(&image))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::InputStore;
+
+    #[test]
+    fn test_with_sample_day09() -> Result<()> {
+        let input = &InputStore::default().sample(2023, 11)?;
+        assert!(Solution.validate(input).is_empty());
+        let universe = Universe::from_input(input)?;
+        assert_eq!(universe.solve(&Options::PART1), 374);
+        assert_eq!(universe.solve(&Options::PART2.expansion(10)?), 1030);
+        assert_eq!(universe.solve(&Options::PART2.expansion(100)?), 8410);
+        assert_eq!(universe.solve(&Options::PART2), 82000210);
+        Ok(())
+    }
+
+    #[test]
+    fn test_configured_expansion() -> Result<()> {
+        let input = &InputStore::default().sample(2023, 11)?;
+        let run = |params: &Params| -> Result<Option<Answer>> {
+            crate::Solver::parse_with(&Solution, input, params)?.part2()
+        };
+        assert_eq!(
+            run(&Params::new().with("expansion", "10"))?,
+            Some(1030.into())
+        );
+        assert!(run(&Params::new().with("expansion", "0")).is_err());
+        assert!(run(&Params::new().with("expansion", "-1")).is_err());
+
+        assert!(Options::PART1.expansion(0).is_err());
+        let mut universe = Universe::from_input(input)?;
+        assert!(universe.expand(0).is_err());
+        universe.expand(2)?;
+        assert_eq!(universe.sum_of_shortest_distance(), 374);
+        Ok(())
+    }
+}
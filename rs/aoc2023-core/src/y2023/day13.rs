@@ -0,0 +1,426 @@
+use core::fmt;
+
+use anyhow::Result;
+use rand::{seq::SliceRandom, Rng as _};
+
+use crate::{
+    generate::{self, Rng},
+    log,
+    solver::{self, FromInput, Puzzle},
+    util::grid::Grid,
+    validate::{self, Violation},
+    Answer, Artifact, FromParams, Params,
+};
+
+/// Parameters of the puzzle, e.g. `Options::PART1.smudges(2)`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+    smudges: usize,
+}
+
+impl Options {
+    pub const PART1: Options = Options { smudges: 0 };
+    pub const PART2: Options = Options { smudges: 1 };
+
+    /// Number of entries of a pattern that differ from their reflection
+    pub fn smudges(self, smudges: usize) -> Self {
+        Options { smudges }
+    }
+}
+
+// Parameters set the options of part 2
+impl FromParams for Options {
+    const KEYS: &'static [&'static str] = &["smudges"];
+
+    fn defaults() -> Self {
+        Options::PART2
+    }
+
+    fn set(&mut self, key: &str, value: &str) -> Result<()> {
+        match key {
+            "smudges" => *self = self.smudges(value.parse()?),
+            _ => anyhow::bail!("unknown option {}", key),
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Entry {
+    Ash,
+    Rock,
+}
+
+impl TryFrom<u8> for Entry {
+    type Error = anyhow::Error;
+
+    fn try_from(value: u8) -> Result<Self> {
+        Ok(match value {
+            b'#' => Entry::Rock,
+            b'.' => Entry::Ash,
+            _ => anyhow::bail!("Invalid entry: {}", value),
+        })
+    }
+}
+
+impl fmt::Display for Entry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Entry::Ash => write!(f, "."),
+            Entry::Rock => write!(f, "#"),
+        }
+    }
+}
+
+// Pattern grid
+// Update x-coordinate to move down/up
+// Update y-coordinate to move right/left
+#[derive(Debug)]
+struct Pattern {
+    grid: Grid<Entry>,
+}
+
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} x {}:", self.grid.rows(), self.grid.cols())?;
+        write!(f, "{}", self.grid)
+    }
+}
+
+impl TryFrom<&[u8]> for Pattern {
+    type Error = anyhow::Error;
+
+    fn try_from(value: &[u8]) -> Result<Self> {
+        let grid = Grid::parse(value, Entry::try_from)?;
+        anyhow::ensure!(grid.rows() > 0, "empty pattern");
+        Ok(Pattern { grid })
+    }
+}
+
+impl Pattern {
+    // Whether there's a vertical reflection line between column `mid` and
+    // `mid + 1`, with exactly `smudges` entries differing from their
+    // reflection
+    fn is_vertical_reflection_line_at(&self, mid: usize, smudges: usize) -> bool {
+        // NB mid and mid + 1 must be valid
+        assert!(mid + 1 < self.grid.cols());
+        let mut different = 0;
+        for (ub1, lb2) in (0..=mid).rev().zip(mid + 1..self.grid.cols()) {
+            for row in 0..self.grid.rows() {
+                if self.grid[(row, ub1)] != self.grid[(row, lb2)] {
+                    different += 1;
+                }
+            }
+
+            // short circuit
+            if different > smudges {
+                return false;
+            }
+        }
+        different == smudges
+    }
+
+    // First reflection line between `lb` and `ub` by the search of the
+    // middle, then the half after it, then the half before it
+    fn vertical_reflection_line(&self, lb: usize, ub: usize, smudges: usize) -> Option<usize> {
+        // halves still to search, the next on top
+        let mut halves = vec![(lb, ub)];
+        while let Some((lb, ub)) = halves.pop() {
+            if lb >= ub {
+                continue;
+            }
+            let mid = (lb + ub) / 2;
+            if self.is_vertical_reflection_line_at(mid, smudges) {
+                return Some(mid);
+            }
+            halves.push((lb, mid));
+            halves.push((mid + 1, ub));
+        }
+        None
+    }
+
+    fn vertical_line(&self, smudges: usize) -> Option<usize> {
+        log::debug!("exploring vertical reflection line");
+        let res = self.vertical_reflection_line(0, self.grid.cols() - 1, smudges);
+        log::debug!("found vertical reflection line: {:?}", res);
+        res
+    }
+
+    // Whether there's a horizontal reflection line between row `mid` and
+    // `mid + 1`, with exactly `smudges` entries differing from their
+    // reflection
+    fn is_horizontal_reflection_line_at(&self, mid: usize, smudges: usize) -> bool {
+        log::debug!("checking horizontal reflection line at {}", mid);
+        // NB mid and mid + 1 must be valid
+        assert!(mid + 1 < self.grid.rows());
+        let mut different = 0;
+        for (ub1, lb2) in (0..=mid).rev().zip(mid + 1..self.grid.rows()) {
+            for col in 0..self.grid.cols() {
+                if self.grid[(ub1, col)] != self.grid[(lb2, col)] {
+                    different += 1;
+                }
+            }
+
+            // short circuit
+            if different > smudges {
+                log::debug!("short circuit");
+                return false;
+            }
+        }
+        different == smudges
+    }
+
+    // First reflection line between `lb` and `ub` by the search of the
+    // middle, then the half after it, then the half before it
+    fn horizontal_reflection_line(&self, lb: usize, ub: usize, smudges: usize) -> Option<usize> {
+        // halves still to search, the next on top
+        let mut halves = vec![(lb, ub)];
+        while let Some((lb, ub)) = halves.pop() {
+            if lb >= ub {
+                continue;
+            }
+            let mid = (lb + ub) / 2;
+            if self.is_horizontal_reflection_line_at(mid, smudges) {
+                return Some(mid);
+            }
+            halves.push((lb, mid));
+            halves.push((mid + 1, ub));
+        }
+        None
+    }
+
+    fn horizontal_line(&self, smudges: usize) -> Option<usize> {
+        log::debug!("exploring horizontal reflection line");
+        let res = self.horizontal_reflection_line(0, self.grid.rows() - 1, smudges);
+        log::debug!("found horizontal reflection line: {:?}", res);
+        res
+    }
+
+    // Number of columns left of the vertical reflection line, plus 100 times
+    // the number of rows above the horizontal one
+    fn summarize(&self, smudges: usize) -> usize {
+        log::debug!("pattern:\n{}", self);
+        let mut sum = 0;
+        if let Some(mid) = self.vertical_line(smudges) {
+            log::debug!(
+                "vertical reflection line between column: {} and {}, with {} columns to left",
+                mid,
+                mid + 1,
+                mid + 1
+            );
+
+            sum += mid + 1;
+        }
+
+        if let Some(mid) = self.horizontal_line(smudges) {
+            log::debug!(
+                "horizontal reflection line between row: {} and {}, with {} row to top",
+                mid,
+                mid + 1,
+                mid + 1
+            );
+
+            sum += 100 * (mid + 1);
+        }
+        sum
+    }
+}
+
+/// Patterns of ash and rocks
+#[derive(Debug)]
+pub struct Patterns {
+    patterns: Vec<Pattern>,
+    // options of part 2, the puzzle's unless configured otherwise
+    part2: Options,
+}
+
+impl FromInput<'_> for Patterns {
+    fn from_input(s: &str) -> Result<Self> {
+        let patterns = s
+            .split("\n\n")
+            .map(|s| s.as_bytes())
+            .map(Pattern::try_from)
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Patterns {
+            patterns,
+            part2: Options::PART2,
+        })
+    }
+}
+
+impl Patterns {
+    /// Sum of the summaries of the reflection lines of all patterns
+    pub fn solve(&self, options: &Options) -> usize {
+        self.patterns
+            .iter()
+            .map(|pattern| pattern.summarize(options.smudges))
+            .sum()
+    }
+}
+
+pub struct Solution;
+
+solver::register!(Solution);
+
+impl Puzzle for Solution {
+    type Input<'a> = Patterns;
+
+    fn year(&self) -> u16 {
+        2023
+    }
+
+    fn day(&self) -> u8 {
+        13
+    }
+
+    fn configure(&self, patterns: &mut Patterns, params: &Params) -> Result<()> {
+        patterns.part2 = params.options(self.day())?;
+        Ok(())
+    }
+
+    fn part1(&self, patterns: &Patterns) -> Result<Option<Answer>> {
+        let part1 = patterns.solve(&Options::PART1);
+        Ok(Some(part1.into()))
+    }
+
+    fn part2(&self, patterns: &Patterns) -> Result<Option<Answer>> {
+        let part2 = patterns.solve(&patterns.part2);
+        Ok(Some(part2.into()))
+    }
+
+    // Reflection lines of each pattern, without and with a smudge, between
+    // the columns or rows counted from 1
+    fn explain(&self, patterns: &Patterns) -> Result<Vec<Artifact>> {
+        let line = |pattern: &Pattern, smudges| {
+            let vertical = pattern
+                .vertical_line(smudges)
+                .map(|mid| format!("columns {}|{}", mid + 1, mid + 2));
+            let horizontal = pattern
+                .horizontal_line(smudges)
+                .map(|mid| format!("rows {}|{}", mid + 1, mid + 2));
+            let lines = vertical.into_iter().chain(horizontal).collect::<Vec<_>>();
+            if lines.is_empty() {
+                "none".to_string()
+            } else {
+                lines.join(", ")
+            }
+        };
+        let lines = patterns.patterns.iter().enumerate().map(|(i, pattern)| {
+            format!(
+                "{:>3} ({:>2}x{:<2}): {:<13}  smudged: {}",
+                i + 1,
+                pattern.grid.rows(),
+                pattern.grid.cols(),
+                line(pattern, Options::PART1.smudges),
+                line(pattern, patterns.part2.smudges)
+            )
+        });
+        Ok(vec![Artifact::new("reflection lines", lines)])
+    }
+
+    // Every pattern is a rectangle of ash and rocks
+    fn validate(&self, input: &str) -> Vec<Violation> {
+        let patterns = validate::blocks(input);
+        if patterns.is_empty() {
+            return vec![Violation::new("no patterns")];
+        }
+        patterns
+            .into_iter()
+            .flat_map(|pattern| validate::grid(pattern, |c| ".#".contains(c)))
+            .collect()
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        let patterns = (0..100 * size).map(|_| loop {
+            if let Some(pattern) = reflected(rng) {
+                break pattern;
+            }
+        });
+        Some(patterns.collect::<Vec<_>>().join("\n\n"))
+    }
+}
+
+// Pattern with a single line of reflection, and a single other line of
+// reflection with one smudge, if the pattern drawn has no more. It's drawn
+// reflected across both a vertical and a horizontal line, then one entry is
+// flipped where only the horizontal line reflects it, and it's turned on its
+// side or not.
+fn reflected(rng: &mut Rng) -> Option<String> {
+    let (rows, cols) = (rng.gen_range(5..=17), rng.gen_range(5..=17));
+    // lines between column `col` and the next, and row `row` and the next
+    let (col, row) = (rng.gen_range(0..cols - 1), rng.gen_range(0..rows - 1));
+    let mirror = |i: usize, line: usize, len: usize| {
+        (2 * line + 1).checked_sub(i).filter(|&mirror| mirror < len)
+    };
+    let entries = generate::tiles(rng, rows, cols, &[(b'.', 1), (b'#', 1)]);
+    let mut grid = vec![vec![b'.'; cols]; rows];
+    for (y, grid_row) in grid.iter_mut().enumerate() {
+        for (x, entry) in grid_row.iter_mut().enumerate() {
+            let y = mirror(y, row, rows).map_or(y, |mirror| y.min(mirror));
+            let x = mirror(x, col, cols).map_or(x, |mirror| x.min(mirror));
+            *entry = entries[y][x];
+        }
+    }
+    let xs = (0..cols)
+        .filter(|&x| mirror(x, col, cols).is_none())
+        .collect::<Vec<_>>();
+    let ys = (0..rows)
+        .filter(|&y| mirror(y, row, rows).is_some())
+        .collect::<Vec<_>>();
+    let (&x, &y) = (xs.choose(rng)?, ys.choose(rng)?);
+    grid[y][x] = if grid[y][x] == b'#' { b'.' } else { b'#' };
+    if rng.gen_bool(0.5) {
+        grid = (0..cols)
+            .map(|x| grid.iter().map(|grid_row| grid_row[x]).collect())
+            .collect();
+    }
+
+    let pattern = generate::grid(&grid);
+    let parsed = Pattern::try_from(pattern.as_bytes()).ok()?;
+    let lines = |smudges| {
+        let vertical = (0..parsed.grid.cols() - 1)
+            .filter(|&mid| parsed.is_vertical_reflection_line_at(mid, smudges))
+            .count();
+        let horizontal = (0..parsed.grid.rows() - 1)
+            .filter(|&mid| parsed.is_horizontal_reflection_line_at(mid, smudges))
+            .count();
+        vertical + horizontal
+    };
+    (lines(0) == 1 && lines(1) == 1).then_some(pattern)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::InputStore;
+
+    #[test]
+    fn test_with_sample() -> Result<()> {
+        let input = &InputStore::default().sample(2023, 13)?;
+        assert!(Solution.validate(input).is_empty());
+        let patterns = Patterns::from_input(input)?;
+        assert_eq!(patterns.solve(&Options::PART1), 405);
+        assert_eq!(patterns.solve(&Options::PART2), 400);
+        assert_eq!(
+            Solution.explain(&patterns)?[0].lines,
+            [
+                "  1 ( 7x9 ): columns 5|6    smudged: rows 3|4",
+                "  2 ( 7x9 ): rows 4|5       smudged: rows 1|2"
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_configured_smudges() -> Result<()> {
+        let input = &InputStore::default().sample(2023, 13)?;
+        let run = |params: &Params| -> Result<Option<Answer>> {
+            crate::Solver::parse_with(&Solution, input, params)?.part2()
+        };
+        assert_eq!(run(&Params::new())?, Some(400.into()));
+        assert_eq!(run(&Params::new().with("smudges", "0"))?, Some(405.into()));
+        assert!(run(&Params::new().with("smudges", "one")).is_err());
+        assert!(run(&Params::new().with("cycles", "1")).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,319 @@
+use core::fmt;
+use std::collections::HashMap;
+
+use anyhow::Result;
+
+use crate::{
+    generate::{self, Rng},
+    log,
+    solver::{self, FromInput, Puzzle},
+    util::{
+        grid::{Direction, Grid},
+        zobrist::Zobrist,
+    },
+    validate::{self, Violation},
+    Answer, FromParams, Params,
+};
+
+/// Parameters of the puzzle, e.g. `Options::PART2.cycles(3)`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+    cycles: usize,
+}
+
+impl Options {
+    pub const PART2: Options = Options {
+        cycles: 1_000_000_000,
+    };
+
+    /// Number of spin cycles
+    pub fn cycles(self, cycles: usize) -> Self {
+        Options { cycles }
+    }
+}
+
+// Parameters set the options of part 2
+impl FromParams for Options {
+    const KEYS: &'static [&'static str] = &["cycles"];
+
+    fn defaults() -> Self {
+        Options::PART2
+    }
+
+    fn set(&mut self, key: &str, value: &str) -> Result<()> {
+        match key {
+            "cycles" => *self = self.cycles(value.parse()?),
+            _ => anyhow::bail!("unknown option {}", key),
+        }
+        Ok(())
+    }
+}
+
+/// Entry of the platform
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Entry {
+    CubeRock,  // #
+    RoundRock, // O
+    Empty,     // .
+}
+
+impl TryFrom<u8> for Entry {
+    type Error = anyhow::Error;
+
+    fn try_from(value: u8) -> Result<Self> {
+        match value {
+            b'#' => Ok(Entry::CubeRock),
+            b'O' => Ok(Entry::RoundRock),
+            b'.' => Ok(Entry::Empty),
+            _ => anyhow::bail!("Invalid entry: {}", value),
+        }
+    }
+}
+
+impl fmt::Display for Entry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Entry::CubeRock => write!(f, "#"),
+            Entry::RoundRock => write!(f, "O"),
+            Entry::Empty => write!(f, "."),
+        }
+    }
+}
+
+/// Platform of rocks, which roll when it's tilted
+#[derive(Debug, Clone)]
+pub struct Platform {
+    entries: Grid<Entry>,
+    rows: usize,
+    cols: usize,
+    // hash of the positions of the round rocks, kept as they roll
+    zobrist: Zobrist,
+    hash: u64,
+    // options of part 2, the puzzle's unless configured otherwise
+    part2: Options,
+}
+
+impl FromInput<'_> for Platform {
+    fn from_input(s: &str) -> Result<Self> {
+        let entries = Grid::parse(s.as_bytes(), Entry::try_from)?;
+        Ok(Platform::from(entries))
+    }
+}
+
+impl From<Grid<Entry>> for Platform {
+    fn from(entries: Grid<Entry>) -> Self {
+        let (rows, cols) = (entries.rows(), entries.cols());
+        let zobrist = Zobrist::new(rows * cols, 1);
+        let round_rocks = entries
+            .iter()
+            .filter(|(_, entry)| **entry == Entry::RoundRock)
+            .map(|((row, col), _)| (row * cols + col, 0));
+        let hash = zobrist.hash(round_rocks);
+        Platform {
+            entries,
+            rows,
+            cols,
+            zobrist,
+            hash,
+            part2: Options::PART2,
+        }
+    }
+}
+
+impl fmt::Display for Platform {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} x {}: ", self.rows, self.cols)?;
+        write!(f, "{}", self.entries)
+    }
+}
+
+impl Platform {
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> Option<&Entry> {
+        self.entries.get((row, col))
+    }
+
+    /// Tilt north, then west, south and east
+    pub fn cycle(&mut self) {
+        self.tilt_north();
+        self.tilt_west();
+        self.tilt_south();
+        self.tilt_east();
+    }
+
+    pub fn tilt_north(&mut self) {
+        self.tilt(Direction::Up);
+    }
+
+    pub fn tilt_west(&mut self) {
+        self.tilt(Direction::Left);
+    }
+
+    pub fn tilt_south(&mut self) {
+        self.tilt(Direction::Down);
+    }
+
+    pub fn tilt_east(&mut self) {
+        self.tilt(Direction::Right);
+    }
+
+    // Roll the round rocks toward `dir` until they hit an edge, a cube rock
+    // or another round rock, rehashing their positions as they go
+    fn tilt(&mut self, dir: Direction) {
+        let moves = self.entries.slide(
+            dir,
+            |entry| *entry == Entry::CubeRock,
+            |entry| *entry == Entry::RoundRock,
+        );
+        for ((from_row, from_col), (to_row, to_col)) in moves {
+            self.hash = self
+                .zobrist
+                .toggle(self.hash, from_row * self.cols + from_col, 0);
+            self.hash = self
+                .zobrist
+                .toggle(self.hash, to_row * self.cols + to_col, 0);
+        }
+    }
+
+    /// Copy of the platform tilted north
+    pub fn tilted_north(&self) -> Self {
+        let mut grid = self.clone();
+        grid.tilt_north();
+        grid
+    }
+
+    /// Load on the north support beams after spinning the platform through
+    /// the given number of cycles. Platforms end up repeating themselves, so
+    /// only the cycles up to the first repeat and what remains of the last
+    /// period are run.
+    pub fn solve(&self, options: &Options) -> usize {
+        let mut grid = self.clone();
+        let mut seen = HashMap::new();
+        let mut cycle = 0;
+        while cycle < options.cycles {
+            // only the round rocks move, so their hash tells platforms apart
+            if let Some(start) = seen.insert(grid.hash, cycle) {
+                let period = cycle - start;
+                log::debug!("grid after {} cycles repeats every {}", start, period);
+                for _ in 0..(options.cycles - cycle) % period {
+                    grid.cycle();
+                }
+                break;
+            }
+            grid.cycle();
+            log::debug!("grid after {} cycle has load {}", cycle + 1, grid.load());
+            cycle += 1;
+        }
+        grid.load()
+    }
+
+    /// Total load on the north support beams
+    pub fn load(&self) -> usize {
+        let mut sum = 0;
+        for ((row, _), entry) in self.entries.iter() {
+            match entry {
+                Entry::CubeRock => {}
+                Entry::RoundRock => sum += self.rows - row,
+                Entry::Empty => {}
+            }
+        }
+        sum
+    }
+}
+
+pub struct Solution;
+
+solver::register!(Solution);
+
+impl Puzzle for Solution {
+    type Input<'a> = Platform;
+
+    fn year(&self) -> u16 {
+        2023
+    }
+
+    fn day(&self) -> u8 {
+        14
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Self::Input<'a>> {
+        let grid = Platform::from_input(input)?;
+        log::debug!("original grid:\n{}", grid);
+        Ok(grid)
+    }
+
+    fn configure(&self, grid: &mut Platform, params: &Params) -> Result<()> {
+        grid.part2 = params.options(self.day())?;
+        Ok(())
+    }
+
+    fn part1(&self, grid: &Platform) -> Result<Option<Answer>> {
+        let grid = grid.tilted_north();
+        log::debug!("grid after being tilted north:\n{}", grid);
+        let part1 = grid.load();
+        Ok(Some(part1.into()))
+    }
+
+    fn part2(&self, grid: &Platform) -> Result<Option<Answer>> {
+        let part2 = grid.solve(&grid.part2);
+        Ok(Some(part2.into()))
+    }
+
+    // The platform is a rectangle of rounded rocks, cube rocks and empty
+    // space
+    fn validate(&self, input: &str) -> Vec<Violation> {
+        validate::grid(validate::lines(input), |c| "O#.".contains(c))
+    }
+
+    // Rounded rocks, cube rocks and empty space at about the odds of the real
+    // platforms
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        let platform = generate::tiles(
+            rng,
+            100 * size,
+            100 * size,
+            &[(b'.', 14), (b'O', 4), (b'#', 2)],
+        );
+        Some(generate::grid(&platform))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::InputStore;
+
+    #[test]
+    fn test_with_sample() -> Result<()> {
+        let input = &InputStore::default().sample(2023, 14)?;
+        assert!(Solution.validate(input).is_empty());
+        let grid = Platform::from_input(input)?;
+        assert_eq!(grid.tilted_north().load(), 136);
+        assert_eq!(grid.tilted_north().load(), 136);
+
+        let mut grid = Platform::from_input(input)?;
+        grid.cycle();
+        assert_eq!(grid.get(0, 5), Some(&Entry::CubeRock));
+        assert_eq!(grid.get(1, 8), Some(&Entry::RoundRock));
+        // the hash kept through the cycle is the hash from scratch
+        assert_eq!(grid.hash, Platform::from(grid.entries.clone()).hash);
+
+        let grid = Platform::from_input(input)?;
+        assert_eq!(grid.solve(&Options::PART2.cycles(1)), 87);
+        assert_eq!(grid.solve(&Options::PART2), 64);
+
+        let run = |params: &Params| -> Result<Option<Answer>> {
+            crate::Solver::parse_with(&Solution, input, params)?.part2()
+        };
+        assert_eq!(run(&Params::new().with("cycles", "1"))?, Some(87.into()));
+        assert!(run(&Params::new().with("cycles", "1e9")).is_err());
+        Ok(())
+    }
+}
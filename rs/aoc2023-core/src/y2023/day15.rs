@@ -0,0 +1,226 @@
+use anyhow::Result;
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{alpha1, digit0},
+    combinator::map_res,
+    sequence::tuple,
+    IResult,
+};
+use rand::{seq::SliceRandom, Rng as _};
+
+use crate::{
+    error,
+    generate::Rng,
+    solver::{self, FromInput, Puzzle},
+    Answer,
+};
+
+#[derive(Debug, Clone, Copy)]
+enum Op {
+    Remove,
+    Add(usize),
+}
+
+fn parse_digit(input: &str) -> IResult<&str, usize> {
+    let (input, digit) = map_res(digit0, |digit: &str| digit.parse::<usize>())(input)?;
+    Ok((input, digit))
+}
+
+fn parse_remove_op(input: &str) -> IResult<&str, Op> {
+    let (input, _) = tag("-")(input)?;
+    Ok((input, Op::Remove))
+}
+
+fn parse_add_op(input: &str) -> IResult<&str, Op> {
+    let (input, _) = tag("=")(input)?;
+    let (input, num) = parse_digit(input)?;
+    Ok((input, Op::Add(num)))
+}
+
+fn parse_op(input: &str) -> IResult<&str, Op> {
+    let (input, op) = alt((parse_remove_op, parse_add_op))(input)?;
+    Ok((input, op))
+}
+
+fn parse_step(s: &str) -> IResult<&str, Step<'_>> {
+    let (input, (label, op)) = tuple((alpha1, parse_op))(s)?;
+    Ok((
+        input,
+        Step {
+            // the step as parsed, without whitespace after it, e.g. the
+            // newline ending the input
+            inner: &s[..s.len() - input.len()],
+            label,
+            op,
+        },
+    ))
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Step<'a> {
+    inner: &'a str,
+    label: &'a str,
+    op: Op,
+}
+
+impl<'a> TryFrom<&'a str> for Step<'a> {
+    type Error = anyhow::Error;
+
+    fn try_from(s: &'a str) -> Result<Self> {
+        Ok(error::parse(Solution.day(), s, parse_step)?)
+    }
+}
+
+impl<'a> Step<'a> {
+    fn hash(bytes: &[u8]) -> usize {
+        let mut hash = 0usize;
+        for &b in bytes {
+            hash = hash.wrapping_add(b as usize);
+            hash = hash.wrapping_mul(17);
+            hash = hash.wrapping_rem(256);
+        }
+        hash
+    }
+
+    fn hash_step(&self) -> usize {
+        Step::hash(self.inner.as_bytes())
+    }
+
+    fn hash_label(&self) -> usize {
+        Step::hash(self.label.as_bytes())
+    }
+}
+
+/// Steps of the initialization sequence, borrowing their labels from the
+/// input
+#[derive(Debug)]
+pub struct Steps<'a>(Vec<Step<'a>>);
+
+impl<'a> FromInput<'a> for Steps<'a> {
+    fn from_input(s: &'a str) -> Result<Self> {
+        let steps = s
+            .split(',')
+            .map(|step| error::parse_within(Solution.day(), s, step, parse_step))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Steps(steps))
+    }
+}
+
+impl<'a> Steps<'a> {
+    fn sum_of_hashes(&self) -> usize {
+        self.0.iter().map(|s| s.hash_step()).sum()
+    }
+
+    fn run(&self) -> usize {
+        // lenses in each box, as their label borrowed from the input and
+        // focal length
+        let mut boxes: Vec<Vec<(&str, usize)>> = vec![vec![]; 256];
+        for step in &self.0 {
+            let lenses = &mut boxes[step.hash_label()];
+            let idx = lenses.iter().position(|&(label, _)| label == step.label);
+            match (step.op, idx) {
+                (Op::Remove, Some(idx)) => {
+                    lenses.remove(idx);
+                }
+                (Op::Remove, None) => {}
+                (Op::Add(focal_length), Some(idx)) => lenses[idx].1 = focal_length,
+                (Op::Add(focal_length), None) => lenses.push((step.label, focal_length)),
+            }
+        }
+
+        let mut power = 0;
+        for (bx_idx, bx) in boxes.iter().enumerate() {
+            for (lens_idx, (_, focal_length)) in bx.iter().enumerate() {
+                power += (bx_idx + 1) * (lens_idx + 1) * focal_length;
+            }
+        }
+        power
+    }
+}
+
+pub struct Solution;
+
+solver::register!(Solution);
+
+impl Puzzle for Solution {
+    type Input<'a> = Steps<'a>;
+
+    fn year(&self) -> u16 {
+        2023
+    }
+
+    fn day(&self) -> u8 {
+        15
+    }
+
+    fn part1(&self, steps: &Steps<'_>) -> Result<Option<Answer>> {
+        let part1 = steps.sum_of_hashes();
+        Ok(Some(part1.into()))
+    }
+
+    fn part2(&self, steps: &Steps<'_>) -> Result<Option<Answer>> {
+        let part2 = steps.run();
+        Ok(Some(part2.into()))
+    }
+
+    // Steps on a few hundred labels, so the same lenses come and go
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        let labels = (0..500 * size)
+            .map(|_| {
+                (0..rng.gen_range(2..=6))
+                    .map(|_| char::from(rng.gen_range(b'a'..=b'z')))
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>();
+        let steps = (0..4000 * size).map(|_| {
+            let label = labels.choose(rng).unwrap();
+            if rng.gen_bool(0.3) {
+                format!("{}-", label)
+            } else {
+                format!("{}={}", label, rng.gen_range(1..=9))
+            }
+        });
+        Some(steps.collect::<Vec<_>>().join(","))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::InputStore;
+
+    #[test]
+    fn test_hash() -> Result<()> {
+        let step = Step::try_from("rn=1")?;
+        assert_eq!(step.hash_step(), 30);
+        assert_eq!(step.hash_label(), 0);
+
+        let step = Step::try_from("cm-")?;
+        assert_eq!(step.hash_step(), 253);
+        assert_eq!(step.hash_label(), 0);
+
+        let step = Step::try_from("qp=3")?;
+        assert_eq!(step.hash_step(), 97);
+        assert_eq!(step.hash_label(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_with_sample() -> Result<()> {
+        let input = &InputStore::default().sample(2023, 15)?;
+        let steps = Steps::from_input(input)?;
+        let part1 = steps.sum_of_hashes();
+        assert_eq!(part1, 1320);
+
+        let part2 = steps.run();
+        assert_eq!(part2, 145);
+
+        // as downloaded, with a newline at the end, which isn't hashed
+        let input = format!("{}\n", input);
+        let steps = Steps::from_input(&input)?;
+        assert_eq!(steps.sum_of_hashes(), 1320);
+        assert_eq!(steps.run(), 145);
+        Ok(())
+    }
+}
@@ -0,0 +1,347 @@
+use core::fmt;
+
+use anyhow::Result;
+
+use crate::{
+    generate::{self, Rng},
+    log, par,
+    solver::{self, FromInput, Puzzle},
+    util::grid::Grid,
+    validate::{self, Violation},
+    Answer,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Entry {
+    Empty,              // .
+    RightMirror,        // /
+    LeftMirror,         // \
+    VerticalSplitter,   // |
+    HorizontalSplitter, // -
+}
+
+impl TryFrom<u8> for Entry {
+    type Error = anyhow::Error;
+
+    fn try_from(b: u8) -> Result<Self> {
+        match b {
+            b'.' => Ok(Entry::Empty),
+            b'/' => Ok(Entry::RightMirror),
+            b'\\' => Ok(Entry::LeftMirror),
+            b'|' => Ok(Entry::VerticalSplitter),
+            b'-' => Ok(Entry::HorizontalSplitter),
+            _ => Err(anyhow::anyhow!("Invalid entry: {}", b as char)),
+        }
+    }
+}
+
+impl fmt::Display for Entry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Entry::Empty => write!(f, "."),
+            Entry::RightMirror => write!(f, "/"),
+            Entry::LeftMirror => write!(f, "\\"),
+            Entry::VerticalSplitter => write!(f, "|"),
+            Entry::HorizontalSplitter => write!(f, "-"),
+        }
+    }
+}
+
+/// Contraption of mirrors and splitters
+#[derive(Debug)]
+pub struct Contraption {
+    entries: Grid<Entry>,
+    rows: usize,
+    cols: usize,
+}
+
+impl FromInput<'_> for Contraption {
+    fn from_input(s: &str) -> Result<Self> {
+        let entries = Grid::parse(s.as_bytes(), Entry::try_from)?;
+        let (rows, cols) = (entries.rows(), entries.cols());
+        Ok(Contraption {
+            entries,
+            rows,
+            cols,
+        })
+    }
+}
+
+impl fmt::Display for Contraption {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} x {}", self.rows, self.cols)?;
+        write!(f, "{}", self.entries)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+#[derive(Debug)]
+struct Traverse<'a> {
+    grid: &'a Contraption,
+    // directions of the beams through each tile so far, a bit per direction
+    visited: Grid<u8>,
+}
+
+impl fmt::Display for Traverse<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.visited.iter_rows() {
+            for &dirs in row {
+                write!(f, "{}", if dirs == 0 { '·' } else { '#' })?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Traverse<'a> {
+    fn new(grid: &'a Contraption) -> Self {
+        Self {
+            grid,
+            visited: Grid::filled(grid.rows, grid.cols, 0),
+        }
+    }
+
+    // Follow the beam entering (`row`, `col`) going `dir`, and the beams it
+    // splits into, until they leave the grid or retrace their steps. Beams
+    // still to follow are kept on a stack rather than recursed into, as a
+    // beam can run through every tile.
+    fn traverse(&mut self, row: isize, col: isize, dir: Direction) {
+        let mut beams = vec![(row, col, dir)];
+        while let Some((row, col, dir)) = beams.pop() {
+            self.step(row, col, dir, &mut beams);
+        }
+    }
+
+    // Energize (`row`, `col`) by a beam going `dir`, pushing the beams
+    // leaving it onto `beams`
+    fn step(
+        &mut self,
+        row: isize,
+        col: isize,
+        dir: Direction,
+        beams: &mut Vec<(isize, isize, Direction)>,
+    ) {
+        if row < 0 || col < 0 || row >= self.grid.rows as isize || col >= self.grid.cols as isize {
+            log::debug!("done: row={}, col={}, dir={:?}", row, col, dir);
+            return;
+        }
+
+        let row_usize = row as usize;
+        let col_usize = col as usize;
+        // mark (row, col) as visited going `dir`
+        let dirs = &mut self.visited[(row_usize, col_usize)];
+        if *dirs & 1 << dir as u8 != 0 {
+            log::debug!("already visited: row={}, col={}, dir={:?}", row, col, dir);
+            return;
+        }
+        *dirs |= 1 << dir as u8;
+
+        let entry = &self.grid.entries[(row_usize, col_usize)];
+        match (dir, entry) {
+            // up
+            (Direction::Up, Entry::Empty) => {
+                beams.push((row - 1, col, Direction::Up));
+            }
+            (Direction::Up, Entry::VerticalSplitter) => {
+                beams.push((row - 1, col, Direction::Up));
+            }
+            (Direction::Up, Entry::HorizontalSplitter) => {
+                beams.push((row, col - 1, Direction::Left));
+                beams.push((row, col + 1, Direction::Right));
+            }
+            (Direction::Up, Entry::LeftMirror) => {
+                beams.push((row, col - 1, Direction::Left));
+            }
+            (Direction::Up, Entry::RightMirror) => {
+                beams.push((row, col + 1, Direction::Right));
+            }
+
+            // right
+            (Direction::Right, Entry::Empty) => {
+                beams.push((row, col + 1, Direction::Right));
+            }
+            (Direction::Right, Entry::VerticalSplitter) => {
+                beams.push((row - 1, col, Direction::Up));
+                beams.push((row + 1, col, Direction::Down));
+            }
+            (Direction::Right, Entry::HorizontalSplitter) => {
+                beams.push((row, col + 1, Direction::Right));
+            }
+            (Direction::Right, Entry::LeftMirror) => {
+                beams.push((row + 1, col, Direction::Down));
+            }
+            (Direction::Right, Entry::RightMirror) => {
+                beams.push((row - 1, col, Direction::Up));
+            }
+
+            // down
+            (Direction::Down, Entry::Empty) => {
+                beams.push((row + 1, col, Direction::Down));
+            }
+            (Direction::Down, Entry::VerticalSplitter) => {
+                beams.push((row + 1, col, Direction::Down));
+            }
+            (Direction::Down, Entry::HorizontalSplitter) => {
+                beams.push((row, col - 1, Direction::Left));
+                beams.push((row, col + 1, Direction::Right));
+            }
+            (Direction::Down, Entry::LeftMirror) => {
+                beams.push((row, col + 1, Direction::Right));
+            }
+            (Direction::Down, Entry::RightMirror) => {
+                beams.push((row, col - 1, Direction::Left));
+            }
+
+            // left
+            (Direction::Left, Entry::Empty) => {
+                beams.push((row, col - 1, Direction::Left));
+            }
+            (Direction::Left, Entry::VerticalSplitter) => {
+                beams.push((row - 1, col, Direction::Up));
+                beams.push((row + 1, col, Direction::Down));
+            }
+            (Direction::Left, Entry::HorizontalSplitter) => {
+                beams.push((row, col - 1, Direction::Left));
+            }
+            (Direction::Left, Entry::LeftMirror) => {
+                beams.push((row - 1, col, Direction::Up));
+            }
+            (Direction::Left, Entry::RightMirror) => {
+                beams.push((row + 1, col, Direction::Down));
+            }
+        }
+    }
+
+    fn energized(&self) -> usize {
+        self.visited.iter().filter(|&(_, &dirs)| dirs != 0).count()
+    }
+}
+
+pub struct Solution;
+
+solver::register!(Solution);
+
+impl Puzzle for Solution {
+    type Input<'a> = Contraption;
+
+    fn year(&self) -> u16 {
+        2023
+    }
+
+    fn day(&self) -> u8 {
+        16
+    }
+
+    fn parse<'a>(&self, input: &'a str) -> Result<Self::Input<'a>> {
+        let grid = Contraption::from_input(input)?;
+        log::debug!("grid:\n{}", grid);
+        Ok(grid)
+    }
+
+    fn part1(&self, grid: &Contraption) -> Result<Option<Answer>> {
+        let mut traverser = Traverse::new(grid);
+        traverser.traverse(0, 0, Direction::Right);
+        log::debug!("after traversal:\n{}", traverser);
+        let part1 = traverser.energized();
+        Ok(Some(part1.into()))
+    }
+
+    fn part2(&self, grid: &Contraption) -> Result<Option<Answer>> {
+        let mut starts = vec![];
+        // an empty contraption has no edges to start from
+        if grid.rows == 0 || grid.cols == 0 {
+            return Ok(Some(0.into()));
+        }
+        for col in 0..grid.cols {
+            starts.push((0, col, Direction::Down));
+            starts.push((grid.rows - 1, col, Direction::Up));
+        }
+        for row in 0..grid.rows {
+            starts.push((row, 0, Direction::Right));
+            starts.push((row, grid.cols - 1, Direction::Left));
+        }
+
+        // beams from each start traversed in parallel
+        let energized = par::map(&starts, |&(row, col, dir)| {
+            let mut traverser = Traverse::new(grid);
+            traverser.traverse(row as isize, col as isize, dir);
+            traverser.energized()
+        });
+        let part2 = energized.into_iter().max().unwrap_or(0);
+        Ok(Some(part2.into()))
+    }
+
+    // The contraption is a rectangle of mirrors, splitters and empty space
+    fn validate(&self, input: &str) -> Vec<Violation> {
+        validate::grid(validate::lines(input), |c| r".|-/\".contains(c))
+    }
+
+    // Empty space with mirrors and splitters scattered over it
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        let contraption = generate::tiles(
+            rng,
+            50 * size,
+            50 * size,
+            &[(b'.', 36), (b'|', 1), (b'-', 1), (b'/', 1), (b'\\', 1)],
+        );
+        Some(generate::grid(&contraption))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::InputStore;
+
+    #[test]
+    fn test_with_sample() -> Result<()> {
+        let input = &InputStore::default().sample(2023, 16)?;
+        assert!(Solution.validate(input).is_empty());
+        let grid = Contraption::from_input(input)?;
+
+        let mut traverser = Traverse::new(&grid);
+        traverser.traverse(0, 0, Direction::Right);
+        let part1 = traverser.energized();
+        assert_eq!(part1, 46);
+        Ok(())
+    }
+
+    // A beam across a row longer than the stack is deep, were it recursed
+    // into step by step
+    #[test]
+    fn test_long_beam() -> Result<()> {
+        let grid = Contraption::from_input(&".".repeat(200_000))?;
+        let mut traverser = Traverse::new(&grid);
+        traverser.traverse(0, 0, Direction::Right);
+        assert_eq!(traverser.energized(), 200_000);
+        Ok(())
+    }
+
+    #[test]
+    fn test_empty() -> Result<()> {
+        for input in ["", "\n"] {
+            let grid = Contraption::from_input(input)?;
+            assert_eq!(Solution.part1(&grid)?, Some(0.into()));
+            assert_eq!(Solution.part2(&grid)?, Some(0.into()));
+        }
+
+        // columns but no rows, or rows but no columns
+        for (rows, cols) in [(0, 5), (5, 0)] {
+            let grid = Contraption {
+                entries: Grid::filled(rows, cols, Entry::Empty),
+                rows,
+                cols,
+            };
+            assert_eq!(Solution.part2(&grid)?, Some(0.into()));
+        }
+        Ok(())
+    }
+}